@@ -15,7 +15,7 @@ use crate::{
     results_builder::{build_empty_results, build_simple_results},
     results_data::ResultsData,
     value::Value,
-    writer::{Writer, new_csv_writer},
+    writer::{Writer, new_delimited_csv_writer},
 };
 
 pub(crate) fn alter(
@@ -39,12 +39,8 @@ pub(crate) fn alter(
     let current_data = read_file(engine, name);
     let mut current_data = match current_data {
         Ok(data) => data,
-        Err(CvsSqlError::TableNotExists(_)) => {
-            if if_exists {
-                return build_empty_results(&["action", "table", "file"]);
-            } else {
-                return current_data;
-            }
+        Err(CvsSqlError::TableNotExists(_)) if if_exists => {
+            return build_empty_results(&["action", "table", "file"]);
         }
         _ => {
             return current_data;
@@ -87,12 +83,18 @@ pub(crate) fn alter(
         return Err(CvsSqlError::ReadOnlyMode);
     }
 
-    let file = OpenOptions::new()
-        .write(true)
-        .truncate(true)
-        .open(table_file.path)?;
-    let mut writer = new_csv_writer(file, engine.first_line_as_name);
-    writer.write(&current_data)?;
+    if engine.should_write(&table_file) {
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .open(&table_file.path)?;
+        let mut writer = new_delimited_csv_writer(
+            file,
+            engine.first_line_as_name,
+            engine.delimiter(&table_file.path),
+        );
+        writer.write(&current_data)?;
+    }
 
     build_simple_results(vec![
         ("action", Value::Str("ALTERED".to_string())),
@@ -17,6 +17,19 @@ pub enum OutputFormat {
     Json,
     /// Excel sheet
     Xls,
+    /// Markdown tables
+    Markdown,
+    /// SQL INSERT statements
+    Sql,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+#[clap(rename_all = "kebab_case")]
+pub enum NumericLocale {
+    /// Comma as thousands separator and dot as decimal point (1,234.56)
+    Us,
+    /// Dot as thousands separator and comma as decimal point (1.234,56)
+    Eu,
 }
 
 #[derive(Parser, Debug, Default)]
@@ -32,10 +45,22 @@ pub struct Args {
     #[arg(short, long)]
     pub command: Option<Vec<String>>,
 
+    /// Check the commands without running them on the data: tables are read up to their header line, write statements do not change any file and no results are printed, only the errors
+    #[arg(long, default_value_t = false)]
+    pub validate: bool,
+
+    /// Report a failing statement and continue to the next one instead of stopping (the exit code will still be non-zero)
+    #[arg(long, default_value_t = false)]
+    pub continue_on_error: bool,
+
     /// Use Excel-like column names (if not set, the first line of each file will be used as column names)
     #[arg(short, long, default_value_t = false)]
     pub first_line_as_data: bool,
 
+    /// Read the files without a header line, naming the columns `col1`, `col2`, ... (like `-f`, but with numbered column names)
+    #[arg(long, default_value_t = false)]
+    pub no_headers: bool,
+
     /// Home directory (base path for CSV files and databases). Defaults to current directory.
     #[arg(short = 'm', long)]
     #[arg(value_hint = clap::ValueHint::DirPath)]
@@ -50,15 +75,96 @@ pub struct Args {
     #[arg(value_hint = clap::ValueHint::DirPath)]
     pub output: Option<PathBuf>,
 
-    /// Output format when saving to files
+    /// Output format when saving to files (TXT, HTML and JSON are written to standard output if no output directory is set)
     #[arg(short='p', long, value_enum, default_value_t=OutputFormat::Csv)]
     pub output_format: OutputFormat,
 
+    /// Add a second header line with the type of every column to the CSV and TXT outputs
+    #[arg(long, default_value_t = false)]
+    pub typed_header: bool,
+
+    /// Delimiter of the CSV outputs (a single character, like ';'). Defaults to a comma
+    #[arg(long)]
+    pub delimiter: Option<char>,
+
+    /// Compress the CSV, TXT and JSON output files with gzip (adding a `.gz` extension). Requires `--output`
+    #[arg(long, default_value_t = false)]
+    pub gzip: bool,
+
+    /// String to write for empty (NULL) values in the CSV, TXT and table outputs (like '\N'). Defaults to an empty string
+    #[arg(long, default_value = "")]
+    pub null_string: String,
+
+    /// Write integral numbers without a decimal point (so `12.00` is written as `12`)
+    #[arg(long, default_value_t = false)]
+    pub integers: bool,
+
+    /// Print only the value of results with a single row and a single column (fails for any other results)
+    #[arg(long, default_value_t = false)]
+    pub scalar: bool,
+
     /// Display output as CSV in console instead of as a table (valid only in console mode)
     #[arg(short, long, default_value_t = false)]
     pub display_as_csv: bool,
 
+    /// Display every column of the results on its own line instead of as a table (valid only in console mode)
+    #[arg(short = 'x', long, default_value_t = false)]
+    pub expanded: bool,
+
     /// Enable write mode to allow modifying files
     #[arg(short, long, default_value_t = false)]
     pub write_mode: bool,
+
+    /// Parse numbers with thousands separators using the given locale
+    #[arg(long, value_enum)]
+    pub numeric_locale: Option<NumericLocale>,
+
+    /// Parse numbers with currency symbols (like $ or €) as numbers
+    #[arg(long, default_value_t = false)]
+    pub strip_currency: bool,
+
+    /// Read the given columns as text, even if they look like numbers (to keep leading zeros)
+    #[arg(long, value_delimiter = ',')]
+    pub string_columns: Vec<String>,
+
+    /// Delimiter of the CSV files to read (a single character, like ';'). Defaults to a tab for `.tsv` files and to a comma otherwise
+    #[arg(long)]
+    pub input_delimiter: Option<char>,
+
+    /// Ignore the lines of the CSV files that start with this character (like '#')
+    #[arg(long)]
+    pub comment: Option<char>,
+
+    /// Read cells with this exact content (like 'NA' or 'NULL') as empty (NULL) values. Can be repeated
+    #[arg(long)]
+    pub null_token: Vec<String>,
+
+    /// Read all the columns as text, without inferring numbers from the cells
+    #[arg(long, alias = "no-infer", default_value_t = false)]
+    pub all_text: bool,
+
+    /// Read quoted empty fields (like `""`) as empty strings instead of NULL
+    #[arg(long, default_value_t = false)]
+    pub empty_as_string: bool,
+
+    /// Remove the whitespace around the column names in the header line
+    #[arg(long, default_value_t = false)]
+    pub trim_headers: bool,
+
+    /// Convert the column names in the header line to lower case
+    #[arg(long, default_value_t = false)]
+    pub lower_headers: bool,
+
+    /// TOML file that maps column names to types (string, number, bool, date or timestamp)
+    #[arg(long)]
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    pub schema: Option<PathBuf>,
+
+    /// Seed for the random number generator (for reproducible RANDOM and TABLESAMPLE results)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Number of threads to read the files of a multi-file table (a table name with `*` or `?`, like `"sales_*"`)
+    #[arg(long, default_value_t = 1)]
+    pub threads: usize,
 }
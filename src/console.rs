@@ -8,9 +8,12 @@ use reedline::{
     Signal, ValidationResult, Validator, default_emacs_keybindings,
 };
 
-use crate::engine::Engine;
+use sqlparser::ast::Statement;
+
+use crate::engine::{CommandExecution, Engine};
 use crate::error::CvsSqlError;
 use crate::outputer::Outputer;
+use crate::streaming::stream_statement;
 
 struct EolValidator {}
 impl Validator for EolValidator {
@@ -37,6 +40,56 @@ pub fn work_on_console(
         stdout(engine, outputer)
     }
 }
+
+/// Executes the commands, writing the results of each statement. Returns `false` if any
+/// statement failed (which is only possible with `continue_on_error`).
+pub fn execute_commands(
+    engine: &Engine,
+    commands: &[String],
+    continue_on_error: bool,
+    outputer: &mut dyn Outputer,
+) -> Result<bool, CvsSqlError> {
+    let mut success = true;
+    for command in commands {
+        let statements = match engine.parse(command) {
+            Ok(statements) => statements,
+            Err(err) if continue_on_error => {
+                eprintln!("Got error: {err}");
+                success = false;
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        for statement in statements {
+            match execute_statement(engine, &statement, outputer) {
+                Ok(Some(out)) => println!("{out}"),
+                Ok(None) => {}
+                Err(err) if continue_on_error => {
+                    eprintln!("Statement `{statement}` failed: {err}");
+                    success = false;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+    Ok(success)
+}
+
+/// Executes a single statement and writes its results. Simple queries are streamed to the outputer
+/// without reading the entire table into memory.
+fn execute_statement(
+    engine: &Engine,
+    statement: &Statement,
+    outputer: &mut dyn Outputer,
+) -> Result<Option<String>, CvsSqlError> {
+    let sql = statement.to_string();
+    if let Some(rows) = stream_statement(engine, statement)? {
+        return outputer.write_stream(&sql, rows);
+    }
+    let results = engine.extract_statement(statement)?;
+    outputer.write(&CommandExecution { sql, results })
+}
+
 struct ReadlineRepl {
     editor: Reedline,
 }
@@ -162,11 +215,16 @@ fn repl(
             Some(command) => {
                 let command = command.replace("\\\n", "\n");
 
-                match engine.execute_commands(&command) {
-                    Ok(results) => {
-                        for results in results {
-                            if let Some(out) = outputer.write(&results)? {
-                                repl.print_output(&out);
+                match engine.parse(&command) {
+                    Ok(statements) => {
+                        for statement in statements {
+                            match execute_statement(engine, &statement, outputer) {
+                                Ok(Some(out)) => repl.print_output(&out),
+                                Ok(None) => {}
+                                Err(e) => {
+                                    repl.print_error(e);
+                                    break;
+                                }
                             }
                         }
                     }
@@ -218,10 +276,7 @@ mod tests {
         }
     }
     impl Outputer for TestOutputer {
-        fn write(
-            &mut self,
-            _: &crate::engine::CommandExecution,
-        ) -> Result<Option<String>, CvsSqlError> {
+        fn write(&mut self, _: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
             let ret = self.results.get(self.results_count).cloned();
             self.results_count += 1;
             Ok(ret)
@@ -269,4 +324,54 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn continue_on_error() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+        let mut outputer = TestOutputer::default();
+        let commands = vec![
+            "SELECT * FROM tests.data.artists; SELECT * FROM tests.data.no_such_table; SELECT COUNT(*) FROM tests.data.artists".to_string(),
+        ];
+
+        let success = execute_commands(&engine, &commands, true, &mut outputer)?;
+
+        assert!(!success);
+        assert_eq!(outputer.results_count, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn stop_on_error() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+        let mut outputer = TestOutputer::default();
+        let commands = vec![
+            "SELECT * FROM tests.data.artists".to_string(),
+            "SELECT * FROM tests.data.no_such_table".to_string(),
+            "SELECT COUNT(*) FROM tests.data.artists".to_string(),
+        ];
+
+        let results = execute_commands(&engine, &commands, false, &mut outputer);
+
+        assert!(matches!(results, Err(CvsSqlError::TableNotExists(_))));
+        assert_eq!(outputer.results_count, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn execute_commands_without_errors() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+        let mut outputer = TestOutputer::default();
+        let commands = vec![
+            "SELECT * FROM tests.data.artists; SELECT COUNT(*) FROM tests.data.artists".to_string(),
+        ];
+
+        let success = execute_commands(&engine, &commands, true, &mut outputer)?;
+
+        assert!(success);
+        assert_eq!(outputer.results_count, 2);
+
+        Ok(())
+    }
 }
@@ -13,7 +13,7 @@ use crate::results::ResultSet;
 use crate::results_builder::build_simple_results;
 use crate::results_data::ResultsData;
 use crate::value::Value;
-use crate::writer::{Writer, new_csv_writer};
+use crate::writer::{Writer, new_delimited_csv_writer};
 
 impl Extractor for CreateTable {
     fn extract(&self, engine: &Engine) -> Result<ResultSet, CvsSqlError> {
@@ -204,7 +204,7 @@ impl Extractor for CreateTable {
             let mut metadata = SimpleResultSetMetadata::new(None);
             for col in &self.columns {
                 AvailableDataTypes::try_from(&col.data_type)?;
-                metadata.add_column(&col.name.to_string());
+                metadata.add_column(&col.name.value);
             }
             let metadata = Rc::new(metadata.build());
             ResultSet {
@@ -235,12 +235,16 @@ impl Extractor for CreateTable {
             if !self.if_not_exists {
                 return Err(CvsSqlError::TableAlreadyExists(table_name));
             }
-        } else {
+        } else if engine.should_write(&file) {
             if let Some(parent) = file.path.parent() {
                 fs::create_dir_all(parent)?;
             }
-            let writer = File::create(file.path)?;
-            let mut writer = new_csv_writer(writer, engine.first_line_as_name);
+            let writer = File::create(&file.path)?;
+            let mut writer = new_delimited_csv_writer(
+                writer,
+                engine.first_line_as_name,
+                engine.delimiter(&file.path),
+            );
             writer.write(&data)?;
         }
 
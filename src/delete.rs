@@ -13,7 +13,7 @@ use crate::{
     results_builder::build_simple_results,
     results_data::ResultsData,
     value::Value,
-    writer::{Writer, new_csv_writer},
+    writer::{Writer, new_delimited_csv_writer},
 };
 
 impl Extractor for Delete {
@@ -101,12 +101,18 @@ impl Extractor for Delete {
         let data = ResultsData::new(new_data);
         let results = ResultSet { metadata, data };
 
-        let file = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .open(table_file.path)?;
-        let mut writer = new_csv_writer(file, engine.first_line_as_name);
-        writer.write(&results)?;
+        if engine.should_write(&table_file) {
+            let file = OpenOptions::new()
+                .write(true)
+                .truncate(true)
+                .open(&table_file.path)?;
+            let mut writer = new_delimited_csv_writer(
+                file,
+                engine.first_line_as_name,
+                engine.delimiter(&table_file.path),
+            );
+            writer.write(&results)?;
+        }
 
         build_simple_results(vec![
             ("action", Value::Str("DELETED".to_string())),
@@ -1,4 +1,8 @@
+use sqlparser::ast::{BinaryOperator, CeilFloorKind, Expr, Value};
 use sqlparser::dialect::Dialect;
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::tokenizer::Token;
 
 #[derive(Debug)]
 pub struct FilesDialect {}
@@ -39,4 +43,71 @@ impl Dialect for FilesDialect {
     fn supports_insert_set(&self) -> bool {
         true
     }
+
+    fn supports_group_by_expr(&self) -> bool {
+        true
+    }
+    fn supports_group_by_with_modifier(&self) -> bool {
+        true
+    }
+    fn supports_select_wildcard_rename(&self) -> bool {
+        true
+    }
+    fn supports_filter_during_aggregation(&self) -> bool {
+        true
+    }
+
+    fn parse_infix(
+        &self,
+        parser: &mut Parser,
+        expr: &Expr,
+        precedence: u8,
+    ) -> Option<Result<Expr, ParserError>> {
+        if !parser.parse_keyword(Keyword::DIV) {
+            return None;
+        }
+        Some(
+            parser
+                .parse_subexpr(precedence)
+                .map(|right| Expr::BinaryOp {
+                    left: Box::new(expr.clone()),
+                    op: BinaryOperator::MyIntegerDivide,
+                    right: Box::new(right),
+                }),
+        )
+    }
+
+    fn parse_prefix(&self, parser: &mut Parser) -> Option<Result<Expr, ParserError>> {
+        if !parser.peek_keyword(Keyword::CEIL) && !parser.peek_keyword(Keyword::FLOOR) {
+            return None;
+        }
+        parser
+            .maybe_parse(parse_negative_scale_ceil_floor)
+            .transpose()
+    }
+}
+
+/// The parser only accepts a positive scale for `CEIL` and `FLOOR` (like `CEIL(x, 2)`), this
+/// allows a negative one as well (like `CEIL(x, -2)`).
+fn parse_negative_scale_ceil_floor(parser: &mut Parser) -> Result<Expr, ParserError> {
+    let is_ceil = parser.parse_keyword(Keyword::CEIL);
+    if !is_ceil {
+        parser.expect_keyword(Keyword::FLOOR)?;
+    }
+    parser.expect_token(&Token::LParen)?;
+    let expr = Box::new(parser.parse_expr()?);
+    parser.expect_token(&Token::Comma)?;
+    parser.expect_token(&Token::Minus)?;
+    let Value::Number(scale, long) = parser.parse_value()?.value else {
+        return Err(ParserError::ParserError(
+            "Scale field can only be of number type".to_string(),
+        ));
+    };
+    parser.expect_token(&Token::RParen)?;
+    let field = CeilFloorKind::Scale(Value::Number(-scale, long));
+    if is_ceil {
+        Ok(Expr::Ceil { expr, field })
+    } else {
+        Ok(Expr::Floor { expr, field })
+    }
 }
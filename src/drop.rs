@@ -69,7 +69,9 @@ pub(crate) fn drop_table(
             if *temporary {
                 return Err(CvsSqlError::TableNotTemporary(file.result_name.full_name()));
             }
-            fs::remove_file(&file.path)?;
+            if engine.should_write(&file) {
+                fs::remove_file(&file.path)?;
+            }
         }
         let file_name = engine.get_file_name(&file);
         let row = vec![
@@ -1,23 +1,59 @@
 use crate::error::CvsSqlError;
+use crate::explain::Profile;
 use crate::extractor::Extractor;
+use crate::file_results::get_default_header;
 use crate::results::Name;
 use crate::results_builder::build_simple_results;
+use crate::results_data::ResultsData;
+use crate::schema::{ColumnType, Schema};
 use crate::session::Session;
 use crate::stdin_as_table::{StdinReader, create_stdin_reader};
 use crate::value::Value;
-use crate::{args::Args, dialect::FilesDialect, results::ResultSet};
-use sqlparser::ast::ObjectName;
-use sqlparser::parser::Parser;
-use std::cell::RefCell;
-use std::{env::current_dir, path::PathBuf};
+use crate::{
+    args::{Args, NumericLocale},
+    dialect::FilesDialect,
+    results::ResultSet,
+};
+use csv::ReaderBuilder;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use sqlparser::ast::{ObjectName, Statement};
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::tokenizer::{Token, Tokenizer};
+use std::cell::{RefCell, RefMut};
+use std::rc::Rc;
+use std::{
+    env::current_dir,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 
 pub struct Engine {
     pub(crate) first_line_as_name: bool,
+    numbered_columns: bool,
+    pub(crate) numeric_locale: Option<NumericLocale>,
+    pub(crate) strip_currency: bool,
+    pub(crate) empty_as_string: bool,
+    pub(crate) headers_only: bool,
+    pub(crate) integers: bool,
+    string_columns: Vec<String>,
+    all_text: bool,
+    trim_headers: bool,
+    lower_headers: bool,
+    input_delimiter: Option<u8>,
+    comment: Option<u8>,
+    pub(crate) null_tokens: Vec<String>,
+    pub(crate) threads: usize,
+    schema: Schema,
     home: RefCell<PathBuf>,
     session: RefCell<Session>,
     read_only: bool,
+    dry_run: bool,
     stdin: RefCell<Box<dyn StdinReader>>,
+    rng: Rc<RefCell<StdRng>>,
+    profile: RefCell<Option<Profile>>,
+    common_tables: RefCell<Vec<(String, ResultSet)>>,
+    params: RefCell<Vec<Value>>,
 }
 impl TryFrom<&Args> for Engine {
     type Error = EngineError;
@@ -29,12 +65,50 @@ impl TryFrom<&Args> for Engine {
             .ok_or(EngineError::NoHomeDir)?;
         let stdin = RefCell::new(create_stdin_reader(args.command.is_some()));
         let home = RefCell::new(home.clone());
+        let schema = match &args.schema {
+            Some(path) => Schema::read(path)?,
+            None => Schema::default(),
+        };
+        let input_delimiter = match args.input_delimiter {
+            Some(delimiter) if delimiter.is_ascii() => Some(delimiter as u8),
+            Some(delimiter) => return Err(EngineError::InvalidDelimiter(delimiter)),
+            None => None,
+        };
+        let comment = match args.comment {
+            Some(comment) if comment.is_ascii() => Some(comment as u8),
+            Some(comment) => return Err(EngineError::InvalidComment(comment)),
+            None => None,
+        };
+        let rng = match args.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => rand::make_rng(),
+        };
         Ok(Self {
             home,
-            first_line_as_name: !args.first_line_as_data,
+            first_line_as_name: !args.first_line_as_data && !args.no_headers,
+            numbered_columns: args.no_headers,
+            numeric_locale: args.numeric_locale,
+            strip_currency: args.strip_currency,
+            empty_as_string: args.empty_as_string,
+            headers_only: args.validate,
+            integers: args.integers,
+            string_columns: args.string_columns.clone(),
+            all_text: args.all_text,
+            trim_headers: args.trim_headers,
+            lower_headers: args.lower_headers,
+            input_delimiter,
+            comment,
+            null_tokens: args.null_token.clone(),
+            threads: args.threads.max(1),
+            schema,
             session: RefCell::new(Session::default()),
             read_only: !args.write_mode,
+            dry_run: args.validate,
             stdin,
+            rng: Rc::new(RefCell::new(rng)),
+            profile: RefCell::new(None),
+            common_tables: RefCell::new(vec![]),
+            params: RefCell::new(vec![]),
         })
     }
 }
@@ -58,6 +132,7 @@ pub(crate) struct FoundFile {
     pub(crate) exists: bool,
     pub(crate) original_path: Option<PathBuf>,
     pub(crate) read_only: bool,
+    pub(crate) sheet: Option<String>,
 }
 impl FoundFile {
     fn get_display_path(&self) -> Option<&PathBuf> {
@@ -74,18 +149,114 @@ pub struct CommandExecution {
     pub sql: String,
     pub results: ResultSet,
 }
+pub type StatementResults = (String, Result<ResultSet, CvsSqlError>);
 impl Engine {
     pub fn execute_commands(&self, sql: &str) -> Result<Vec<CommandExecution>, CvsSqlError> {
-        let dialect = FilesDialect {};
         let mut all_results = Vec::new();
-        for statement in Parser::parse_sql(&dialect, sql)? {
+        for statement in self.parse(sql)? {
             let sql = statement.to_string();
-            let results = statement.extract(self)?;
+            let results = self.extract_statement(&statement)?;
             all_results.push(CommandExecution { sql, results });
         }
         Ok(all_results)
     }
 
+    /// Like `execute_commands`, but binds the `?` placeholders of the SQL to the values of `params`
+    /// (in order), so the values do not need to be formatted into the SQL.
+    pub fn execute_with_params(
+        &self,
+        sql: &str,
+        params: &[Value],
+    ) -> Result<Vec<CommandExecution>, CvsSqlError> {
+        let dialect = FilesDialect {};
+        let mut tokens = Tokenizer::new(&dialect, sql)
+            .tokenize()
+            .map_err(ParserError::from)?;
+        let mut placeholders = 0;
+        for token in &mut tokens {
+            if let Token::Placeholder(placeholder) = token
+                && placeholder == "?"
+            {
+                placeholders += 1;
+                *placeholder = format!("?{placeholders}");
+            }
+        }
+        if placeholders != params.len() {
+            return Err(CvsSqlError::ParamsMismatch(placeholders, params.len()));
+        }
+        let statements = Parser::new(&dialect)
+            .with_tokens(tokens)
+            .parse_statements()?;
+
+        *self.params.borrow_mut() = params.to_vec();
+        let all_results = statements
+            .iter()
+            .map(|statement| {
+                let sql = statement.to_string();
+                let results = self.extract_statement(statement)?;
+                Ok(CommandExecution { sql, results })
+            })
+            .collect();
+        self.params.borrow_mut().clear();
+        all_results
+    }
+
+    /// The value bound to a numbered placeholder (like `?1`) by `execute_with_params`.
+    pub(crate) fn param(&self, placeholder: &str) -> Option<Value> {
+        let index = placeholder.strip_prefix('?')?.parse::<usize>().ok()?;
+        self.params.borrow().get(index.checked_sub(1)?).cloned()
+    }
+
+    /// Runs the SQL statements and returns only their results (without the SQL of each statement).
+    pub fn query(&self, sql: &str) -> Result<Vec<ResultSet>, CvsSqlError> {
+        Ok(self
+            .execute_commands(sql)?
+            .into_iter()
+            .map(|execution| execution.results)
+            .collect())
+    }
+
+    /// Like `execute_commands`, but a failing statement does not stop the following statements.
+    /// Returns the SQL of each statement with its results.
+    pub fn execute_each_command(&self, sql: &str) -> Result<Vec<StatementResults>, CvsSqlError> {
+        let mut all_results = Vec::new();
+        for statement in self.parse(sql)? {
+            let sql = statement.to_string();
+            let results = self.extract_statement(&statement);
+            all_results.push((sql, results));
+        }
+        Ok(all_results)
+    }
+
+    /// Extracts the results of a top level statement.
+    pub(crate) fn extract_statement(
+        &self,
+        statement: &Statement,
+    ) -> Result<ResultSet, CvsSqlError> {
+        let results = statement.extract(self)?;
+        if !self.integers {
+            return Ok(results);
+        }
+        let data = results
+            .data
+            .into_iter()
+            .map(|mut row| {
+                row.trim_integers();
+                row
+            })
+            .collect();
+        let data = ResultsData::new(data);
+        Ok(ResultSet {
+            metadata: results.metadata,
+            data,
+        })
+    }
+
+    pub(crate) fn parse(&self, sql: &str) -> Result<Vec<Statement>, CvsSqlError> {
+        let dialect = FilesDialect {};
+        Ok(Parser::parse_sql(&dialect, sql)?)
+    }
+
     pub fn prompt(&self) -> String {
         let home = self.home.borrow();
         let name = home
@@ -100,6 +271,100 @@ impl Engine {
         format!("{name} {active_transaction}")
     }
 
+    pub(crate) fn rng(&self) -> Rc<RefCell<StdRng>> {
+        self.rng.clone()
+    }
+
+    pub(crate) fn profile(&self) -> RefMut<'_, Option<Profile>> {
+        self.profile.borrow_mut()
+    }
+
+    /// Registers the results of a common table expression (`WITH name AS (...)`).
+    pub(crate) fn add_common_table(&self, name: &str, results: ResultSet) {
+        self.common_tables
+            .borrow_mut()
+            .push((name.to_string(), results));
+    }
+
+    /// The number of common tables registered so far, used to drop the ones of a query once it is done.
+    pub(crate) fn common_tables_count(&self) -> usize {
+        self.common_tables.borrow().len()
+    }
+
+    pub(crate) fn drop_common_tables(&self, count: usize) {
+        self.common_tables.borrow_mut().truncate(count);
+    }
+
+    /// Returns a copy of the latest common table with that name (if any).
+    pub(crate) fn common_table(&self, name: &ObjectName) -> Option<ResultSet> {
+        let [part] = name.0.as_slice() else {
+            return None;
+        };
+        let name = &part.as_ident()?.value;
+        let common_tables = self.common_tables.borrow();
+        let (_, results) = common_tables
+            .iter()
+            .rev()
+            .find(|(common_name, _)| common_name == name)?;
+        let data = ResultsData::new(results.data.iter().cloned().collect());
+        Some(ResultSet {
+            metadata: results.metadata.clone(),
+            data,
+        })
+    }
+
+    /// The delimiter of the file: the `--input-delimiter`, a tab for `.tsv` files or a comma.
+    pub(crate) fn delimiter(&self, path: &Path) -> u8 {
+        match self.input_delimiter {
+            Some(delimiter) => delimiter,
+            None if path.extension().is_some_and(|ext| ext == "tsv") => b'\t',
+            None => b',',
+        }
+    }
+
+    /// The name of a column without a header: `col1`, `col2`, ... with `--no-headers`, or the Excel-like name.
+    pub(crate) fn default_header(&self, index: usize) -> String {
+        if self.numbered_columns {
+            format!("col{}", index + 1)
+        } else {
+            get_default_header(index)
+        }
+    }
+
+    /// A CSV reader builder for the file, with its delimiter and the comment character.
+    pub(crate) fn csv_reader(&self, path: &Path) -> ReaderBuilder {
+        let mut builder = ReaderBuilder::new();
+        builder
+            .flexible(true)
+            .delimiter(self.delimiter(path))
+            .comment(self.comment);
+        builder
+    }
+
+    pub(crate) fn header_title(&self, title: &str) -> String {
+        let title = if self.trim_headers {
+            title.trim()
+        } else {
+            title
+        };
+        if self.lower_headers {
+            title.to_lowercase()
+        } else {
+            title.to_string()
+        }
+    }
+
+    pub(crate) fn column_type(&self, name: &str) -> Option<ColumnType> {
+        if let Some(column_type) = self.schema.column_type(name) {
+            return Some(column_type);
+        }
+        if self.all_text || self.string_columns.iter().any(|column| column == name) {
+            Some(ColumnType::String)
+        } else {
+            None
+        }
+    }
+
     pub(crate) fn file_name(&self, name: &ObjectName) -> Result<FoundFile, CvsSqlError> {
         if name.0.len() == 1
             && let Some(name) = name.0.first()
@@ -113,6 +378,7 @@ impl Engine {
                 exists: true,
                 original_path: None,
                 read_only: true,
+                sheet: None,
             });
         }
         let file_name = &name.0;
@@ -120,7 +386,10 @@ impl Engine {
         let mut path = self.home.borrow().to_path_buf();
         let mut result_name = None;
         while let Some(name) = file_names.next() {
-            let name = name.to_string();
+            let name = match name.as_ident() {
+                Some(ident) => ident.value.clone(),
+                None => name.to_string(),
+            };
             result_name = result_name.append(&name);
             if file_names.peek().is_none() {
                 path = path.join(format!("{name}.csv"));
@@ -131,6 +400,42 @@ impl Engine {
         let Some(result_name) = result_name else {
             return Err(CvsSqlError::MissingTableName);
         };
+        // An Excel file, with an optional `!sheet` suffix (like `"report!Sales"`)
+        let (xlsx_path, sheet) = match path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.rsplit_once('!'))
+        {
+            Some((workbook, sheet)) => (
+                path.with_file_name(format!("{workbook}.xlsx")),
+                Some(sheet.to_string()),
+            ),
+            None => (path.with_extension("xlsx"), None),
+        };
+        let list_path = path.with_extension("");
+        let list_paths = if list_path
+            .extension()
+            .is_some_and(|ext| ext == "txt" || ext == "list")
+        {
+            vec![list_path]
+        } else {
+            vec![]
+        };
+        let fallbacks = [
+            (vec![path.with_extension("json")], None),
+            (
+                vec![path.with_extension("csv.gz"), path.with_extension("gz")],
+                None,
+            ),
+            (vec![path.with_extension("tsv")], None),
+            (vec![xlsx_path], sheet),
+            (list_paths, None),
+        ];
+        for (candidates, sheet) in fallbacks {
+            if let Some(file) = self.read_only_file(&path, &result_name, &candidates, sheet) {
+                return Ok(file);
+            }
+        }
         let original_path = if let Some(ref mut transaction) = self.session.borrow_mut().transaction
         {
             let original_path = path;
@@ -154,6 +459,37 @@ impl Engine {
             exists,
             original_path,
             read_only: self.session.borrow().transaction.is_none() && !is_temp && self.read_only,
+            sheet: None,
+        })
+    }
+
+    /// A table from a file that is not a plain CSV (like `name.json`), used only when
+    /// there is neither a `name.csv` file nor a temporary table with that name.
+    fn read_only_file(
+        &self,
+        path: &Path,
+        result_name: &Name,
+        candidates: &[PathBuf],
+        sheet: Option<String>,
+    ) -> Option<FoundFile> {
+        if path.exists()
+            || self
+                .session
+                .borrow()
+                .get_temporary_table(result_name)
+                .is_some()
+        {
+            return None;
+        }
+        let path = candidates.iter().find(|candidate| candidate.is_file())?;
+        Some(FoundFile {
+            is_temp: false,
+            path: path.clone(),
+            result_name: result_name.clone(),
+            exists: true,
+            original_path: None,
+            read_only: true,
+            sheet,
         })
     }
 
@@ -219,6 +555,7 @@ impl Engine {
             exists: false,
             original_path: None,
             read_only: false,
+            sheet: None,
         })
     }
     pub(crate) fn get_file_name(&self, file: &FoundFile) -> String {
@@ -229,6 +566,19 @@ impl Engine {
             .to_string()
     }
 
+    pub(crate) fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// In validate mode the write statements are checked but only temporary tables are changed.
+    pub(crate) fn should_write(&self, file: &FoundFile) -> bool {
+        file.is_temp || !self.dry_run
+    }
+
+    pub(crate) fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
     pub(crate) fn start_transaction(&self) -> Result<(), CvsSqlError> {
         self.session.borrow_mut().start_transaction()
     }
@@ -236,6 +586,9 @@ impl Engine {
         if self.read_only {
             return Err(CvsSqlError::ReadOnlyMode);
         }
+        if self.dry_run {
+            return self.session.borrow_mut().rollback_transaction();
+        }
         self.session.borrow_mut().commit_transaction()
     }
     pub(crate) fn rollback_transaction(&self) -> Result<(), CvsSqlError> {
@@ -247,6 +600,12 @@ impl Engine {
 pub enum EngineError {
     #[error("Cannot find home directory")]
     NoHomeDir,
+    #[error("Invalid schema: {0}")]
+    InvalidSchema(String),
+    #[error("Invalid input delimiter '{0}', it must be a single byte character")]
+    InvalidDelimiter(char),
+    #[error("Invalid comment character '{0}', it must be a single byte character")]
+    InvalidComment(char),
 }
 
 #[cfg(test)]
@@ -255,6 +614,7 @@ mod tests {
     use sqlparser::ast::Ident;
 
     use super::*;
+    use crate::results::Column;
 
     struct FakeStdIn {}
     impl StdinReader for FakeStdIn {
@@ -294,4 +654,64 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn query_rows_as_maps() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let results = engine.query(
+            "SELECT artist_id, name FROM tests.data.artists ORDER BY artist_id LIMIT 2; SELECT name AS a, artist_id AS a FROM tests.data.artists WHERE artist_id = 1",
+        )?;
+
+        assert_eq!(results.len(), 2);
+        let artists: Vec<_> = results[0].rows_as_maps().collect();
+        assert_eq!(artists.len(), 2);
+        assert_eq!(artists[0].get("artist_id"), Some(&Value::Number(1.into())));
+        assert_eq!(artists[0].get("name"), Some(&Value::Str("AC/DC".into())));
+        assert_eq!(
+            artists[1].get("name"),
+            Some(&Value::Str("Aerosmith".into()))
+        );
+        let duplicates: Vec<_> = results[1].rows_as_maps().collect();
+        assert_eq!(duplicates[0].len(), 1);
+        assert_eq!(duplicates[0].get("a"), Some(&Value::Str("AC/DC".into())));
+
+        Ok(())
+    }
+
+    #[test]
+    fn execute_with_params() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let results = engine.execute_with_params(
+            "SELECT * FROM tests.data.sales WHERE price > ?",
+            &[Value::Number(500.into())],
+        )?;
+        let expected =
+            engine.execute_commands("SELECT * FROM tests.data.sales WHERE price > 500")?;
+        let results = &results[0].results;
+        let expected = &expected[0].results;
+        assert!(results.data.iter().count() > 0);
+        assert_eq!(results.data.iter().count(), expected.data.iter().count());
+
+        let results = engine.execute_with_params(
+            "SELECT name, ? FROM tests.data.artists WHERE name = ? AND artist_id < ?",
+            &[
+                Value::Str("it's".into()),
+                Value::Str("AC/DC".into()),
+                Value::Number(3.into()),
+            ],
+        )?;
+        let row = results[0].results.data.iter().next().unwrap();
+        assert_eq!(row.get(&Column::from_index(0)), &Value::Str("AC/DC".into()));
+        assert_eq!(row.get(&Column::from_index(1)), &Value::Str("it's".into()));
+
+        let err = engine
+            .execute_with_params("SELECT * FROM tests.data.sales WHERE price > ?", &[])
+            .err()
+            .unwrap();
+        assert!(matches!(err, CvsSqlError::ParamsMismatch(1, 0)));
+
+        Ok(())
+    }
 }
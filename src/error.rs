@@ -20,6 +20,8 @@ pub enum CvsSqlError {
     Terminal(String),
     #[error("CSV Error: `{0}`")]
     CsvError(#[from] CsvError),
+    #[error("JSON Error: `{0}`")]
+    JsonError(#[from] serde_json::Error),
     #[error("Unsupported: `{0}`")]
     Unsupported(String),
     #[error("TODO: `{0}`")]
@@ -30,10 +32,12 @@ pub enum CvsSqlError {
     ColumnIndexError(#[from] ColumnIndexError),
     #[error("Can't aggregate without a group")]
     NoGroupBy,
-    #[error("Offset must be a positive number")]
+    #[error("Offset must be a positive whole number")]
     NoNumericOffset,
-    #[error("Limit must be a positive number")]
+    #[error("Limit must be a positive whole number")]
     NoNumericLimit,
+    #[error("Sample size must be a positive number (and a percentage must be up to 100)")]
+    NoNumericSample,
     #[error("Table `{0}` already exists.")]
     TableAlreadyExists(String),
     #[error("Cannot write to permanent file in read only mode.")]
@@ -78,8 +82,28 @@ pub enum CvsSqlError {
     StdinUnusable,
     #[error("Xlsx Error: `{0}`")]
     XlsxError(#[from] XlsxError),
+    #[error("Xlsx read Error: `{0}`")]
+    XlsxReadError(#[from] calamine::XlsxError),
     #[error("Cannot access parent dir of: `{0}`")]
     CannotAccessParentDir(PathBuf),
     #[error("Path is not a directory: `{0}`")]
     NotADir(String),
+    #[error("Invalid regular expression: `{0}`")]
+    InvalidRegex(String),
+    #[error("JSON file `{0}` is not an array of objects.")]
+    NotJsonTable(String),
+    #[error("Value `{0}` of column `{1}` is not a valid {2}.")]
+    SchemaMismatch(String, String, String),
+    #[error("Expected a single value, got {0} rows and {1} columns.")]
+    NotAScalar(usize, usize),
+    #[error("Invalid arguments for {0}: {1}.")]
+    InvalidTableFunctionArguments(String, String),
+    #[error("More than one source row matches the same target row.")]
+    MergeMultipleMatches,
+    #[error("Column `{0}` of the join is missing from the {1} table.")]
+    NoUsingColumn(String, String),
+    #[error("The SQL has {0} placeholders, but {1} parameters were given.")]
+    ParamsMismatch(usize, usize),
+    #[error("Could not read table file `{0}`: {1}.")]
+    TableFileError(String, String),
 }
@@ -0,0 +1,482 @@
+use std::ops::Deref;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use bigdecimal::BigDecimal;
+use itertools::Itertools;
+use sqlparser::ast::{
+    AnalyzeFormatKind, GroupByExpr, OrderByKind, Query, Select, SetExpr, Statement, TableFactor,
+    UtilityOption,
+};
+
+use crate::{
+    engine::Engine,
+    error::CvsSqlError,
+    extractor::Extractor,
+    result_set_metadata::SimpleResultSetMetadata,
+    results::ResultSet,
+    results_data::{DataRow, ResultsData},
+    value::Value,
+};
+
+struct StageStats {
+    query: String,
+    name: &'static str,
+    rows_in: Option<usize>,
+    rows_out: usize,
+    elapsed: Duration,
+}
+
+/// The stages that were measured while running an `EXPLAIN ANALYZE` statement.
+pub(crate) struct Profile {
+    stages: Vec<StageStats>,
+    /// The number of the query that is running, like `[2, 1]` for the first sub query of the second query.
+    query: Vec<usize>,
+    /// The number of queries that were started in every level of `query` (and in the level below it).
+    started: Vec<usize>,
+}
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            stages: vec![],
+            query: vec![],
+            started: vec![0],
+        }
+    }
+}
+
+/// Labels the stages that are recorded while it is alive with a new query number, nested under the
+/// number of the query that is already running (if any).
+pub(crate) struct QueryScope<'a> {
+    engine: &'a Engine,
+}
+impl<'a> QueryScope<'a> {
+    pub(crate) fn enter(engine: &'a Engine) -> Self {
+        if let Some(profile) = engine.profile().as_mut()
+            && let Some(started) = profile.started.last_mut()
+        {
+            *started += 1;
+            let number = *started;
+            profile.query.push(number);
+            profile.started.push(0);
+        }
+        Self { engine }
+    }
+}
+impl Drop for QueryScope<'_> {
+    fn drop(&mut self) {
+        if let Some(profile) = self.engine.profile().as_mut() {
+            profile.query.pop();
+            profile.started.pop();
+        }
+    }
+}
+
+/// Measures a single stage of a query if the engine is profiling it.
+pub(crate) struct Stage<'a> {
+    engine: &'a Engine,
+    start: Option<Instant>,
+}
+impl<'a> Stage<'a> {
+    pub(crate) fn start(engine: &'a Engine) -> Self {
+        let start = engine.profile().is_some().then(Instant::now);
+        Self { engine, start }
+    }
+
+    /// Records the stage and returns the number of rows it produced (or `None` if the engine is not profiling).
+    pub(crate) fn end(
+        self,
+        name: &'static str,
+        rows_in: Option<usize>,
+        rows_out: impl FnOnce() -> usize,
+    ) -> Option<usize> {
+        let elapsed = self.start?.elapsed();
+        let rows_out = rows_out();
+        if let Some(profile) = self.engine.profile().as_mut() {
+            let query = profile.query.iter().join(".");
+            profile.stages.push(StageStats {
+                query,
+                name,
+                rows_in,
+                rows_out,
+                elapsed,
+            });
+        }
+        Some(rows_out)
+    }
+}
+
+/// The number of stages (and of sub queries) recorded so far, used to discard the stages of a
+/// failed attempt.
+pub(crate) fn recorded_stages(engine: &Engine) -> Option<(usize, usize)> {
+    engine.profile().as_ref().map(|profile| {
+        (
+            profile.stages.len(),
+            profile.started.last().copied().unwrap_or_default(),
+        )
+    })
+}
+
+pub(crate) fn discard_stages(engine: &Engine, recorded: Option<(usize, usize)>) {
+    if let (Some(profile), Some((stages, started))) = (engine.profile().as_mut(), recorded) {
+        profile.stages.truncate(stages);
+        if let Some(last) = profile.started.last_mut() {
+            *last = started;
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn explain(
+    engine: &Engine,
+    analyze: bool,
+    verbose: bool,
+    query_plan: bool,
+    estimate: bool,
+    statement: &Statement,
+    format: &Option<AnalyzeFormatKind>,
+    options: &Option<Vec<UtilityOption>>,
+) -> Result<ResultSet, CvsSqlError> {
+    if verbose {
+        return Err(CvsSqlError::Unsupported("EXPLAIN VERBOSE".into()));
+    }
+    if query_plan {
+        return Err(CvsSqlError::Unsupported("EXPLAIN QUERY PLAN".into()));
+    }
+    if estimate {
+        return Err(CvsSqlError::Unsupported("EXPLAIN ESTIMATE".into()));
+    }
+    if format.is_some() {
+        return Err(CvsSqlError::Unsupported("EXPLAIN with FORMAT".into()));
+    }
+    if options.is_some() {
+        return Err(CvsSqlError::Unsupported("EXPLAIN with options".into()));
+    }
+    let Statement::Query(query) = statement else {
+        return Err(CvsSqlError::Unsupported(format!("EXPLAIN {statement}")));
+    };
+    if !analyze {
+        return Ok(plan(engine, query));
+    }
+
+    let previous = engine.profile().replace(Profile::default());
+    let results = statement.extract(engine);
+    let profile = engine.profile().take();
+    *engine.profile() = previous;
+    results?;
+    let stages = profile.map(|profile| profile.stages).unwrap_or_default();
+
+    let mut metadata = SimpleResultSetMetadata::new(None);
+    for column in ["query", "stage", "rows_in", "rows_out", "time_ms"] {
+        metadata.add_column(column);
+    }
+    let metadata = Rc::new(metadata.build());
+    let data = stages
+        .into_iter()
+        .map(|stage| {
+            DataRow::new(vec![
+                Value::Str(stage.query),
+                Value::Str(stage.name.to_string()),
+                match stage.rows_in {
+                    Some(rows) => Value::Number((rows as u64).into()),
+                    None => Value::Empty,
+                },
+                Value::Number((stage.rows_out as u64).into()),
+                Value::Number(BigDecimal::new(stage.elapsed.as_micros().into(), 3)),
+            ])
+        })
+        .collect();
+    let data = ResultsData::new(data);
+    Ok(ResultSet { metadata, data })
+}
+
+/// The logical plan of a query (for `EXPLAIN` without `ANALYZE`), one line per row. The query is not
+/// executed, the plan lists the stages in the order they are applied; nested lines are indented.
+fn plan(engine: &Engine, query: &Query) -> ResultSet {
+    let mut lines = vec![];
+    plan_query(engine, query, 0, &mut lines);
+
+    let mut metadata = SimpleResultSetMetadata::new(None);
+    metadata.add_column("plan");
+    let metadata = Rc::new(metadata.build());
+    let data = lines
+        .into_iter()
+        .map(|line| DataRow::new(vec![Value::Str(line)]))
+        .collect();
+    let data = ResultsData::new(data);
+    ResultSet { metadata, data }
+}
+
+fn add_line(lines: &mut Vec<String>, depth: usize, line: String) {
+    lines.push(format!("{}{line}", "  ".repeat(depth)));
+}
+
+fn plan_query(engine: &Engine, query: &Query, depth: usize, lines: &mut Vec<String>) {
+    if let Some(with) = &query.with {
+        for table in &with.cte_tables {
+            add_line(lines, depth, format!("with {}", table.alias));
+            plan_query(engine, &table.query, depth + 1, lines);
+        }
+    }
+    // Like in the extraction, the ordering and the trimming of a select come before its projection
+    let mut trailing = vec![];
+    if let Some(order_by) = &query.order_by {
+        let order = match &order_by.kind {
+            OrderByKind::All(_) => "ALL".to_string(),
+            OrderByKind::Expressions(expressions) => expressions.iter().join(", "),
+        };
+        trailing.push(format!("order: {order}"));
+    }
+    if let Some(limit_clause) = &query.limit_clause {
+        trailing.push(format!("trim: {}", limit_clause.to_string().trim()));
+    }
+    match query.body.deref() {
+        SetExpr::Select(select) => plan_select(engine, select, trailing, depth, lines),
+        body => {
+            plan_set_expr(engine, body, depth, lines);
+            for line in trailing {
+                add_line(lines, depth, line);
+            }
+        }
+    }
+}
+
+fn plan_set_expr(engine: &Engine, body: &SetExpr, depth: usize, lines: &mut Vec<String>) {
+    match body {
+        SetExpr::Select(select) => plan_select(engine, select, vec![], depth, lines),
+        SetExpr::Query(query) => plan_query(engine, query, depth, lines),
+        SetExpr::SetOperation {
+            op,
+            set_quantifier,
+            left,
+            right,
+        } => {
+            add_line(
+                lines,
+                depth,
+                format!("{op} {set_quantifier}").trim().to_string(),
+            );
+            plan_set_expr(engine, left, depth + 1, lines);
+            plan_set_expr(engine, right, depth + 1, lines);
+        }
+        _ => add_line(lines, depth, body.to_string()),
+    }
+}
+
+fn plan_select(
+    engine: &Engine,
+    select: &Select,
+    trailing: Vec<String>,
+    depth: usize,
+    lines: &mut Vec<String>,
+) {
+    add_line(lines, depth, "scan".into());
+    for table in &select.from {
+        plan_table(engine, &table.relation, depth + 1, lines);
+        for join in &table.joins {
+            add_line(lines, depth + 1, join.to_string().trim().to_string());
+            plan_table(engine, &join.relation, depth + 2, lines);
+        }
+    }
+    if let Some(selection) = &select.selection {
+        add_line(lines, depth, format!("filter: {selection}"));
+    }
+    if let GroupByExpr::Expressions(expressions, _) = &select.group_by
+        && !expressions.is_empty()
+    {
+        add_line(
+            lines,
+            depth,
+            format!("group: {}", expressions.iter().join(", ")),
+        );
+    }
+    if let Some(having) = &select.having {
+        add_line(lines, depth, format!("having: {having}"));
+    }
+    for line in trailing {
+        add_line(lines, depth, line);
+    }
+    add_line(
+        lines,
+        depth,
+        format!("project: {}", select.projection.iter().join(", ")),
+    );
+}
+
+fn plan_table(engine: &Engine, table: &TableFactor, depth: usize, lines: &mut Vec<String>) {
+    match table {
+        TableFactor::Table {
+            name, args: None, ..
+        } => {
+            let line = match engine.file_name(name) {
+                Ok(file) if file.exists && !file.is_temp => {
+                    format!("read {name} from {}", file.path.display())
+                }
+                _ => format!("read {name}"),
+            };
+            add_line(lines, depth, line);
+        }
+        TableFactor::Derived { subquery, .. } => {
+            add_line(lines, depth, "subquery".into());
+            plan_query(engine, subquery, depth + 1, lines);
+        }
+        _ => add_line(lines, depth, format!("read {table}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{args::Args, engine::Engine, error::CvsSqlError, results::Column, value::Value};
+
+    /// The name, the rows in and the rows out of a stage.
+    type StageRow = (String, Value, Value);
+
+    fn explain(sql: &str) -> Result<Vec<StageRow>, CvsSqlError> {
+        Ok(explain_queries(sql)?
+            .into_iter()
+            .map(|(_, stage)| stage)
+            .collect())
+    }
+
+    fn explain_queries(sql: &str) -> Result<Vec<(String, StageRow)>, CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+        let results = engine.execute_commands(sql)?;
+        let results = &results.first().unwrap().results;
+        Ok(results
+            .data
+            .iter()
+            .map(|row| {
+                (
+                    row.get(&Column::from_index(0)).to_string(),
+                    (
+                        row.get(&Column::from_index(1)).to_string(),
+                        row.get(&Column::from_index(2)).clone(),
+                        row.get(&Column::from_index(3)).clone(),
+                    ),
+                )
+            })
+            .collect())
+    }
+
+    #[test]
+    fn explain_analyze_reports_the_stages() -> Result<(), CvsSqlError> {
+        let stages = explain(
+            "EXPLAIN ANALYZE SELECT name FROM tests.data.artists WHERE artist_id > 1 ORDER BY name LIMIT 2",
+        )?;
+
+        let names: Vec<_> = stages.iter().map(|(name, _, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["scan", "filter", "group", "order", "trim", "project"]
+        );
+        assert_eq!(stages[0].1, Value::Empty);
+        assert_eq!(stages[0].2, Value::Number(4.into()));
+        assert_eq!(stages[1].2, Value::Number(3.into()));
+        assert_eq!(stages[5].2, Value::Number(2.into()));
+        for (previous, next) in stages.iter().zip(stages.iter().skip(1)) {
+            assert_eq!(previous.2, next.1);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn explain_analyze_reports_the_aggregation_once() -> Result<(), CvsSqlError> {
+        let stages = explain("EXPLAIN ANALYZE SELECT COUNT(*) FROM tests.data.artists")?;
+
+        let names: Vec<_> = stages.iter().map(|(name, _, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["scan", "filter", "group", "order", "trim", "project"]
+        );
+        assert_eq!(stages[2].1, Value::Number(4.into()));
+        assert_eq!(stages[2].2, Value::Number(1.into()));
+        assert_eq!(stages[5].2, Value::Number(1.into()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn explain_analyze_labels_the_stages_of_sub_queries() -> Result<(), CvsSqlError> {
+        let stages = explain_queries(
+            "EXPLAIN ANALYZE WITH big AS (SELECT * FROM tests.data.sales WHERE price > 300) SELECT a.name FROM (SELECT * FROM tests.data.artists WHERE artist_id > 1) AS a WHERE a.artist_id < 4",
+        )?;
+
+        let queries: Vec<_> = stages.iter().map(|(query, _)| query.as_str()).collect();
+        assert_eq!(
+            queries,
+            vec![
+                "1", "1", "1", "1", "1", "1", "2.1", "2.1", "2.1", "2.1", "2.1", "2.1", "2", "2",
+                "2", "2", "2", "2"
+            ]
+        );
+        // Every query is a pipeline of its own
+        for query in ["1", "2.1", "2"] {
+            let stages: Vec<_> = stages
+                .iter()
+                .filter(|(q, _)| q == query)
+                .map(|(_, stage)| stage)
+                .collect();
+            assert_eq!(stages[0].0, "scan");
+            assert_eq!(stages[0].1, Value::Empty);
+            for (previous, next) in stages.iter().zip(stages.iter().skip(1)) {
+                assert_eq!(previous.2, next.1, "{query}");
+            }
+        }
+        let outer_scan = &stages[12].1;
+        assert_eq!(outer_scan.2, Value::Number(3.into()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn explain_shows_the_plan() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let results = engine.execute_commands(
+            "EXPLAIN SELECT a.name, COUNT(*) FROM tests.data.artists AS a LEFT JOIN tests.data.albums AS b ON a.artist_id = b.artist_id WHERE a.artist_id > 1 GROUP BY a.name ORDER BY a.name LIMIT 3",
+        )?;
+        let results = &results.first().unwrap().results;
+        let plan: Vec<_> = results
+            .data
+            .iter()
+            .map(|row| row.get(&Column::from_index(0)).to_string())
+            .collect();
+
+        assert_eq!(plan.len(), 9);
+        assert_eq!(plan[0], "scan");
+        assert!(plan[1].starts_with("  read tests.data.artists from "));
+        assert!(plan[1].ends_with("artists.csv"));
+        assert_eq!(
+            plan[2],
+            "  LEFT JOIN tests.data.albums AS b ON a.artist_id = b.artist_id"
+        );
+        assert!(plan[3].starts_with("    read tests.data.albums from "));
+        assert_eq!(
+            &plan[4..],
+            [
+                "filter: a.artist_id > 1",
+                "group: a.name",
+                "order: a.name",
+                "trim: LIMIT 3",
+                "project: a.name, COUNT(*)",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn explain_verbose_is_unsupported() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let err = engine
+            .execute_commands("EXPLAIN VERBOSE SELECT * FROM tests.data.artists")
+            .err()
+            .unwrap();
+
+        assert!(matches!(err, CvsSqlError::Unsupported(_)));
+
+        Ok(())
+    }
+}
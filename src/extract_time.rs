@@ -21,11 +21,16 @@ impl Projection for TimeFieldExtractor {
     }
     fn get<'a>(&'a self, row: &'a GroupRow) -> SmartReference<'a, Value> {
         let value = self.value.get(row);
-        match value.deref() {
-            Value::Timestamp(ts) => extract_timestamp(&self.field, ts),
-            Value::Date(dt) => extract_date(&self.field, dt),
-            _ => Value::Empty.into(),
-        }
+        extract_field(&self.field, value.deref())
+    }
+}
+
+/// Extracts a field of a date or a timestamp (any other value has no fields).
+pub(crate) fn extract_field<'a>(field: &'a Field, value: &Value) -> SmartReference<'a, Value> {
+    match value {
+        Value::Timestamp(ts) => extract_timestamp(field, ts),
+        Value::Date(dt) => extract_date(field, dt),
+        _ => Value::Empty.into(),
     }
 }
 
@@ -37,7 +42,10 @@ pub fn create_extract(
     let field = field.try_into()?;
     Ok(Box::new(TimeFieldExtractor { value, name, field }))
 }
-enum Field {
+pub(crate) enum Field {
+    Year,
+    Quarter,
+    Month,
     Day,
     DayOfWeek,
     DayOfYear,
@@ -56,6 +64,9 @@ impl TryFrom<&DateTimeField> for Field {
     type Error = CvsSqlError;
     fn try_from(value: &DateTimeField) -> Result<Self, Self::Error> {
         match value {
+            DateTimeField::Year => Ok(Field::Year),
+            DateTimeField::Quarter => Ok(Field::Quarter),
+            DateTimeField::Month => Ok(Field::Month),
             DateTimeField::Day => Ok(Field::Day),
             DateTimeField::Dow | DateTimeField::DayOfWeek => Ok(Field::DayOfWeek),
             DateTimeField::Doy | DateTimeField::DayOfYear => Ok(Field::DayOfYear),
@@ -126,6 +137,9 @@ fn milliseconds<'a>(ts: &NaiveDateTime) -> SmartReference<'a, Value> {
 
 fn extract_timestamp<'a>(field: &'a Field, ts: &NaiveDateTime) -> SmartReference<'a, Value> {
     match field {
+        Field::Year => from_i32(ts.year()),
+        Field::Quarter => from_u32(ts.month0() / 3 + 1),
+        Field::Month => from_u32(ts.month()),
         Field::Day => from_u32(ts.day()),
         Field::DayOfWeek => from_u32(ts.weekday().num_days_from_sunday()),
         Field::DayOfYear => from_u32(ts.ordinal()),
@@ -143,6 +157,9 @@ fn extract_timestamp<'a>(field: &'a Field, ts: &NaiveDateTime) -> SmartReference
 }
 fn extract_date<'a>(field: &'a Field, dt: &NaiveDate) -> SmartReference<'a, Value> {
     match field {
+        Field::Year => from_i32(dt.year()),
+        Field::Quarter => from_u32(dt.month0() / 3 + 1),
+        Field::Month => from_u32(dt.month()),
         Field::Day => from_u32(dt.day()),
         Field::DayOfWeek => from_u32(dt.weekday().num_days_from_sunday()),
         Field::DayOfYear => from_u32(dt.ordinal()),
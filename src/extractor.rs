@@ -1,21 +1,28 @@
 use sqlparser::ast::{
     Expr, GroupByExpr, LimitClause, OrderBy, Query, Select, SetExpr, Statement, TableFactor, Use,
+    With,
 };
 
 use crate::alter::alter;
 use crate::drop::drop_table;
 use crate::error::CvsSqlError;
+use crate::explain::{QueryScope, Stage, discard_stages, explain, recorded_stages};
 use crate::file_results::read_file;
 use crate::filter_results::{apply_having, make_filter};
 use crate::group_by::{force_group_by, group_by};
 use crate::join::create_join;
 use crate::named_results::alias_results;
 use crate::order_by_results::order_by;
+use crate::pivot::Pivot;
 use crate::projections::make_projection;
-use crate::show::{show_databases, show_tables};
+use crate::sampler::sample_results;
+use crate::select_into::select_into;
+use crate::show::{describe_table, show_databases, show_tables};
+use crate::table_functions::extract_table_function;
 use crate::transaction::{commit_transaction, rollback_transaction, start_transaction};
 use crate::trimmer::trim;
 use crate::update::update_table;
+use crate::window::add_window_columns;
 use crate::{engine::Engine, results::ResultSet};
 pub trait Extractor {
     fn extract(&self, engine: &Engine) -> Result<ResultSet, CvsSqlError>;
@@ -57,6 +64,7 @@ impl Extractor for Statement {
                 table,
             ),
             Statement::Delete(delete) => delete.extract(engine),
+            Statement::Merge(merge) => merge.extract(engine),
             Statement::AlterTable(a) => alter(
                 engine,
                 &a.name,
@@ -111,6 +119,36 @@ impl Extractor for Statement {
                 }
                 show_tables(engine, full)
             }
+            Statement::Explain {
+                describe_alias: _,
+                analyze,
+                verbose,
+                query_plan,
+                estimate,
+                statement,
+                format,
+                options,
+            } => explain(
+                engine,
+                *analyze,
+                *verbose,
+                *query_plan,
+                *estimate,
+                statement,
+                format,
+                options,
+            ),
+            Statement::ExplainTable {
+                describe_alias: _,
+                hive_format,
+                has_table_keyword: _,
+                table_name,
+            } => {
+                if hive_format.is_some() {
+                    return Err(CvsSqlError::Unsupported(self.to_string()));
+                }
+                describe_table(engine, table_name)
+            }
             Statement::ShowDatabases {
                 terse,
                 history,
@@ -133,54 +171,87 @@ impl Extractor for Statement {
 
 impl Extractor for Query {
     fn extract(&self, engine: &Engine) -> Result<ResultSet, CvsSqlError> {
-        if self.fetch.is_some() {
-            return Err(CvsSqlError::Unsupported("SELECT ... FETCH".to_string()));
-        }
-        if self.for_clause.is_some() {
-            return Err(CvsSqlError::Unsupported("SELECT ... FOR".to_string()));
-        }
-        if self.with.is_some() {
-            return Err(CvsSqlError::Unsupported("SELECT ... WITH".to_string()));
-        }
-        let (limit, offset) = match &self.limit_clause {
-            None => (None, None),
-            Some(LimitClause::OffsetCommaLimit { offset, limit }) => (Some(offset), Some(limit)),
-            Some(LimitClause::LimitOffset {
-                limit,
-                offset,
-                limit_by,
-            }) => {
-                if !limit_by.is_empty() {
-                    return Err(CvsSqlError::Unsupported("SELECT ... LIMIT BY".to_string()));
-                }
-                let offset = offset.as_ref().map(|o| &o.value);
-                let limit = limit.as_ref();
-                (limit, offset)
-            }
+        let Some(with) = &self.with else {
+            return extract_query(self, engine);
         };
-        if !self.locks.is_empty() {
+        // The common tables are only visible while the query is extracted
+        let count = engine.common_tables_count();
+        let results = add_common_tables(engine, with).and_then(|_| extract_query(self, engine));
+        engine.drop_common_tables(count);
+        results
+    }
+}
+fn add_common_tables(engine: &Engine, with: &With) -> Result<(), CvsSqlError> {
+    if with.recursive {
+        return Err(CvsSqlError::Unsupported("WITH RECURSIVE".to_string()));
+    }
+    for cte in &with.cte_tables {
+        if cte.from.is_some() {
+            return Err(CvsSqlError::Unsupported("WITH ... FROM".to_string()));
+        }
+        if !cte.alias.columns.is_empty() {
             return Err(CvsSqlError::Unsupported(
-                "SELECT ... FOR UPDATE/SHARE".to_string(),
+                "WITH with column alias".to_string(),
             ));
         }
-        if self.settings.is_some() {
-            return Err(CvsSqlError::Unsupported("SELECT ... SETTINGS".to_string()));
-        }
-        if self.format_clause.is_some() {
-            return Err(CvsSqlError::Unsupported("SELECT ... FORMAT".to_string()));
+        let results = cte.query.extract(engine)?;
+        let results = alias_results(&cte.alias.name, results);
+        engine.add_common_table(&cte.alias.name.value, results);
+    }
+    Ok(())
+}
+fn extract_query(query: &Query, engine: &Engine) -> Result<ResultSet, CvsSqlError> {
+    if query.fetch.is_some() {
+        return Err(CvsSqlError::Unsupported("SELECT ... FETCH".to_string()));
+    }
+    if query.for_clause.is_some() {
+        return Err(CvsSqlError::Unsupported("SELECT ... FOR".to_string()));
+    }
+    let (limit, offset) = match &query.limit_clause {
+        None => (None, None),
+        Some(LimitClause::OffsetCommaLimit { offset, limit }) => (Some(limit), Some(offset)),
+        Some(LimitClause::LimitOffset {
+            limit,
+            offset,
+            limit_by,
+        }) => {
+            if !limit_by.is_empty() {
+                return Err(CvsSqlError::Unsupported("SELECT ... LIMIT BY".to_string()));
+            }
+            let offset = offset.as_ref().map(|o| &o.value);
+            let limit = limit.as_ref();
+            (limit, offset)
         }
+    };
+    if !query.locks.is_empty() {
+        return Err(CvsSqlError::Unsupported(
+            "SELECT ... FOR UPDATE/SHARE".to_string(),
+        ));
+    }
+    if query.settings.is_some() {
+        return Err(CvsSqlError::Unsupported("SELECT ... SETTINGS".to_string()));
+    }
+    if query.format_clause.is_some() {
+        return Err(CvsSqlError::Unsupported("SELECT ... FORMAT".to_string()));
+    }
 
-        match &*self.body {
-            SetExpr::Select(select) => {
-                extract(select, &self.order_by, limit, offset, engine, false)
+    match &*query.body {
+        SetExpr::Select(select) => {
+            let results = extract(select, &query.order_by, limit, offset, engine, false)?;
+            match &select.into {
+                Some(into) => select_into(engine, into, results),
+                None => Ok(results),
             }
-            SetExpr::Values(values) => values.extract(engine),
-            _ => Err(CvsSqlError::Unsupported(format!("SELECT {}", self.body))),
         }
+        SetExpr::Values(values) => values.extract(engine),
+        _ => Err(CvsSqlError::Unsupported(format!("SELECT {}", query.body))),
     }
 }
 impl Extractor for Select {
     fn extract(&self, engine: &Engine) -> Result<ResultSet, CvsSqlError> {
+        if self.into.is_some() {
+            return Err(CvsSqlError::Unsupported("SELECT INTO".to_string()));
+        }
         extract(self, &None, None, None, engine, false)
     }
 }
@@ -198,9 +269,6 @@ fn extract(
     if select.top.is_some() {
         return Err(CvsSqlError::Unsupported("SELECT TOP".to_string()));
     }
-    if select.into.is_some() {
-        return Err(CvsSqlError::Unsupported("SELECT INTO".to_string()));
-    }
     if !select.lateral_views.is_empty() {
         return Err(CvsSqlError::Unsupported("SELECT LATERAL VIEW".to_string()));
     }
@@ -241,10 +309,19 @@ fn extract(
         return Err(CvsSqlError::Unsupported("SELECT without FROM".to_string()));
     }
 
+    // The retry with a forced group is still the same query
+    let _scope = (!force_group).then(|| QueryScope::enter(engine));
+    let recorded = recorded_stages(engine);
+
+    let stage = Stage::start(engine);
     let product = create_join(&select.from, engine)?;
+    let rows = stage.end("scan", None, || product.data.iter().count());
 
+    let stage = Stage::start(engine);
     let filter = make_filter(engine, &select.selection, product)?;
+    let rows = stage.end("filter", rows, || filter.data.iter().count());
 
+    let stage = Stage::start(engine);
     let mut group_by = if force_group {
         force_group_by(filter)
     } else {
@@ -254,24 +331,30 @@ fn extract(
                     "SELECT ... GROUP BY ALL".to_string(),
                 ));
             }
-            GroupByExpr::Expressions(exp, mods) => {
-                if !mods.is_empty() {
-                    return Err(CvsSqlError::Unsupported(
-                        "SELECT ... GROUP BY WITH".to_string(),
-                    ));
-                }
-                group_by(engine, exp, filter)?
-            }
+            GroupByExpr::Expressions(exp, mods) => group_by(engine, exp, mods, filter)?,
         }
     };
     apply_having(engine, &select.having, &mut group_by)?;
+    add_window_columns(engine, &select.projection, &mut group_by)?;
+    let rows = stage.end("group", rows, || group_by.rows.len());
+
+    let stage = Stage::start(engine);
+    order_by(engine, order, &select.projection, &mut group_by)?;
+    let rows = stage.end("order", rows, || group_by.rows.len());
 
-    order_by(engine, order, &mut group_by)?;
+    let stage = Stage::start(engine);
     trim(limit, offset, engine, &mut group_by)?;
+    let rows = stage.end("trim", rows, || group_by.rows.len());
+
+    let stage = Stage::start(engine);
     match make_projection(engine, group_by, &select.projection) {
-        Ok(proj) => Ok(proj),
+        Ok(proj) => {
+            stage.end("project", rows, || proj.data.iter().count());
+            Ok(proj)
+        }
         Err(CvsSqlError::NoGroupBy) => {
             if !force_group {
+                discard_stages(engine, recorded);
                 extract(select, order, limit, offset, engine, true)
             } else {
                 Err(CvsSqlError::NoGroupBy)
@@ -296,11 +379,6 @@ impl Extractor for TableFactor {
                 sample,
                 index_hints,
             } => {
-                if args.is_some() {
-                    return Err(CvsSqlError::Unsupported(
-                        "SELECT ... FROM with table arguments".to_string(),
-                    ));
-                }
                 if !with_hints.is_empty() {
                     return Err(CvsSqlError::Unsupported(
                         "SELECT ... FROM  WITH".to_string(),
@@ -326,18 +404,19 @@ impl Extractor for TableFactor {
                         "SELECT ... FROM with JSON path".to_string(),
                     ));
                 }
-                if sample.is_some() {
-                    return Err(CvsSqlError::Unsupported(
-                        "SELECT ... FROM with sample".to_string(),
-                    ));
-                }
                 if !index_hints.is_empty() {
                     return Err(CvsSqlError::Unsupported(
                         "SELECT ... FROM with index hints".to_string(),
                     ));
                 }
 
-                let results = read_file(engine, name)?;
+                let mut results = match args {
+                    Some(args) => extract_table_function(engine, name, args)?,
+                    None => read_file(engine, name)?,
+                };
+                if let Some(sample) = sample {
+                    results = sample_results(sample, engine, results)?;
+                }
                 if let Some(alias) = alias {
                     if !alias.columns.is_empty() {
                         return Err(CvsSqlError::Unsupported(
@@ -355,17 +434,15 @@ impl Extractor for TableFactor {
                 alias,
                 sample,
             } => {
-                if sample.is_some() {
-                    return Err(CvsSqlError::Unsupported(
-                        "SELECT ... FROM with subquery sample".to_string(),
-                    ));
-                }
                 if *lateral {
                     return Err(CvsSqlError::Unsupported(
                         "SELECT ... FROM with lateral subquery".to_string(),
                     ));
                 }
-                let results = subquery.extract(engine)?;
+                let mut results = subquery.extract(engine)?;
+                if let Some(sample) = sample {
+                    results = sample_results(sample, engine, results)?;
+                }
                 if let Some(alias) = alias {
                     if !alias.columns.is_empty() {
                         return Err(CvsSqlError::Unsupported(
@@ -377,6 +454,22 @@ impl Extractor for TableFactor {
                     Ok(results)
                 }
             }
+            TableFactor::Pivot {
+                table,
+                aggregate_functions,
+                value_column,
+                value_source,
+                default_on_null,
+                alias,
+            } => Pivot {
+                table,
+                aggregate_functions,
+                value_column,
+                value_source,
+                default_on_null,
+                alias,
+            }
+            .extract(engine),
             _ => Err(CvsSqlError::Unsupported(
                 "SELECT ... FROM must be a table or sub query".to_string(),
             )),
@@ -1,15 +1,28 @@
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::Path;
 use std::rc::Rc;
+use std::str::FromStr;
 
-use csv::ReaderBuilder;
+use bigdecimal::BigDecimal;
+use calamine::{Data, DataType, Range, Reader, Xlsx, open_workbook};
+use csv::StringRecord;
+use flate2::read::GzDecoder;
+use serde_json::Value as JsonValue;
 use sqlparser::ast::ObjectName;
 
 use crate::engine::Engine;
 use crate::error::CvsSqlError;
+use crate::glob_table::read_glob_table;
+use crate::information_schema::read_information_schema;
 use crate::result_set_metadata::SimpleResultSetMetadata;
+use crate::results::Name;
 use crate::results_data::{DataRow, ResultsData};
+use crate::schema::ColumnType;
+use crate::value::parse_formatted_number;
 use crate::{results::ResultSet, value::Value};
 
-fn get_default_header(index: usize) -> String {
+pub(crate) fn get_default_header(index: usize) -> String {
     let mut index = index;
     let mut title = String::from("$");
     let first = 'A' as usize;
@@ -27,52 +40,348 @@ fn get_default_header(index: usize) -> String {
 }
 
 pub fn read_file(engine: &Engine, name: &ObjectName) -> Result<ResultSet, CvsSqlError> {
+    if let Some(results) = engine.common_table(name) {
+        return Ok(results);
+    }
+    if let Some(results) = read_information_schema(engine, name)? {
+        return Ok(results);
+    }
+    if let Some(results) = read_glob_table(engine, name)? {
+        return Ok(results);
+    }
     let file = engine.file_name(name)?;
     if !file.exists {
         return Err(CvsSqlError::TableNotExists(file.result_name.full_name()));
     }
+    if file.path.extension().is_some_and(|ext| ext == "json") {
+        return read_json_file(&file.path, file.result_name);
+    }
+    if file.path.extension().is_some_and(|ext| ext == "xlsx") {
+        return read_xlsx_file(engine, &file.path, file.sheet.as_deref(), file.result_name);
+    }
+    if file
+        .path
+        .extension()
+        .is_some_and(|ext| ext == "txt" || ext == "list")
+    {
+        return read_list_file(engine, &file.path, file.result_name);
+    }
 
-    let mut reader = ReaderBuilder::new()
-        .flexible(true)
+    let gzip = file.path.extension().is_some_and(|ext| ext == "gz");
+    let raw = match (engine.empty_as_string && !engine.headers_only, gzip) {
+        (false, _) => None,
+        (true, false) => Some(fs::read(&file.path)?),
+        (true, true) => {
+            let mut raw = Vec::new();
+            GzDecoder::new(File::open(&file.path)?).read_to_end(&mut raw)?;
+            Some(raw)
+        }
+    };
+    let input: Box<dyn Read> = match &raw {
+        Some(raw) => Box::new(raw.as_slice()),
+        None if gzip => Box::new(GzDecoder::new(File::open(&file.path)?)),
+        None => Box::new(File::open(&file.path)?),
+    };
+    let delimiter = engine.delimiter(&file.path);
+    let mut reader = engine
+        .csv_reader(&file.path)
         .has_headers(engine.first_line_as_name)
-        .from_path(file.path)?;
+        .from_reader(input);
 
     let mut metadata = SimpleResultSetMetadata::new(Some(file.result_name));
 
+    let mut column_types = Vec::new();
     if engine.first_line_as_name {
-        let header = reader.headers()?;
-        for h in header {
-            metadata.add_column(h);
+        add_header(engine, &mut metadata, &mut column_types, reader.headers()?);
+    }
+    // Without a header line, the first row is needed for the column names
+    let rows_to_read = match (engine.headers_only, engine.first_line_as_name) {
+        (false, _) => usize::MAX,
+        (true, true) => 0,
+        (true, false) => 1,
+    };
+    let mut data = Vec::new();
+    for records in reader.records().take(rows_to_read) {
+        let records = records?;
+        if is_blank(&records) {
+            continue;
+        }
+        let quoted = match (&raw, records.position()) {
+            (Some(raw), Some(position)) => {
+                quoted_fields(&raw[position.byte() as usize..], delimiter)
+            }
+            _ => vec![],
+        };
+        data.push(read_row(
+            engine,
+            &mut metadata,
+            &mut column_types,
+            &records,
+            &quoted,
+        )?);
+    }
+    let metadata = Rc::new(metadata.build());
+    let data = ResultsData::new(data);
+    let results = ResultSet { metadata, data };
+
+    Ok(results)
+}
+
+pub(crate) fn add_header(
+    engine: &Engine,
+    metadata: &mut SimpleResultSetMetadata,
+    column_types: &mut Vec<(String, Option<ColumnType>)>,
+    header: &StringRecord,
+) {
+    for h in header {
+        let h = engine.header_title(h);
+        metadata.add_column(&h);
+        let column_type = engine.column_type(&h);
+        column_types.push((h, column_type));
+    }
+}
+
+/// Read the cells of a record, adding default columns for the cells beyond the header.
+pub(crate) fn read_row(
+    engine: &Engine,
+    metadata: &mut SimpleResultSetMetadata,
+    column_types: &mut Vec<(String, Option<ColumnType>)>,
+    records: &StringRecord,
+    quoted: &[bool],
+) -> Result<DataRow, CvsSqlError> {
+    let mut values = Vec::new();
+    for (index, record) in records.iter().enumerate() {
+        if index >= metadata.len() {
+            let title = engine.default_header(index);
+            metadata.add_column(&title);
+            let column_type = engine.column_type(&title);
+            column_types.push((title, column_type));
+        }
+        let (title, column_type) = &column_types[index];
+        if record.is_empty() && quoted.get(index).is_some_and(|quoted| *quoted) {
+            values.push(Value::Str(String::new()));
+        } else {
+            values.push(read_cell(engine, title, *column_type, record)?);
         }
     }
+    Ok(DataRow::new(values))
+}
+
+/// A line with nothing but whitespace. The reader already skips the empty lines, and a single
+/// empty field (`""`) is a value, not a blank line.
+pub(crate) fn is_blank(record: &StringRecord) -> bool {
+    record.len() == 1 && !record[0].is_empty() && record[0].trim().is_empty()
+}
+
+pub(crate) fn quoted_fields(raw: &[u8], delimiter: u8) -> Vec<bool> {
+    let mut quoted = vec![];
+    let mut bytes = raw.iter().peekable();
+    loop {
+        let is_quoted = bytes.peek() == Some(&&b'"');
+        quoted.push(is_quoted);
+        if is_quoted {
+            bytes.next();
+            while let Some(byte) = bytes.next() {
+                if *byte == b'"' {
+                    if bytes.peek() == Some(&&b'"') {
+                        bytes.next();
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+        loop {
+            match bytes.next() {
+                Some(byte) if *byte == delimiter => break,
+                Some(b'\n') | Some(b'\r') | None => return quoted,
+                _ => {}
+            }
+        }
+    }
+}
+
+pub(crate) fn read_cell(
+    engine: &Engine,
+    title: &str,
+    column_type: Option<ColumnType>,
+    record: &str,
+) -> Result<Value, CvsSqlError> {
+    if engine.null_tokens.iter().any(|token| token == record) {
+        return Ok(Value::Empty);
+    }
+    match column_type {
+        Some(column_type) => column_type.parse(title, record),
+        None => Ok(
+            match parse_formatted_number(record, engine.numeric_locale, engine.strip_currency) {
+                Some(number) => Value::Number(number),
+                None => Value::from(record),
+            },
+        ),
+    }
+}
+
+fn read_list_file(engine: &Engine, path: &Path, name: Name) -> Result<ResultSet, CvsSqlError> {
+    let title = get_default_header(0);
+    let column_type = engine.column_type(&title);
+    let mut metadata = SimpleResultSetMetadata::new(Some(name));
+    metadata.add_column(&title);
     let mut data = Vec::new();
-    for records in reader.records() {
+    for line in fs::read_to_string(path)?.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let value = read_cell(engine, &title, column_type, line)?;
+        data.push(DataRow::new(vec![value]));
+    }
+    let metadata = Rc::new(metadata.build());
+    let data = ResultsData::new(data);
+    Ok(ResultSet { metadata, data })
+}
+
+fn read_xlsx_file(
+    engine: &Engine,
+    path: &Path,
+    sheet: Option<&str>,
+    name: Name,
+) -> Result<ResultSet, CvsSqlError> {
+    let mut workbook: Xlsx<_> = open_workbook(path)?;
+    let range = match sheet {
+        Some(sheet) => workbook.worksheet_range(sheet)?,
+        None => workbook
+            .worksheet_range_at(0)
+            .unwrap_or_else(|| Ok(Range::empty()))?,
+    };
+    let mut rows = range.rows();
+
+    let mut metadata = SimpleResultSetMetadata::new(Some(name));
+    let mut column_types = Vec::new();
+    if engine.first_line_as_name
+        && let Some(header) = rows.next()
+    {
+        for cell in header {
+            let title = engine.header_title(&cell.to_string());
+            metadata.add_column(&title);
+            let column_type = engine.column_type(&title);
+            column_types.push((title, column_type));
+        }
+    }
+    let mut data = Vec::new();
+    for row in rows {
         let mut values = Vec::new();
-        let records = records?;
-        for (index, record) in records.iter().enumerate() {
-            let value = Value::from(record);
-            values.push(value);
+        for (index, cell) in row.iter().enumerate() {
             if index >= metadata.len() {
-                metadata.add_column(&get_default_header(index));
+                let title = engine.default_header(index);
+                metadata.add_column(&title);
+                let column_type = engine.column_type(&title);
+                column_types.push((title, column_type));
             }
+            let (title, column_type) = &column_types[index];
+            values.push(read_xlsx_cell(engine, title, *column_type, cell)?);
         }
-        let values = DataRow::new(values);
-        data.push(values);
+        data.push(DataRow::new(values));
     }
     let metadata = Rc::new(metadata.build());
     let data = ResultsData::new(data);
-    let results = ResultSet { metadata, data };
+    Ok(ResultSet { metadata, data })
+}
 
-    Ok(results)
+fn read_xlsx_cell(
+    engine: &Engine,
+    title: &str,
+    column_type: Option<ColumnType>,
+    cell: &Data,
+) -> Result<Value, CvsSqlError> {
+    Ok(match cell {
+        Data::Empty => Value::Empty,
+        Data::String(text) => return read_cell(engine, title, column_type, text),
+        _ if column_type.is_some() => {
+            return read_cell(engine, title, column_type, &cell.to_string());
+        }
+        Data::Int(number) => Value::Number((*number).into()),
+        Data::Float(number) => match BigDecimal::from_str(&number.to_string()) {
+            Ok(number) => Value::Number(number.normalized()),
+            Err(_) => Value::Str(number.to_string()),
+        },
+        Data::Bool(value) => Value::Bool(*value),
+        Data::DateTime(_) | Data::DateTimeIso(_) => match cell.as_datetime() {
+            Some(timestamp) => Value::Timestamp(timestamp),
+            None => Value::Str(cell.to_string()),
+        },
+        _ => Value::Str(cell.to_string()),
+    })
+}
+
+fn read_json_file(path: &Path, name: Name) -> Result<ResultSet, CvsSqlError> {
+    let reader = BufReader::new(File::open(path)?);
+    let json: JsonValue = serde_json::from_reader(reader)?;
+    let JsonValue::Array(items) = json else {
+        return Err(CvsSqlError::NotJsonTable(name.full_name()));
+    };
+    let mut objects = Vec::with_capacity(items.len());
+    let mut columns: Vec<String> = Vec::new();
+    for item in items {
+        let JsonValue::Object(object) = item else {
+            return Err(CvsSqlError::NotJsonTable(name.full_name()));
+        };
+        for key in object.keys() {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+        objects.push(object);
+    }
+
+    let mut metadata = SimpleResultSetMetadata::new(Some(name));
+    for column in &columns {
+        metadata.add_column(column);
+    }
+    let data = objects
+        .into_iter()
+        .map(|mut object| {
+            let values = columns
+                .iter()
+                .map(|column| json_to_value(object.remove(column)))
+                .collect();
+            DataRow::new(values)
+        })
+        .collect();
+    let metadata = Rc::new(metadata.build());
+    let data = ResultsData::new(data);
+    Ok(ResultSet { metadata, data })
+}
+
+fn json_to_value(json: Option<JsonValue>) -> Value {
+    match json {
+        None | Some(JsonValue::Null) => Value::Empty,
+        Some(JsonValue::Bool(b)) => Value::Bool(b),
+        Some(JsonValue::Number(n)) => match BigDecimal::from_str(&n.to_string()) {
+            Ok(n) => Value::Number(n),
+            Err(_) => Value::Str(n.to_string()),
+        },
+        Some(JsonValue::String(s)) => Value::Str(s),
+        Some(json) => Value::Str(json.to_string()),
+    }
 }
 #[cfg(test)]
 mod tests {
     use std::fs::{self, File};
     use std::io::Write;
 
+    use chrono::NaiveDate;
+    use clap::Parser;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
     use tempfile::tempdir;
 
-    use crate::{args::Args, engine::Engine, error::CvsSqlError, results::Column};
+    use crate::args::NumericLocale;
+    use crate::console::execute_commands;
+    use crate::outputer::create_outputer;
+    use crate::{
+        args::Args, engine::Engine, engine::EngineError, error::CvsSqlError, results::Column,
+        results::ColumnIndexError, value::Value,
+    };
 
     #[test]
     fn read_file_with_missing_headers() -> Result<(), CvsSqlError> {
@@ -139,6 +448,87 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn read_file_with_numbered_columns() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        let table = working_dir.path().join("tab.csv");
+        fs::write(table, "1,a\n2\n3,c,z\n")?;
+
+        let args = Args {
+            no_headers: true,
+            home: Some(working_dir.path().to_path_buf()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands("SELECT * FROM tab")?;
+        let results = &results.first().unwrap().results;
+        assert_eq!(results.metadata.number_of_columns(), 3);
+        assert_eq!(
+            results.metadata.column_title(&Column::from_index(0)),
+            "col1"
+        );
+        assert_eq!(
+            results.metadata.column_title(&Column::from_index(1)),
+            "col2"
+        );
+        assert_eq!(
+            results.metadata.column_title(&Column::from_index(2)),
+            "col3"
+        );
+
+        let results = engine.execute_commands("SELECT col2, col3 FROM tab ORDER BY col1")?;
+        let results = &results.first().unwrap().results;
+        let rows: Vec<_> = results
+            .data
+            .iter()
+            .map(|row| {
+                (
+                    row.get(&Column::from_index(0)).clone(),
+                    row.get(&Column::from_index(1)).clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (Value::Str("a".into()), Value::Empty),
+                (Value::Empty, Value::Empty),
+                (Value::Str("c".into()), Value::Str("z".into())),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn select_columns_by_position() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        let table = working_dir.path().join("tab.csv");
+        fs::write(table, "1,a,x\n2,b,y\n")?;
+
+        let args = Args {
+            first_line_as_data: true,
+            home: Some(working_dir.path().to_path_buf()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands("SELECT $1, @3 FROM tab WHERE $2 = 'b'")?;
+        let results = &results.first().unwrap().results;
+        assert_eq!(results.metadata.column_title(&Column::from_index(0)), "A$");
+        assert_eq!(results.metadata.column_title(&Column::from_index(1)), "C$");
+        let rows: Vec<_> = results.data.iter().collect();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get(&Column::from_index(0)), &Value::from("2"));
+        assert_eq!(rows[0].get(&Column::from_index(1)), &Value::from("y"));
+
+        let err = engine.execute_commands("SELECT $4 FROM tab").err().unwrap();
+        assert!(matches!(err, CvsSqlError::ColumnIndexError(_)));
+
+        Ok(())
+    }
+
     #[test]
     fn read_no_headers_large_file() -> Result<(), CvsSqlError> {
         let working_dir = tempdir()?;
@@ -188,4 +578,642 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn read_list_file() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        fs::write(
+            working_dir.path().join("names.txt"),
+            "Bob\n\n  Alice \r\n12\n",
+        )?;
+
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands("SELECT * FROM \"names.txt\"")?;
+        let results = &results.first().unwrap().results;
+        assert_eq!(results.metadata.number_of_columns(), 1);
+        assert_eq!(results.metadata.column_title(&Column::from_index(0)), "A$");
+        let rows: Vec<_> = results.data.iter().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].get(&Column::from_index(0)), &Value::from("Bob"));
+        assert_eq!(rows[1].get(&Column::from_index(0)), &Value::from("Alice"));
+        assert_eq!(
+            rows[2].get(&Column::from_index(0)),
+            &Value::Number(12.into())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_gzip_file() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        for (source, target) in [("sales", "sales.csv.gz"), ("customers", "customers.gz")] {
+            let mut encoder = GzEncoder::new(
+                File::create(working_dir.path().join(target))?,
+                Compression::default(),
+            );
+            encoder.write_all(&fs::read(format!("tests/data/{source}.csv"))?)?;
+            encoder.finish()?;
+        }
+
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+        let gzipped = engine.execute_commands(
+            "SELECT COUNT(*), SUM(price) FROM sales; SELECT COUNT(*), MAX(name) FROM customers",
+        )?;
+        let engine = Engine::try_from(&Args::default())?;
+        let plain = engine.execute_commands(
+            "SELECT COUNT(*), SUM(price) FROM tests.data.sales; SELECT COUNT(*), MAX(name) FROM tests.data.customers",
+        )?;
+
+        for (gzipped, plain) in gzipped.iter().zip(plain.iter()) {
+            let gzipped = gzipped.results.data.iter().next().unwrap();
+            let plain = plain.results.data.iter().next().unwrap();
+            for index in 0..2 {
+                let column = Column::from_index(index);
+                assert_eq!(gzipped.get(&column), plain.get(&column));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_tsv_file() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        fs::write(
+            working_dir.path().join("tab.tsv"),
+            "name\tcity\tsize\nJohn\tNew York, NY\t10\n\"Jane\tDoe\"\tParis\t\"\"\n",
+        )?;
+
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            empty_as_string: true,
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+        let results = engine.execute_commands("SELECT * FROM tab")?;
+
+        let results = &results.first().unwrap().results;
+        assert_eq!(results.metadata.number_of_columns(), 3);
+        let rows: Vec<_> = results.data.iter().collect();
+        assert_eq!(
+            rows[0].get(&Column::from_index(1)),
+            &Value::Str("New York, NY".into())
+        );
+        assert_eq!(
+            rows[0].get(&Column::from_index(2)),
+            &Value::Number(10.into())
+        );
+        assert_eq!(
+            rows[1].get(&Column::from_index(0)),
+            &Value::Str("Jane\tDoe".into())
+        );
+        assert_eq!(
+            rows[1].get(&Column::from_index(2)),
+            &Value::Str(String::new())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_file_with_input_delimiter() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        fs::write(working_dir.path().join("tab.csv"), "a;b\n1,5;2\n")?;
+
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            input_delimiter: Some(';'),
+            write_mode: true,
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+        let results = engine.execute_commands("SELECT b FROM tab WHERE a = '1,5'")?;
+        let row = results.first().unwrap().results.data.iter().next().unwrap();
+        assert_eq!(row.get(&Column::from_index(0)), &Value::Number(2.into()));
+
+        engine.execute_commands("INSERT INTO tab VALUES ('3', 4)")?;
+        assert_eq!(
+            fs::read_to_string(working_dir.path().join("tab.csv"))?,
+            "a;b\n1,5;2\n3;4\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_file_with_comments() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        fs::write(
+            working_dir.path().join("tab.csv"),
+            "# generated on 2024-01-01\n# by the nightly job\nid,name\n1,a\n\n  \n# removed 2\n3,c\n",
+        )?;
+
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            comment: Some('#'),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+        let results = engine.execute_commands("SELECT COUNT(*), MAX(name) FROM tab")?;
+        let row = results.first().unwrap().results.data.iter().next().unwrap();
+        assert_eq!(row.get(&Column::from_index(0)), &Value::Number(2.into()));
+        assert_eq!(row.get(&Column::from_index(1)), &Value::Str("c".into()));
+
+        let results = engine.execute_commands("SELECT id FROM tab WHERE name = 'c'")?;
+        let row = results.first().unwrap().results.data.iter().next().unwrap();
+        assert_eq!(row.get(&Column::from_index(0)), &Value::Number(3.into()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_null_tokens() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        fs::write(
+            working_dir.path().join("tab.csv"),
+            "id,score\n1,10\n2,NA\n3,NULL\n4,na\n",
+        )?;
+        let query = "SELECT id FROM tab WHERE score IS NULL";
+
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+        let results = engine.execute_commands(query)?;
+        assert_eq!(results.first().unwrap().results.data.iter().count(), 0);
+
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            null_token: vec!["NA".into(), "NULL".into()],
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+        let results = engine.execute_commands(query)?;
+        let ids: Vec<_> = results
+            .first()
+            .unwrap()
+            .results
+            .data
+            .iter()
+            .map(|row| row.get(&Column::from_index(0)).clone())
+            .collect();
+        assert_eq!(ids, vec![Value::Number(2.into()), Value::Number(3.into())]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn input_delimiter_must_be_a_single_byte() {
+        let args = Args {
+            input_delimiter: Some('§'),
+            ..Args::default()
+        };
+
+        assert!(matches!(
+            Engine::try_from(&args),
+            Err(EngineError::InvalidDelimiter('§'))
+        ));
+    }
+
+    #[test]
+    fn validate_reads_only_the_headers() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        fs::write(
+            working_dir.path().join("tab.csv"),
+            b"a,b\n1,2\n\xff\xfe,3\n",
+        )?;
+
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            validate: true,
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands("SELECT a, b FROM tab WHERE a > 1")?;
+        let results = &results.first().unwrap().results;
+        assert_eq!(results.metadata.number_of_columns(), 2);
+        assert_eq!(results.data.iter().count(), 0);
+
+        let results = engine.execute_commands("SELECT c FROM tab");
+        assert!(matches!(
+            results,
+            Err(CvsSqlError::ColumnIndexError(
+                ColumnIndexError::NoSuchColumn(_)
+            ))
+        ));
+
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+        assert!(engine.execute_commands("SELECT a FROM tab").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_does_not_write() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        fs::write(working_dir.path().join("tab.csv"), "a,b\n1,2\n")?;
+
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            write_mode: true,
+            validate: true,
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+        let mut outputer = create_outputer(&args)?;
+        let commands = vec![
+            "INSERT INTO tab VALUES (3, 4)".to_string(),
+            "UPDATE tab SET a = 5".to_string(),
+            "CREATE TABLE new_tab (c INT)".to_string(),
+        ];
+
+        let success = execute_commands(&engine, &commands, true, outputer.as_mut())?;
+
+        assert!(success);
+        assert_eq!(
+            fs::read_to_string(working_dir.path().join("tab.csv"))?,
+            "a,b\n1,2\n"
+        );
+        assert!(!working_dir.path().join("new_tab.csv").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_json_file() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        fs::create_dir_all(&working_dir)?;
+        let table = working_dir.path().join("tab.json");
+        fs::write(
+            table,
+            r#"[{"a": 1, "b": "x"}, {"b": null, "c": true}, {"a": {"d": [1]}}]"#,
+        )?;
+
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands("SELECT * FROM tab")?;
+        let results = &results.first().unwrap().results;
+        assert_eq!(results.metadata.number_of_columns(), 3);
+        assert_eq!(results.metadata.column_title(&Column::from_index(0)), "a");
+        assert_eq!(results.metadata.column_title(&Column::from_index(1)), "b");
+        assert_eq!(results.metadata.column_title(&Column::from_index(2)), "c");
+
+        let rows: Vec<_> = results.data.iter().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].get(&Column::from_index(0)), &Value::from("1"));
+        assert_eq!(rows[0].get(&Column::from_index(1)), &Value::Str("x".into()));
+        assert_eq!(rows[0].get(&Column::from_index(2)), &Value::Empty);
+        assert_eq!(rows[1].get(&Column::from_index(1)), &Value::Empty);
+        assert_eq!(rows[1].get(&Column::from_index(2)), &Value::Bool(true));
+        assert_eq!(
+            rows[2].get(&Column::from_index(0)),
+            &Value::Str(r#"{"d":[1]}"#.into())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_json_file_that_is_not_an_array() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        fs::create_dir_all(&working_dir)?;
+        let table = working_dir.path().join("tab.json");
+        fs::write(table, r#"{"a": 1}"#)?;
+
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands("SELECT * FROM tab");
+        assert!(matches!(results, Err(CvsSqlError::NotJsonTable(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_us_formatted_numbers() -> Result<(), CvsSqlError> {
+        let args = Args {
+            numeric_locale: Some(NumericLocale::Us),
+            strip_currency: true,
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results =
+            engine.execute_commands("SELECT SUM(price), MAX(price) FROM tests.data.us_prices")?;
+        let results = &results.first().unwrap().results;
+        let row = results.data.iter().next().unwrap();
+        assert_eq!(row.get(&Column::from_index(0)), &Value::from("3051.49"));
+        assert_eq!(row.get(&Column::from_index(1)), &Value::from("2005.00"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_eu_formatted_numbers() -> Result<(), CvsSqlError> {
+        let args = Args {
+            numeric_locale: Some(NumericLocale::Eu),
+            strip_currency: true,
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results =
+            engine.execute_commands("SELECT SUM(price), MIN(price) FROM tests.data.eu_prices")?;
+        let results = &results.first().unwrap().results;
+        let row = results.data.iter().next().unwrap();
+        assert_eq!(row.get(&Column::from_index(0)), &Value::from("3051.49"));
+        assert_eq!(row.get(&Column::from_index(1)), &Value::from("-1000.00"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn formatted_numbers_are_strings_by_default() -> Result<(), CvsSqlError> {
+        let args = Args::default();
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands("SELECT price FROM tests.data.us_prices")?;
+        let results = &results.first().unwrap().results;
+        let row = results.data.iter().next().unwrap();
+        assert_eq!(
+            row.get(&Column::from_index(0)),
+            &Value::Str("$1,234.50".into())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_string_columns() -> Result<(), CvsSqlError> {
+        let args = Args {
+            string_columns: vec!["id".into(), "zip".into()],
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands("SELECT id, zip FROM tests.data.zip_codes")?;
+        let results = &results.first().unwrap().results;
+        let rows: Vec<_> = results.data.iter().collect();
+        assert_eq!(
+            rows[0].get(&Column::from_index(0)),
+            &Value::Str("00123".into())
+        );
+        assert_eq!(
+            rows[0].get(&Column::from_index(1)),
+            &Value::Str("02134".into())
+        );
+        assert_eq!(rows[2].get(&Column::from_index(1)), &Value::Empty);
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_quoted_empty_fields_as_strings() -> Result<(), CvsSqlError> {
+        let args = Args {
+            empty_as_string: true,
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results =
+            engine.execute_commands("SELECT name, nickname FROM tests.data.empty_strings")?;
+        let results = &results.first().unwrap().results;
+        let rows: Vec<_> = results.data.iter().collect();
+        assert_eq!(
+            rows[0].get(&Column::from_index(1)),
+            &Value::Str(String::new())
+        );
+        assert_eq!(rows[1].get(&Column::from_index(1)), &Value::Empty);
+        assert_eq!(
+            rows[2].get(&Column::from_index(0)),
+            &Value::Str("Carol, Jr.".into())
+        );
+        assert_eq!(
+            rows[2].get(&Column::from_index(1)),
+            &Value::Str(String::new())
+        );
+        assert_eq!(
+            rows[3].get(&Column::from_index(0)),
+            &Value::Str(String::new())
+        );
+        assert_eq!(
+            rows[4].get(&Column::from_index(1)),
+            &Value::Str("Evie".into())
+        );
+
+        let results = engine.execute_commands(
+            "SELECT COUNT(*) FROM tests.data.empty_strings WHERE nickname IS NULL",
+        )?;
+        let results = &results.first().unwrap().results;
+        let rows: Vec<_> = results.data.iter().collect();
+        assert_eq!(
+            rows[0].get(&Column::from_index(0)),
+            &Value::Number(1.into())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn quoted_empty_fields_are_null_by_default() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let results = engine.execute_commands(
+            "SELECT COUNT(*) FROM tests.data.empty_strings WHERE nickname IS NULL",
+        )?;
+        let results = &results.first().unwrap().results;
+        let rows: Vec<_> = results.data.iter().collect();
+        assert_eq!(
+            rows[0].get(&Column::from_index(0)),
+            &Value::Number(3.into())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_trimmed_headers() -> Result<(), CvsSqlError> {
+        let args = Args {
+            trim_headers: true,
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands(
+            "SELECT Name, \"Total Price\" FROM tests.data.padded_headers WHERE \"Customer ID\" = 2",
+        )?;
+        let results = &results.first().unwrap().results;
+        let rows: Vec<_> = results.data.iter().collect();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get(&Column::from_index(0)), &Value::from("Bob"));
+        assert_eq!(
+            rows[0].get(&Column::from_index(1)),
+            &Value::Number(20.into())
+        );
+
+        let results = engine.execute_commands("SELECT name FROM tests.data.padded_headers");
+        assert!(results.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_trimmed_lower_case_headers() -> Result<(), CvsSqlError> {
+        let args = Args {
+            trim_headers: true,
+            lower_headers: true,
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands(
+            "SELECT name, \"total price\" FROM tests.data.padded_headers WHERE \"customer id\" = 3",
+        )?;
+        let results = &results.first().unwrap().results;
+        assert_eq!(
+            results.metadata.column_title(&Column::from_index(0)),
+            "name"
+        );
+        let rows: Vec<_> = results.data.iter().collect();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get(&Column::from_index(0)), &Value::from("Carol"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn headers_are_not_trimmed_by_default() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let results = engine.execute_commands("SELECT Name FROM tests.data.padded_headers");
+        assert!(results.is_err());
+        let results = engine.execute_commands("SELECT \" Name\" FROM tests.data.padded_headers")?;
+        let results = &results.first().unwrap().results;
+        assert_eq!(results.data.iter().count(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_all_columns_as_text() -> Result<(), CvsSqlError> {
+        let args = Args {
+            all_text: true,
+            first_line_as_data: true,
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands("SELECT A$, B$ FROM tests.data.zip_codes")?;
+        let results = &results.first().unwrap().results;
+        let rows: Vec<_> = results.data.iter().collect();
+        assert_eq!(
+            rows[1].get(&Column::from_index(0)),
+            &Value::Str("00123".into())
+        );
+        assert_eq!(
+            rows[2].get(&Column::from_index(1)),
+            &Value::Str("10001".into())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn no_infer_keeps_the_cells_as_text() -> Result<(), CvsSqlError> {
+        let query = "SELECT \"zip\" FROM tests.data.zip_codes";
+        let args = Args::try_parse_from(["csvsql", "--no-infer"]).unwrap();
+        let engine = Engine::try_from(&args)?;
+        let results = engine.execute_commands(query)?;
+        let row = results.first().unwrap().results.data.iter().next().unwrap();
+        assert_eq!(row.get(&Column::from_index(0)), &Value::Str("02134".into()));
+
+        let args = Args::try_parse_from(["csvsql"]).unwrap();
+        let engine = Engine::try_from(&args)?;
+        let results = engine.execute_commands(query)?;
+        let row = results.first().unwrap().results.data.iter().next().unwrap();
+        assert_eq!(row.get(&Column::from_index(0)), &Value::Number(2134.into()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn leading_zeros_are_lost_by_default() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let results = engine.execute_commands("SELECT id FROM tests.data.zip_codes")?;
+        let results = &results.first().unwrap().results;
+        let row = results.data.iter().next().unwrap();
+        assert_eq!(row.get(&Column::from_index(0)), &Value::from("123"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_with_schema() -> Result<(), CvsSqlError> {
+        let args = Args {
+            schema: Some("tests/data/zip_codes_schema.toml".into()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands("SELECT zip, opened FROM tests.data.zip_codes")?;
+        let results = &results.first().unwrap().results;
+        let rows: Vec<_> = results.data.iter().collect();
+        assert_eq!(
+            rows[0].get(&Column::from_index(0)),
+            &Value::Str("02134".into())
+        );
+        assert_eq!(
+            rows[0].get(&Column::from_index(1)),
+            &Value::Date(NaiveDate::from_ymd_opt(2020, 1, 15).unwrap())
+        );
+        assert_eq!(rows[2].get(&Column::from_index(1)), &Value::Empty);
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_with_schema_mismatch() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        fs::write(
+            working_dir.path().join("tab.csv"),
+            "day\n2020-01-01\nyesterday\n",
+        )?;
+        let schema = working_dir.path().join("schema.toml");
+        fs::write(&schema, "day = \"date\"\n")?;
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            schema: Some(schema),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands("SELECT * FROM tab");
+        assert!(matches!(results, Err(CvsSqlError::SchemaMismatch(_, _, _))));
+
+        Ok(())
+    }
 }
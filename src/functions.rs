@@ -1,22 +1,39 @@
-use std::{ops::Deref, str::FromStr};
+use std::{cell::RefCell, ops::Deref, rc::Rc, str::FromStr};
 
 use crate::{
     engine::Engine,
     error::CvsSqlError,
+    extract_time::{Field, extract_field},
     group_by::GroupRow,
+    list_agg::create_list_agg,
+    order_by_results::{OrderByItem, compare_keys},
+    outputer::value_as_json,
     projections::{Projection, SingleConvert},
     result_set_metadata::Metadata,
+    results::Name,
+    to_json::create_to_json,
     util::SmartReference,
     value::Value,
 };
+use base64::{Engine as _, prelude::BASE64_STANDARD};
 use bigdecimal::FromPrimitive;
 use bigdecimal::ToPrimitive;
 use bigdecimal::{BigDecimal, Zero};
-use chrono::{TimeZone, Utc, offset::LocalResult};
+use chrono::{
+    Datelike, Duration, Months, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    format::{Parsed, StrftimeItems, parse},
+    offset::LocalResult,
+};
 use itertools::Itertools;
+use rand::RngExt;
+use rand::rngs::StdRng;
 use regex::Regex;
+use serde_json::Value as JsonValue;
+use sha1::Digest;
+use sqlparser::ast::Value as AstValue;
 use sqlparser::ast::{
-    DuplicateTreatment, Function, FunctionArg, FunctionArgExpr, FunctionArguments,
+    DuplicateTreatment, Expr, Function, FunctionArg, FunctionArgExpr, FunctionArgumentClause,
+    FunctionArguments,
 };
 
 impl SingleConvert for Function {
@@ -25,27 +42,39 @@ impl SingleConvert for Function {
         metadata: &Metadata,
         engine: &Engine,
     ) -> Result<Box<dyn Projection>, CvsSqlError> {
-        if !self.within_group.is_empty() {
+        let name = self.name.to_string().to_uppercase();
+        if !self.within_group.is_empty() && name != "LISTAGG" {
             return Err(CvsSqlError::Unsupported("WITHIN GROUP".into()));
         }
 
         if self.over.is_some() {
-            return Err(CvsSqlError::Unsupported("OVER".into()));
+            // The window functions are calculated before the projection (see `add_window_columns`)
+            let column: Name = self.to_string().into();
+            if metadata.column_index(&column).is_ok() {
+                return column.convert_single(metadata, engine);
+            }
+            return Err(CvsSqlError::Unsupported(format!(
+                "{self} outside of the selected columns"
+            )));
         }
 
         if self.null_treatment.is_some() {
             return Err(CvsSqlError::Unsupported("IGNORE/RESPECT NULLS".into()));
         }
 
-        if self.filter.is_some() {
-            return Err(CvsSqlError::Unsupported("FILTER".into()));
-        }
         if self.parameters != FunctionArguments::None {
             return Err(CvsSqlError::Unsupported("function parameters".into()));
         }
 
-        let name = self.name.to_string().to_uppercase();
-        build_function_from_name(&name, metadata, engine, &self.args)
+        if name == "LISTAGG" {
+            if let Some(filter) = &self.filter {
+                return Err(CvsSqlError::Unsupported(format!(
+                    "{name} with FILTER (WHERE {filter})"
+                )));
+            }
+            return create_list_agg(metadata, engine, &self.args, &self.within_group);
+        }
+        build_function_from_name(&name, metadata, engine, &self.args, self.filter.as_deref())
     }
 }
 fn build_function_from_name(
@@ -53,14 +82,47 @@ fn build_function_from_name(
     metadata: &Metadata,
     engine: &Engine,
     args: &FunctionArguments,
+    filter: Option<&Expr>,
 ) -> Result<Box<dyn Projection>, CvsSqlError> {
-    match name {
-        "COUNT" => build_aggregator_function(metadata, engine, args, Box::new(Count {})),
-        "AVG" => build_aggregator_function(metadata, engine, args, Box::new(Avg {})),
-        "SUM" => build_aggregator_function(metadata, engine, args, Box::new(Sum {})),
-        "MIN" => build_aggregator_function(metadata, engine, args, Box::new(Min {})),
-        "MAX" => build_aggregator_function(metadata, engine, args, Box::new(Max {})),
-        "ANY_VALUE" => build_aggregator_function(metadata, engine, args, Box::new(AnyValue {})),
+    // Only the aggregations take the filter, any other function with a filter is unsupported.
+    let mut filter = filter;
+    let function = match name {
+        "COUNT" => {
+            build_aggregator_function(metadata, engine, args, filter.take(), Box::new(Count {}))
+        }
+        "COUNT_IF" => {
+            build_aggregator_function(metadata, engine, args, filter.take(), Box::new(CountIf {}))
+        }
+        "AVG" => build_aggregator_function(metadata, engine, args, filter.take(), Box::new(Avg {})),
+        "SUM" => build_aggregator_function(metadata, engine, args, filter.take(), Box::new(Sum {})),
+        "MIN" => build_aggregator_function(metadata, engine, args, filter.take(), Box::new(Min {})),
+        "MAX" => build_aggregator_function(metadata, engine, args, filter.take(), Box::new(Max {})),
+        "ANY_VALUE" | "ARBITRARY" => {
+            build_aggregator_function(metadata, engine, args, filter.take(), Box::new(AnyValue {}))
+        }
+        "BOOL_AND" | "EVERY" => {
+            build_aggregator_function(metadata, engine, args, filter.take(), Box::new(BoolAnd {}))
+        }
+        "BOOL_OR" | "ANY" | "SOME" => {
+            build_aggregator_function(metadata, engine, args, filter.take(), Box::new(BoolOr {}))
+        }
+        "ARRAY_AGG" => {
+            build_aggregator_function(metadata, engine, args, filter.take(), Box::new(ArrayAgg {}))
+        }
+        "GROUP_CONCAT" | "STRING_AGG" => {
+            build_group_concat(name, metadata, engine, args, filter.take())
+        }
+        "MEDIAN" => {
+            build_aggregator_function(metadata, engine, args, filter.take(), Box::new(Median {}))
+        }
+        "PERCENTILE_CONT" => build_percentile_cont(metadata, engine, args, filter.take()),
+        "CORR" => build_pair_aggregator_function(metadata, engine, args, Box::new(Corr {})),
+        "COVAR_POP" => {
+            build_pair_aggregator_function(metadata, engine, args, Box::new(CovarPop {}))
+        }
+        "COVAR_SAMP" | "COVAR" => {
+            build_pair_aggregator_function(metadata, engine, args, Box::new(CovarSamp {}))
+        }
 
         "ABS" => build_function(metadata, engine, args, Box::new(Abs {})),
         "ASCII" => build_function(metadata, engine, args, Box::new(Ascii {})),
@@ -80,26 +142,63 @@ fn build_function_from_name(
         "FORMAT" | "DATE_FORMAT" | "TIME_FORMAT" | "TO_CHAR" => {
             build_function(metadata, engine, args, Box::new(Format {}))
         }
+        "STR_TO_DATE" | "TO_DATE" => build_function(metadata, engine, args, Box::new(StrToDate {})),
+        "UNIX_TIMESTAMP" => build_function(metadata, engine, args, Box::new(UnixTimestamp {})),
+        "FORMAT_BYTES" => build_function(metadata, engine, args, Box::new(FormatBytes {})),
         "TO_TIMESTAMP" | "FROM_UNIXTIME" => {
             build_function(metadata, engine, args, Box::new(ToTimestamp {}))
         }
+        "DATEDIFF" => build_function(metadata, engine, args, Box::new(DateDiff {})),
+        "TIMESTAMPDIFF" => {
+            build_function_with_unit(metadata, engine, args, 0, Box::new(TimestampDiff {}))
+        }
+        "DATE_ADD" | "ADDDATE" => {
+            build_function_with_unit(metadata, engine, args, 2, Box::new(DateAdd {}))
+        }
+        "DATE_SUB" | "SUBDATE" => {
+            build_function_with_unit(metadata, engine, args, 2, Box::new(DateSub {}))
+        }
         "GREATEST" => build_function(metadata, engine, args, Box::new(Greatest {})),
         "IF" => build_function(metadata, engine, args, Box::new(If {})),
         "NULLIF" => build_function(metadata, engine, args, Box::new(NullIf {})),
+        "DECODE" => build_function(metadata, engine, args, Box::new(Decode {})),
         "LOWER" | "LCASE" => build_function(metadata, engine, args, Box::new(Lower {})),
+        "INITCAP" => build_function(metadata, engine, args, Box::new(InitCap {})),
         "UPPER" | "UCASE" => build_function(metadata, engine, args, Box::new(Upper {})),
         "LEAST" => build_function(metadata, engine, args, Box::new(Least {})),
         "LEFT" => build_function(metadata, engine, args, Box::new(Left {})),
         "RIGHT" => build_function(metadata, engine, args, Box::new(Right {})),
         "LPAD" => build_function(metadata, engine, args, Box::new(Lpad {})),
         "RPAD" => build_function(metadata, engine, args, Box::new(Rpad {})),
+        "SPACE" => build_function(metadata, engine, args, Box::new(Space {})),
         "LTRIM" => build_function(metadata, engine, args, Box::new(Ltrim {})),
         "RTRIM" => build_function(metadata, engine, args, Box::new(Rtrim {})),
         "PI" => build_function(metadata, engine, args, Box::new(Pi {})),
-        "RANDOM" | "RAND" => build_function(metadata, engine, args, Box::new(Random {})),
-        "POSITION" | "LOCATE" => build_function(metadata, engine, args, Box::new(Position {})),
+        "DEGREES" => build_function(metadata, engine, args, Box::new(Degrees {})),
+        "RADIANS" => build_function(metadata, engine, args, Box::new(Radians {})),
+        "RANDOM" | "RAND" => build_function(
+            metadata,
+            engine,
+            args,
+            Box::new(Random { rng: engine.rng() }),
+        ),
+        "POSITION" | "LOCATE" | "CHARINDEX" => {
+            build_function(metadata, engine, args, Box::new(Position {}))
+        }
+        "INSTR" => build_function(metadata, engine, args, Box::new(Instr {})),
         "REPEAT" => build_function(metadata, engine, args, Box::new(Repeat {})),
         "REPLACE" => build_function(metadata, engine, args, Box::new(Replace {})),
+        "TRANSLATE" => build_function(metadata, engine, args, Box::new(Translate {})),
+        "MD5" => build_function(metadata, engine, args, Box::new(Md5 {})),
+        "SHA1" => build_function(metadata, engine, args, Box::new(Sha1 {})),
+        "SHA256" => build_function(metadata, engine, args, Box::new(Sha256 {})),
+        "TO_BASE64" => build_function(metadata, engine, args, Box::new(ToBase64 {})),
+        "FROM_BASE64" => build_function(metadata, engine, args, Box::new(FromBase64 {})),
+        "HEX" => build_function(metadata, engine, args, Box::new(Hex {})),
+        "UNHEX" => build_function(metadata, engine, args, Box::new(Unhex {})),
+        "BIN" => build_function(metadata, engine, args, Box::new(Bin {})),
+        "OCT" => build_function(metadata, engine, args, Box::new(Oct {})),
+        "SUBSTRING_INDEX" => build_function(metadata, engine, args, Box::new(SubstringIndex {})),
         "REGEX_LIKE" => build_function(metadata, engine, args, Box::new(RegexLike {})),
         "REGEX_REPLACE" => build_function(metadata, engine, args, Box::new(RegexReplace {})),
         "REGEXP_SUBSTR" => build_function(metadata, engine, args, Box::new(RegexSubstring {})),
@@ -110,16 +209,106 @@ fn build_function_from_name(
         "LOG2" => build_function(metadata, engine, args, Box::new(Log2 {})),
         "LOG10" => build_function(metadata, engine, args, Box::new(Log10 {})),
         "POW" | "POWER" => build_function(metadata, engine, args, Box::new(Power {})),
+        "MOD" => build_function(metadata, engine, args, Box::new(Mod {})),
+        "YEAR" => build_function(
+            metadata,
+            engine,
+            args,
+            Box::new(DatePart {
+                name: "YEAR",
+                field: Field::Year,
+            }),
+        ),
+        "MONTH" => build_function(
+            metadata,
+            engine,
+            args,
+            Box::new(DatePart {
+                name: "MONTH",
+                field: Field::Month,
+            }),
+        ),
+        "DAY" => build_function(
+            metadata,
+            engine,
+            args,
+            Box::new(DatePart {
+                name: "DAY",
+                field: Field::Day,
+            }),
+        ),
+        "HOUR" => build_function(
+            metadata,
+            engine,
+            args,
+            Box::new(DatePart {
+                name: "HOUR",
+                field: Field::Hour,
+            }),
+        ),
+        "MINUTE" => build_function(
+            metadata,
+            engine,
+            args,
+            Box::new(DatePart {
+                name: "MINUTE",
+                field: Field::Minute,
+            }),
+        ),
+        "SECOND" => build_function(
+            metadata,
+            engine,
+            args,
+            Box::new(DatePart {
+                name: "SECOND",
+                field: Field::Second,
+            }),
+        ),
+        "QUARTER" => build_function(
+            metadata,
+            engine,
+            args,
+            Box::new(DatePart {
+                name: "QUARTER",
+                field: Field::Quarter,
+            }),
+        ),
+        "DAYOFYEAR" => build_function(
+            metadata,
+            engine,
+            args,
+            Box::new(DatePart {
+                name: "DAYOFYEAR",
+                field: Field::DayOfYear,
+            }),
+        ),
+        "DAYOFWEEK" => build_function(metadata, engine, args, Box::new(DayOfWeek {})),
+        "DAYNAME" => build_function(metadata, engine, args, Box::new(DayName {})),
+        "MONTHNAME" => build_function(metadata, engine, args, Box::new(MonthName {})),
+        "GCD" => build_function(metadata, engine, args, Box::new(Gcd {})),
+        "LCM" => build_function(metadata, engine, args, Box::new(Lcm {})),
         "ROUND" => build_function(metadata, engine, args, Box::new(Round {})),
+        "TRUNCATE" | "TRUNC" => build_function(metadata, engine, args, Box::new(Truncate {})),
+        "SIGN" => build_function(metadata, engine, args, Box::new(Sign {})),
+        "CBRT" => build_function(metadata, engine, args, Box::new(Cbrt {})),
         "SQRT" => build_function(metadata, engine, args, Box::new(Sqrt {})),
+        "WIDTH_BUCKET" => build_function(metadata, engine, args, Box::new(WidthBucket {})),
+        "TO_JSON" => create_to_json(metadata, engine, args),
         _ => Err(CvsSqlError::Unsupported(format!("function {name}"))),
+    }?;
+    if let Some(filter) = filter {
+        return Err(CvsSqlError::Unsupported(format!(
+            "{name} with FILTER (WHERE {filter})"
+        )));
     }
+    Ok(function)
 }
 
 fn build_aggregator_function(
     metadata: &Metadata,
     engine: &Engine,
     args: &FunctionArguments,
+    filter: Option<&Expr>,
     operator: Box<dyn AggregateOperator>,
 ) -> Result<Box<dyn Projection>, CvsSqlError> {
     let parent_metadata = match metadata {
@@ -142,8 +331,21 @@ fn build_aggregator_function(
     };
     let distinct = matches!(lst.duplicate_treatment, Some(DuplicateTreatment::Distinct));
 
-    if let Some(c) = lst.clauses.first() {
-        return Err(CvsSqlError::Unsupported(format!("{c}")));
+    let mut order_by = vec![];
+    for clause in &lst.clauses {
+        match clause {
+            FunctionArgumentClause::OrderBy(items) if operator.support_order_by() => {
+                for item in items {
+                    order_by.push(OrderByItem::create(
+                        parent_metadata,
+                        engine,
+                        item,
+                        &item.expr,
+                    )?);
+                }
+            }
+            _ => return Err(CvsSqlError::Unsupported(format!("{clause}"))),
+        }
     }
     let first = match lst.args.first() {
         Some(arg) => arg,
@@ -179,16 +381,105 @@ fn build_aggregator_function(
         }
         _ => return Err(CvsSqlError::Unsupported(format!("{first}"))),
     };
-    let name = format!("{}({})", operator.name(), argument.name());
+    let filter = filter
+        .map(|filter| filter.convert_single(parent_metadata, engine))
+        .transpose()?;
+    let name = match &filter {
+        None => format!("{}({})", operator.name(), argument.name()),
+        Some(filter) => format!(
+            "{}({}) FILTER (WHERE {})",
+            operator.name(),
+            argument.name(),
+            filter.name()
+        ),
+    };
 
     Ok(Box::new(AggregatedFunction {
         distinct,
         argument,
+        filter,
         operator,
+        order_by,
         name,
     }))
 }
 
+/// The separator of `GROUP_CONCAT` is an optional second argument, so it is taken out of the
+/// arguments before building the aggregation.
+fn build_group_concat(
+    name: &str,
+    metadata: &Metadata,
+    engine: &Engine,
+    args: &FunctionArguments,
+    filter: Option<&Expr>,
+) -> Result<Box<dyn Projection>, CvsSqlError> {
+    let mut args = args.clone();
+    let mut separator = ",".to_string();
+    if let FunctionArguments::List(lst) = &mut args
+        && lst.args.len() == 2
+        && let Some(arg) = lst.args.pop()
+    {
+        let FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(value))) = &arg else {
+            return Err(CvsSqlError::Unsupported(format!(
+                "{name} with separator {arg}"
+            )));
+        };
+        let Some(value) = value.value.clone().into_string() else {
+            return Err(CvsSqlError::Unsupported(format!(
+                "{name} with separator {arg}"
+            )));
+        };
+        separator = value;
+    }
+    let operator = GroupConcat {
+        name: name.to_string(),
+        separator,
+    };
+    build_aggregator_function(metadata, engine, &args, filter, Box::new(operator))
+}
+
+/// The fraction of `PERCENTILE_CONT` is the first argument, so it is taken out of the arguments
+/// before building the aggregation.
+fn build_percentile_cont(
+    metadata: &Metadata,
+    engine: &Engine,
+    args: &FunctionArguments,
+    filter: Option<&Expr>,
+) -> Result<Box<dyn Projection>, CvsSqlError> {
+    let mut args = args.clone();
+    let FunctionArguments::List(lst) = &mut args else {
+        return Err(CvsSqlError::Unsupported(
+            "Function PERCENTILE_CONT must have a fraction and an argument".into(),
+        ));
+    };
+    if lst.args.len() != 2 {
+        return Err(CvsSqlError::Unsupported(
+            "Function PERCENTILE_CONT must have a fraction and an argument".into(),
+        ));
+    }
+    let arg = lst.args.remove(0);
+    let fraction = match &arg {
+        FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(value))) => match &value.value {
+            AstValue::Number(fraction, _) => Some(fraction.clone()),
+            _ => None,
+        },
+        _ => None,
+    };
+    let Some(fraction) = fraction.filter(|f| f >= &BigDecimal::zero() && f <= &BigDecimal::from(1))
+    else {
+        return Err(CvsSqlError::Unsupported(format!(
+            "PERCENTILE_CONT with fraction {arg} (the fraction must be a number between 0 and 1)"
+        )));
+    };
+    build_aggregator_function(
+        metadata,
+        engine,
+        &args,
+        filter,
+        Box::new(PercentileCont { fraction }),
+    )
+}
+
 #[cfg(test)]
 struct AggregationExample<'a> {
     name: &'a str,
@@ -202,6 +493,10 @@ trait AggregateOperator {
     fn support_wildcard_argument(&self) -> bool {
         false
     }
+    /// Whether the order of the values matters, so the function can have an `ORDER BY` clause.
+    fn support_order_by(&self) -> bool {
+        false
+    }
     fn aggregate(&self, data: &mut dyn Iterator<Item = Value>) -> Value;
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<AggregationExample<'a>>;
@@ -261,6 +556,44 @@ impl AggregateOperator for Count {
     }
 }
 
+struct CountIf {}
+
+impl AggregateOperator for CountIf {
+    fn name(&self) -> &str {
+        "COUNT_IF"
+    }
+    fn aggregate(&self, data: &mut dyn Iterator<Item = Value>) -> Value {
+        let count = data.filter(|value| value == &Value::Bool(true)).count();
+        Value::Number((count as u128).into())
+    }
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<AggregationExample<'a>> {
+        vec![
+            AggregationExample {
+                name: "simple",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["TRUE", "FALSE", "TRUE", "TRUE"],
+                expected_results: "3",
+            },
+            AggregationExample {
+                name: "with_non_bools",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["TRUE", "", "a", "1"],
+                expected_results: "1",
+            },
+            AggregationExample {
+                name: "no_true",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["FALSE", ""],
+                expected_results: "0",
+            },
+        ]
+    }
+}
+
 struct Avg {}
 
 impl AggregateOperator for Avg {
@@ -317,6 +650,11 @@ impl AggregateOperator for Avg {
     }
 }
 
+/// Sums the numbers in the data, like `SUM` does (used by the windowed `SUM`).
+pub(crate) fn sum_values(data: &mut dyn Iterator<Item = Value>) -> Value {
+    Sum {}.aggregate(data)
+}
+
 struct Sum {}
 
 impl AggregateOperator for Sum {
@@ -431,81 +769,595 @@ impl AggregateOperator for AnyValue {
         "ANY_VALUE"
     }
     fn aggregate(&self, data: &mut dyn Iterator<Item = Value>) -> Value {
-        let val = data.next();
-        val.unwrap_or(Value::Empty)
+        for value in data {
+            if !value.is_empty() {
+                return value;
+            }
+        }
+        Value::Empty
     }
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<AggregationExample<'a>> {
-        vec![AggregationExample {
-            name: "values",
-            is_distinct: false,
-            is_wildcard: false,
-            data: vec!["a", "b", "2", "3"],
-            expected_results: "a",
-        }]
+        vec![
+            AggregationExample {
+                name: "values",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["a", "b", "2", "3"],
+                expected_results: "a",
+            },
+            AggregationExample {
+                name: "leading_nulls",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["", "", "b", "2"],
+                expected_results: "b",
+            },
+            AggregationExample {
+                name: "only_nulls",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["", ""],
+                expected_results: "",
+            },
+        ]
     }
 }
 
-struct AggregatedFunction {
-    distinct: bool,
-    argument: Box<dyn Projection>,
-    operator: Box<dyn AggregateOperator>,
-    name: String,
-}
-
-impl Projection for AggregatedFunction {
-    fn get<'a>(&'a self, row: &'a GroupRow) -> SmartReference<'a, Value> {
-        let mut iter = row
-            .group_rows
-            .iter()
-            .map(|r| self.argument.get(r))
-            .map(|v| v.clone());
-        let value = if self.distinct {
-            let mut unique = iter.unique();
-            self.operator.aggregate(&mut unique)
-        } else {
-            self.operator.aggregate(&mut iter)
-        };
-        value.into()
-    }
+struct BoolAnd {}
+impl AggregateOperator for BoolAnd {
     fn name(&self) -> &str {
-        &self.name
+        "BOOL_AND"
+    }
+    fn aggregate(&self, data: &mut dyn Iterator<Item = Value>) -> Value {
+        let mut result = Value::Empty;
+        for value in data {
+            match value {
+                Value::Bool(false) => return Value::Bool(false),
+                Value::Bool(true) => result = Value::Bool(true),
+                _ => {}
+            }
+        }
+        result
+    }
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<AggregationExample<'a>> {
+        vec![
+            AggregationExample {
+                name: "all_true",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["TRUE", "TRUE", "TRUE"],
+                expected_results: "TRUE",
+            },
+            AggregationExample {
+                name: "mixed",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["TRUE", "FALSE", "TRUE"],
+                expected_results: "FALSE",
+            },
+            AggregationExample {
+                name: "with_nulls",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["", "TRUE", "", "TRUE"],
+                expected_results: "TRUE",
+            },
+            AggregationExample {
+                name: "with_non_bools",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["TRUE", "a", "0"],
+                expected_results: "TRUE",
+            },
+            AggregationExample {
+                name: "only_nulls",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["", ""],
+                expected_results: "",
+            },
+        ]
     }
 }
 
-struct Wildcard {}
-impl Projection for Wildcard {
-    fn get<'a>(&'a self, _: &'a GroupRow) -> SmartReference<'a, Value> {
-        Value::Bool(true).into()
-    }
+struct BoolOr {}
+impl AggregateOperator for BoolOr {
     fn name(&self) -> &str {
-        "*"
+        "BOOL_OR"
+    }
+    fn aggregate(&self, data: &mut dyn Iterator<Item = Value>) -> Value {
+        let mut result = Value::Empty;
+        for value in data {
+            match value {
+                Value::Bool(true) => return Value::Bool(true),
+                Value::Bool(false) => result = Value::Bool(false),
+                _ => {}
+            }
+        }
+        result
+    }
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<AggregationExample<'a>> {
+        vec![
+            AggregationExample {
+                name: "all_false",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["FALSE", "FALSE"],
+                expected_results: "FALSE",
+            },
+            AggregationExample {
+                name: "mixed",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["FALSE", "TRUE", "FALSE"],
+                expected_results: "TRUE",
+            },
+            AggregationExample {
+                name: "with_nulls",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["", "FALSE", ""],
+                expected_results: "FALSE",
+            },
+            AggregationExample {
+                name: "with_non_bools",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["FALSE", "a", "1"],
+                expected_results: "FALSE",
+            },
+            AggregationExample {
+                name: "only_nulls",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["", ""],
+                expected_results: "",
+            },
+        ]
     }
 }
-fn wildcard_operator() -> Box<dyn Projection> {
-    Box::new(Wildcard {})
-}
-
-#[cfg(test)]
-mod test_aggregations {
-    use std::fs::{self, OpenOptions};
-
-    use crate::{args::Args, engine::Engine, error::CvsSqlError, results::Column};
-    use std::io::Write;
-
-    use super::{AggregateOperator, AggregationExample, AnyValue, Avg, Count, Max, Min, Sum};
 
-    fn test_agg(operator: &impl AggregateOperator) -> Result<(), CvsSqlError> {
-        let dir = format!("./target/function_tests/{}", operator.name().to_lowercase());
-        println!("testing: {}", operator.name());
-        fs::remove_dir_all(&dir).ok();
-        fs::create_dir_all(&dir)?;
-        for example in operator.examples() {
-            test_agg_with_example(operator, &example)?;
+struct ArrayAgg {}
+impl AggregateOperator for ArrayAgg {
+    fn name(&self) -> &str {
+        "ARRAY_AGG"
+    }
+    fn support_order_by(&self) -> bool {
+        true
+    }
+    fn aggregate(&self, data: &mut dyn Iterator<Item = Value>) -> Value {
+        let values: Vec<_> = data.map(|value| value_as_json(&value)).collect();
+        if values.is_empty() {
+            return Value::Empty;
         }
-        fs::remove_dir_all(&dir).ok();
-
-        Ok(())
+        Value::Str(JsonValue::Array(values).to_string())
+    }
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<AggregationExample<'a>> {
+        vec![
+            AggregationExample {
+                name: "duplicates_and_nulls",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["1", "1", "2", "\"\""],
+                expected_results: "[1,1,2,null]",
+            },
+            AggregationExample {
+                name: "distinct",
+                is_distinct: true,
+                is_wildcard: false,
+                data: vec!["1", "\"\"", "1", "2", "\"\""],
+                expected_results: "[1,null,2]",
+            },
+            AggregationExample {
+                name: "mixed_types",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["a", "TRUE", "2024-01-02", "1.5"],
+                expected_results: "[\"a\",true,\"2024-01-02\",1.5]",
+            },
+        ]
+    }
+}
+
+/// The value at the given fraction of the sorted numbers, interpolating between the two closest numbers.
+fn interpolate(data: &mut dyn Iterator<Item = Value>, fraction: &BigDecimal) -> Value {
+    let mut numbers: Vec<_> = data.filter_map(|f| f.to_number()).collect();
+    if numbers.is_empty() {
+        return Value::Empty;
+    }
+    numbers.sort();
+    let position = fraction * BigDecimal::from((numbers.len() - 1) as u64);
+    let lower = position.with_scale_round(0, bigdecimal::RoundingMode::Down);
+    let index = lower.to_usize().unwrap_or_default();
+    let Some(next) = numbers.get(index + 1) else {
+        return Value::Number(numbers[index].clone());
+    };
+    let value = &numbers[index] + (next - &numbers[index]) * (position - lower);
+    if value.is_integer() {
+        Value::Number(value.with_scale(0))
+    } else {
+        Value::Number(value.normalized())
+    }
+}
+
+struct Median {}
+impl AggregateOperator for Median {
+    fn name(&self) -> &str {
+        "MEDIAN"
+    }
+    fn aggregate(&self, data: &mut dyn Iterator<Item = Value>) -> Value {
+        interpolate(data, &BigDecimal::new(5.into(), 1))
+    }
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<AggregationExample<'a>> {
+        vec![
+            AggregationExample {
+                name: "odd",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["5", "11", "1"],
+                expected_results: "5",
+            },
+            AggregationExample {
+                name: "even",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["5", "12", "11", "1"],
+                expected_results: "8",
+            },
+            AggregationExample {
+                name: "distinct",
+                is_distinct: true,
+                is_wildcard: false,
+                data: vec!["4", "4", "4", "1"],
+                expected_results: "2.5",
+            },
+            AggregationExample {
+                name: "not_only_numbers",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["10", "", "nop", "12", "30"],
+                expected_results: "12",
+            },
+            AggregationExample {
+                name: "no_numbers",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["a", "", "nop", ""],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct PercentileCont {
+    fraction: BigDecimal,
+}
+impl AggregateOperator for PercentileCont {
+    fn name(&self) -> &str {
+        "PERCENTILE_CONT"
+    }
+    fn aggregate(&self, data: &mut dyn Iterator<Item = Value>) -> Value {
+        interpolate(data, &self.fraction)
+    }
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<AggregationExample<'a>> {
+        vec![
+            AggregationExample {
+                name: "interpolate",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["10", "40", "20", "30", "50"],
+                expected_results: "20",
+            },
+            AggregationExample {
+                name: "single",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["7"],
+                expected_results: "7",
+            },
+            AggregationExample {
+                name: "no_numbers",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["a", "nop"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct GroupConcat {
+    name: String,
+    separator: String,
+}
+impl AggregateOperator for GroupConcat {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn support_order_by(&self) -> bool {
+        true
+    }
+    fn aggregate(&self, data: &mut dyn Iterator<Item = Value>) -> Value {
+        let values: Vec<_> = data
+            .filter(|value| !value.is_empty())
+            .map(|value| value.to_string())
+            .collect();
+        if values.is_empty() {
+            return Value::Empty;
+        }
+        Value::Str(values.join(&self.separator))
+    }
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<AggregationExample<'a>> {
+        vec![
+            AggregationExample {
+                name: "skip_nulls",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["a", "\"\"", "b", "a"],
+                expected_results: "a,b,a",
+            },
+            AggregationExample {
+                name: "distinct",
+                is_distinct: true,
+                is_wildcard: false,
+                data: vec!["a", "b", "a", "1"],
+                expected_results: "a,b,1",
+            },
+            AggregationExample {
+                name: "only_nulls",
+                is_distinct: false,
+                is_wildcard: false,
+                data: vec!["\"\"", "\"\""],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct AggregatedFunction {
+    distinct: bool,
+    argument: Box<dyn Projection>,
+    filter: Option<Box<dyn Projection>>,
+    operator: Box<dyn AggregateOperator>,
+    order_by: Vec<OrderByItem>,
+    name: String,
+}
+
+impl Projection for AggregatedFunction {
+    fn get<'a>(&'a self, row: &'a GroupRow) -> SmartReference<'a, Value> {
+        let mut rows: Vec<_> = row
+            .group_rows
+            .iter()
+            .filter(|row| match &self.filter {
+                Some(filter) => filter.get(row).deref() == &Value::Bool(true),
+                None => true,
+            })
+            .collect();
+        if !self.order_by.is_empty() {
+            let mut keyed: Vec<_> = rows
+                .into_iter()
+                .map(|row| {
+                    let keys: Vec<_> = self
+                        .order_by
+                        .iter()
+                        .map(|item| item.by.get(row).deref().clone())
+                        .collect();
+                    (keys, row)
+                })
+                .collect();
+            keyed.sort_by(|(left, _), (right, _)| compare_keys(&self.order_by, left, right));
+            rows = keyed.into_iter().map(|(_, row)| row).collect();
+        }
+        let mut iter = rows
+            .into_iter()
+            .map(|r| self.argument.get(r))
+            .map(|v| v.clone());
+        let value = if self.distinct {
+            let mut unique = iter.unique();
+            self.operator.aggregate(&mut unique)
+        } else {
+            self.operator.aggregate(&mut iter)
+        };
+        value.into()
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+struct Wildcard {}
+impl Projection for Wildcard {
+    fn get<'a>(&'a self, _: &'a GroupRow) -> SmartReference<'a, Value> {
+        Value::Bool(true).into()
+    }
+    fn name(&self) -> &str {
+        "*"
+    }
+}
+fn wildcard_operator() -> Box<dyn Projection> {
+    Box::new(Wildcard {})
+}
+
+fn build_pair_aggregator_function(
+    metadata: &Metadata,
+    engine: &Engine,
+    args: &FunctionArguments,
+    operator: Box<dyn PairAggregateOperator>,
+) -> Result<Box<dyn Projection>, CvsSqlError> {
+    let parent_metadata = match metadata {
+        Metadata::Grouped { parent, this: _ } => parent,
+        _ => return Err(CvsSqlError::NoGroupBy),
+    };
+    let FunctionArguments::List(lst) = &args else {
+        return Err(CvsSqlError::Unsupported(format!(
+            "Function {} must have two arguments",
+            operator.name()
+        )));
+    };
+    if lst.duplicate_treatment.is_some() {
+        return Err(CvsSqlError::Unsupported(format!(
+            "Function {} with distinct argument",
+            operator.name()
+        )));
+    }
+    if let Some(c) = lst.clauses.first() {
+        return Err(CvsSqlError::Unsupported(format!("{c}")));
+    }
+    let [first, second] = lst.args.as_slice() else {
+        return Err(CvsSqlError::Unsupported(format!(
+            "Function {} must have two arguments",
+            operator.name()
+        )));
+    };
+    let first = pair_argument(first, parent_metadata, engine)?;
+    let second = pair_argument(second, parent_metadata, engine)?;
+    let name = format!("{}({}, {})", operator.name(), first.name(), second.name());
+
+    Ok(Box::new(PairAggregatedFunction {
+        first,
+        second,
+        operator,
+        name,
+    }))
+}
+
+fn pair_argument(
+    arg: &FunctionArg,
+    metadata: &Metadata,
+    engine: &Engine,
+) -> Result<Box<dyn Projection>, CvsSqlError> {
+    match arg {
+        FunctionArg::Unnamed(FunctionArgExpr::Expr(e)) => e.convert_single(metadata, engine),
+        _ => Err(CvsSqlError::Unsupported(format!("{arg}"))),
+    }
+}
+
+trait PairAggregateOperator {
+    fn name(&self) -> &str;
+    fn aggregate(&self, sums: &PairSums) -> Option<BigDecimal>;
+}
+
+#[derive(Default)]
+struct PairSums {
+    count: BigDecimal,
+    x: BigDecimal,
+    y: BigDecimal,
+    xx: BigDecimal,
+    yy: BigDecimal,
+    xy: BigDecimal,
+}
+impl PairSums {
+    fn add(&mut self, x: &BigDecimal, y: &BigDecimal) {
+        self.count += 1;
+        self.x += x;
+        self.y += y;
+        self.xx += x * x;
+        self.yy += y * y;
+        self.xy += x * y;
+    }
+    fn co_moment(&self) -> BigDecimal {
+        &self.count * &self.xy - &self.x * &self.y
+    }
+}
+
+struct CovarPop {}
+impl PairAggregateOperator for CovarPop {
+    fn name(&self) -> &str {
+        "COVAR_POP"
+    }
+    fn aggregate(&self, sums: &PairSums) -> Option<BigDecimal> {
+        if sums.count.is_zero() {
+            return None;
+        }
+        Some(sums.co_moment() / (&sums.count * &sums.count))
+    }
+}
+
+struct CovarSamp {}
+impl PairAggregateOperator for CovarSamp {
+    fn name(&self) -> &str {
+        "COVAR_SAMP"
+    }
+    fn aggregate(&self, sums: &PairSums) -> Option<BigDecimal> {
+        if sums.count < 2 {
+            return None;
+        }
+        Some(sums.co_moment() / (&sums.count * (&sums.count - 1)))
+    }
+}
+
+struct Corr {}
+impl PairAggregateOperator for Corr {
+    fn name(&self) -> &str {
+        "CORR"
+    }
+    fn aggregate(&self, sums: &PairSums) -> Option<BigDecimal> {
+        let x_moment = &sums.count * &sums.xx - &sums.x * &sums.x;
+        let y_moment = &sums.count * &sums.yy - &sums.y * &sums.y;
+        let denominator = (x_moment * y_moment).sqrt()?;
+        if denominator.is_zero() {
+            return None;
+        }
+        Some(sums.co_moment() / denominator)
+    }
+}
+
+struct PairAggregatedFunction {
+    first: Box<dyn Projection>,
+    second: Box<dyn Projection>,
+    operator: Box<dyn PairAggregateOperator>,
+    name: String,
+}
+
+impl Projection for PairAggregatedFunction {
+    fn get<'a>(&'a self, row: &'a GroupRow) -> SmartReference<'a, Value> {
+        let mut sums = PairSums::default();
+        for row in &row.group_rows {
+            let x = self.first.get(row);
+            let y = self.second.get(row);
+            if let (Value::Number(x), Value::Number(y)) = (x.deref(), y.deref()) {
+                sums.add(x, y);
+            }
+        }
+        self.operator.aggregate(&sums).into()
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[cfg(test)]
+mod test_aggregations {
+    use std::fs::{self, OpenOptions};
+
+    use bigdecimal::{BigDecimal, ToPrimitive};
+
+    use crate::{args::Args, engine::Engine, error::CvsSqlError, results::Column, value::Value};
+    use std::io::Write;
+
+    use super::{
+        AggregateOperator, AggregationExample, AnyValue, ArrayAgg, Avg, BoolAnd, BoolOr, Count,
+        CountIf, GroupConcat, Max, Median, Min, PercentileCont, Sum,
+    };
+
+    fn test_agg(operator: &impl AggregateOperator) -> Result<(), CvsSqlError> {
+        let dir = format!("./target/function_tests/{}", operator.name().to_lowercase());
+        println!("testing: {}", operator.name());
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir)?;
+        for example in operator.examples() {
+            test_agg_with_example(operator, &example)?;
+        }
+        fs::remove_dir_all(&dir).ok();
+
+        Ok(())
     }
 
     fn test_agg_with_example<'a>(
@@ -592,27 +1444,206 @@ mod test_aggregations {
     fn test_any_value() -> Result<(), CvsSqlError> {
         test_agg(&AnyValue {})
     }
-}
 
-#[cfg(test)]
-struct FunctionExample<'a> {
-    name: &'a str,
-    arguments: Vec<&'a str>,
-    expected_results: &'a str,
-}
+    #[test]
+    fn test_count_if() -> Result<(), CvsSqlError> {
+        test_agg(&CountIf {})
+    }
 
-trait Operator {
-    fn name(&self) -> &str;
-    fn min_args(&self) -> usize;
-    fn max_args(&self) -> Option<usize>;
-    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value>;
-    #[cfg(test)]
-    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
-        vec![]
+    #[test]
+    fn test_bool_and() -> Result<(), CvsSqlError> {
+        test_agg(&BoolAnd {})
     }
-}
-struct SimpleFunction {
-    arguments: Vec<Box<dyn Projection>>,
+
+    #[test]
+    fn test_bool_or() -> Result<(), CvsSqlError> {
+        test_agg(&BoolOr {})
+    }
+
+    #[test]
+    fn test_array_agg() -> Result<(), CvsSqlError> {
+        test_agg(&ArrayAgg {})
+    }
+
+    #[test]
+    fn test_array_agg_with_order() -> Result<(), CvsSqlError> {
+        let values = pair_aggregation(
+            "SELECT ARRAY_AGG(name ORDER BY artist_id DESC), ARRAY_AGG(artist_id ORDER BY name), ARRAY_AGG(DISTINCT artist_id % 2 ORDER BY artist_id DESC) FROM tests.data.artists",
+        )?;
+
+        assert_eq!(
+            values[0],
+            Value::Str(r#"["Shaggy","Alanis Morissette","Aerosmith","AC/DC"]"#.into())
+        );
+        assert_eq!(values[1], Value::Str("[1,2,3,4]".into()));
+        assert_eq!(values[2], Value::Str("[0,1]".into()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_group_concat() -> Result<(), CvsSqlError> {
+        test_agg(&GroupConcat {
+            name: "GROUP_CONCAT".into(),
+            separator: ",".into(),
+        })
+    }
+
+    #[test]
+    fn test_group_concat_with_separator() -> Result<(), CvsSqlError> {
+        let values = pair_aggregation(
+            "SELECT STRING_AGG(name, ' | ' ORDER BY artist_id DESC), GROUP_CONCAT(artist_id), GROUP_CONCAT(DISTINCT artist_id % 2, '' ORDER BY artist_id) FROM tests.data.artists",
+        )?;
+
+        assert_eq!(
+            values[0],
+            Value::Str("Shaggy | Alanis Morissette | Aerosmith | AC/DC".into())
+        );
+        assert_eq!(values[1], Value::Str("1,2,3,4".into()));
+        assert_eq!(values[2], Value::Str("10".into()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_median() -> Result<(), CvsSqlError> {
+        test_agg(&Median {})
+    }
+
+    #[test]
+    fn test_percentile_cont() {
+        // The SQL of `test_agg` has no fraction, so the examples are aggregated directly
+        let operator = PercentileCont {
+            fraction: BigDecimal::new(25.into(), 2),
+        };
+        for example in operator.examples() {
+            let mut data = example.data.iter().map(|data| Value::from(*data));
+            let result = operator.aggregate(&mut data);
+            assert_eq!(result, example.expected_results.into(), "{}", example.name);
+        }
+    }
+
+    #[test]
+    fn test_percentile_cont_in_sql() -> Result<(), CvsSqlError> {
+        let values = pair_aggregation(
+            "SELECT PERCENTILE_CONT(0.5, artist_id), PERCENTILE_CONT(0.1, artist_id), PERCENTILE_CONT(1, artist_id) FROM tests.data.artists",
+        )?;
+
+        assert_eq!(values[0], Value::from("2.5"));
+        assert_eq!(values[1], Value::from("1.3"));
+        assert_eq!(values[2], Value::from("4"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn percentile_cont_needs_a_valid_fraction() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        for sql in [
+            "SELECT PERCENTILE_CONT(1.5, artist_id) FROM tests.data.artists",
+            "SELECT PERCENTILE_CONT(artist_id) FROM tests.data.artists",
+            "SELECT PERCENTILE_CONT(name, artist_id) FROM tests.data.artists",
+        ] {
+            let err = engine.execute_commands(sql).err().unwrap();
+            assert!(matches!(err, CvsSqlError::Unsupported(_)), "{sql}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn order_by_is_unsupported_for_other_aggregations() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let err = engine
+            .execute_commands("SELECT SUM(artist_id ORDER BY name) FROM tests.data.artists")
+            .err()
+            .unwrap();
+
+        assert!(matches!(err, CvsSqlError::Unsupported(_)));
+
+        Ok(())
+    }
+
+    fn pair_aggregation(sql: &str) -> Result<Vec<Value>, CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+        let results = engine.execute_commands(sql)?;
+        let results = &results.first().unwrap().results;
+        let row = results.data.iter().next().unwrap();
+        Ok((0..3)
+            .map(|index| row.get(&Column::from_index(index)).clone())
+            .collect())
+    }
+
+    fn assert_close(value: &Value, expected: f64) {
+        let Value::Number(value) = value else {
+            panic!("{value} is not a number");
+        };
+        let value = value.to_f64().unwrap();
+        assert!(
+            (value - expected).abs() < 1e-9,
+            "{value} is not close to {expected}"
+        );
+    }
+
+    #[test]
+    fn test_corr_and_covar() -> Result<(), CvsSqlError> {
+        let values = pair_aggregation(
+            "SELECT CORR(price, \"delivery cost\"), COVAR_POP(price, \"delivery cost\"), COVAR_SAMP(price, \"delivery cost\") FROM tests.data.sales",
+        )?;
+
+        assert_close(&values[0], 0.5233184517695996);
+        assert_close(&values[1], 428.6840674999985);
+        assert_close(&values[2], 439.67596666666515);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_corr_and_covar_of_a_single_row() -> Result<(), CvsSqlError> {
+        let values = pair_aggregation(
+            "SELECT CORR(price, price), COVAR_POP(price, price), COVAR_SAMP(price, price) FROM tests.data.sales WHERE id = 'a69dde4e-6ec2-444e-9c7f-b1939d1a7538'",
+        )?;
+
+        assert_eq!(values[0], Value::Empty);
+        assert_close(&values[1], 0.0);
+        assert_eq!(values[2], Value::Empty);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_corr_and_covar_without_numbers() -> Result<(), CvsSqlError> {
+        let values = pair_aggregation(
+            "SELECT CORR(id, price), COVAR_POP(id, price), COVAR_SAMP(price, id) FROM tests.data.sales",
+        )?;
+
+        assert_eq!(values, vec![Value::Empty, Value::Empty, Value::Empty]);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+struct FunctionExample<'a> {
+    name: &'a str,
+    arguments: Vec<&'a str>,
+    expected_results: &'a str,
+}
+
+trait Operator {
+    fn name(&self) -> &str;
+    fn min_args(&self) -> usize;
+    fn max_args(&self) -> Option<usize>;
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value>;
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![]
+    }
+}
+struct SimpleFunction {
+    arguments: Vec<Box<dyn Projection>>,
     operator: Box<dyn Operator>,
     name: String,
 }
@@ -1019,6 +2050,11 @@ impl Operator for Coalece {
                 arguments: vec!["a", "b"],
                 expected_results: "a",
             },
+            FunctionExample {
+                name: "date",
+                arguments: vec!["", "2024-03-01", "b"],
+                expected_results: "2024-03-01",
+            },
             FunctionExample {
                 name: "empty",
                 arguments: vec![],
@@ -1211,447 +2247,602 @@ impl Operator for Format {
     }
 }
 
-struct ToTimestamp {}
-impl Operator for ToTimestamp {
+struct StrToDate {}
+impl Operator for StrToDate {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let Some(time) = args.first().as_i64() else {
+        let text = args.first();
+        let Some(text) = text.as_string() else {
             return Value::Empty.into();
         };
-
-        let LocalResult::Single(time) = Utc.timestamp_opt(time, 0) else {
+        let format = args.get(1);
+        let Some(format) = format.as_string() else {
             return Value::Empty.into();
         };
-
-        Value::Timestamp(time.naive_utc()).into()
+        if let Ok(ts) = NaiveDateTime::parse_from_str(text, format) {
+            return Value::Timestamp(ts).into();
+        }
+        let mut parsed = Parsed::new();
+        if parse(&mut parsed, text, StrftimeItems::new(format)).is_err() {
+            return Value::Empty.into();
+        }
+        let Ok(date) = parsed.to_naive_date() else {
+            return Value::Empty.into();
+        };
+        if parsed.hour_div_12().is_none()
+            && parsed.hour_mod_12().is_none()
+            && parsed.minute().is_none()
+            && parsed.second().is_none()
+            && parsed.nanosecond().is_none()
+        {
+            // Without any time field, this can only be a date
+            return Value::Date(date).into();
+        }
+        // Only some of the time fields, the missing minutes (and seconds) are zero
+        if parsed.minute().is_none() && parsed.set_minute(0).is_err() {
+            return Value::Empty.into();
+        }
+        match parsed.to_naive_time() {
+            Ok(time) => Value::Timestamp(date.and_time(time)).into(),
+            Err(_) => Value::Empty.into(),
+        }
     }
     fn max_args(&self) -> Option<usize> {
-        Some(1)
+        Some(2)
     }
     fn min_args(&self) -> usize {
-        1
+        2
     }
     fn name(&self) -> &str {
-        "TO_TIMESTAMP"
+        "STR_TO_DATE"
     }
 
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "should_work",
-                arguments: vec!["1400234525"],
-                expected_results: "2014-05-16 10:02:05",
+                name: "date",
+                arguments: vec!["23/11/2024", "%d/%m/%Y"],
+                expected_results: "2024-11-23",
             },
             FunctionExample {
-                name: "nan",
-                arguments: vec!["test"],
+                name: "timestamp",
+                arguments: vec!["23/11/2024 10:20", "%d/%m/%Y %H:%M"],
+                expected_results: "2024-11-23 10:20:00",
+            },
+            FunctionExample {
+                name: "hour_only",
+                arguments: vec!["23/11/2024 10", "%d/%m/%Y %H"],
+                expected_results: "2024-11-23 10:00:00",
+            },
+            FunctionExample {
+                name: "hour_without_am_pm",
+                arguments: vec!["23/11/2024 10", "%d/%m/%Y %I"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "no_match",
+                arguments: vec!["23/11/2024", "%Y-%m-%d"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "invalid_date",
+                arguments: vec!["31/02/2024", "%d/%m/%Y"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_a_string",
+                arguments: vec!["20241123", "%Y%m%d"],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct Greatest {}
-impl Operator for Greatest {
+struct FormatBytes {}
+impl Operator for FormatBytes {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let mut greatest = None;
-        for a in args.iter() {
-            match &greatest {
-                None => greatest = Some(a.deref().clone()),
-                Some(val) => {
-                    if a.deref() > val {
-                        greatest = Some(a.deref().clone());
-                    }
-                }
+        let Some(bytes) = args.first().as_f64() else {
+            return Value::Empty.into();
+        };
+        let units = args.get(1);
+        let units = if units.is_some() {
+            units.as_string().map(|units| units.to_lowercase())
+        } else {
+            Some("decimal".to_string())
+        };
+        let (base, units) = match units.as_deref() {
+            Some("decimal") => (1000.0, ["KB", "MB", "GB", "TB", "PB", "EB"]),
+            Some("binary") => (1024.0, ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"]),
+            _ => {
+                return Value::Empty.into();
             }
+        };
+        if bytes.abs() < base {
+            return Value::Str(format!("{bytes} B")).into();
         }
-        match greatest {
-            None => Value::Empty.into(),
-            Some(greatest) => greatest.into(),
+        let mut size = bytes / base;
+        let mut unit = units[0];
+        for next in &units[1..] {
+            if size.abs() < base {
+                break;
+            }
+            size /= base;
+            unit = next;
         }
+        Value::Str(format!("{size:.1} {unit}")).into()
     }
     fn max_args(&self) -> Option<usize> {
-        None
+        Some(2)
     }
     fn min_args(&self) -> usize {
-        0
+        1
     }
     fn name(&self) -> &str {
-        "GREATEST"
+        "FORMAT_BYTES"
     }
 
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "greatest",
-                arguments: vec!["10", "400040", "1044", "-134522352"],
-                expected_results: "400040",
+                name: "zero",
+                arguments: vec!["0"],
+                expected_results: "0 B",
             },
             FunctionExample {
-                name: "empty",
-                arguments: vec![],
+                name: "bytes",
+                arguments: vec!["512"],
+                expected_results: "512 B",
+            },
+            FunctionExample {
+                name: "kilobytes",
+                arguments: vec!["1536"],
+                expected_results: "1.5 KB",
+            },
+            FunctionExample {
+                name: "kibibytes",
+                arguments: vec!["1536", "binary"],
+                expected_results: "1.5 KiB",
+            },
+            FunctionExample {
+                name: "megabytes",
+                arguments: vec!["2500000", "decimal"],
+                expected_results: "2.5 MB",
+            },
+            FunctionExample {
+                name: "mebibytes",
+                arguments: vec!["1048576", "BINARY"],
+                expected_results: "1.0 MiB",
+            },
+            FunctionExample {
+                name: "gigabytes",
+                arguments: vec!["5000000000"],
+                expected_results: "5.0 GB",
+            },
+            FunctionExample {
+                name: "tebibytes",
+                arguments: vec!["1099511627776", "binary"],
+                expected_results: "1.0 TiB",
+            },
+            FunctionExample {
+                name: "negative",
+                arguments: vec!["-2048", "binary"],
+                expected_results: "-2.0 KiB",
+            },
+            FunctionExample {
+                name: "nan",
+                arguments: vec!["test"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "unknown_units",
+                arguments: vec!["1536", "metric"],
                 expected_results: "",
             },
         ]
     }
 }
-struct If {}
-impl Operator for If {
+
+struct ToTimestamp {}
+impl Operator for ToTimestamp {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let first = args.first();
-        let Some(condition) = first.as_bool() else {
+        let Some(time) = args.first().as_i64() else {
             return Value::Empty.into();
         };
-        let value = if *condition { args.get(1) } else { args.get(2) };
-        match value {
-            Some(v) => v.deref().clone().into(),
-            None => Value::Empty.into(),
-        }
+
+        let LocalResult::Single(time) = Utc.timestamp_opt(time, 0) else {
+            return Value::Empty.into();
+        };
+
+        Value::Timestamp(time.naive_utc()).into()
     }
     fn max_args(&self) -> Option<usize> {
-        Some(3)
+        Some(1)
     }
     fn min_args(&self) -> usize {
-        3
+        1
     }
     fn name(&self) -> &str {
-        "IF"
+        "TO_TIMESTAMP"
     }
 
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "true",
-                arguments: vec!["TRUE", "100", "-100"],
-                expected_results: "100",
-            },
-            FunctionExample {
-                name: "false",
-                arguments: vec!["FALSE", "100", "-100"],
-                expected_results: "-100",
+                name: "should_work",
+                arguments: vec!["1400234525"],
+                expected_results: "2014-05-16 10:02:05",
             },
             FunctionExample {
-                name: "not_bool",
-                arguments: vec!["test", "100", "-100"],
+                name: "nan",
+                arguments: vec!["test"],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct NullIf {}
-impl Operator for NullIf {
+struct UnixTimestamp {}
+impl Operator for UnixTimestamp {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let Some(value_one) = args.first() else {
-            return Value::Empty.into();
-        };
-        let Some(value_two) = args.get(1) else {
-            return Value::Empty.into();
+        let Some(value) = args.first() else {
+            return Value::Number(Utc::now().timestamp().into()).into();
         };
-        if *value_one != *value_two {
-            value_one.deref().clone().into()
-        } else {
-            Value::Empty.into()
+        match value.deref() {
+            Value::Date(_) | Value::Timestamp(_) => extract_field(&Field::Epoch, value.deref())
+                .deref()
+                .clone()
+                .into(),
+            _ => Value::Empty.into(),
         }
     }
     fn max_args(&self) -> Option<usize> {
-        Some(2)
+        Some(1)
     }
     fn min_args(&self) -> usize {
-        2
+        0
     }
     fn name(&self) -> &str {
-        "NULLIF"
+        "UNIX_TIMESTAMP"
     }
 
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "eq",
-                arguments: vec!["hello", "hello"],
-                expected_results: "",
+                name: "timestamp",
+                arguments: vec!["2014-05-16 10:02:05"],
+                expected_results: "1400234525",
             },
             FunctionExample {
-                name: "neq",
-                arguments: vec!["hello", "world"],
-                expected_results: "hello",
+                name: "date",
+                arguments: vec!["2014-05-16"],
+                expected_results: "1400198400",
             },
-        ]
-    }
-}
-
-struct Lower {}
-impl Operator for Lower {
-    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        args.first()
-            .and_then(|f| f.as_string())
-            .map(|f| f.to_lowercase())
-            .into()
-    }
-    fn max_args(&self) -> Option<usize> {
-        Some(1)
-    }
-    fn min_args(&self) -> usize {
-        1
-    }
-    fn name(&self) -> &str {
-        "LOWER"
-    }
-
-    #[cfg(test)]
-    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
-        vec![
             FunctionExample {
-                name: "str",
-                arguments: vec!["HeLLo"],
-                expected_results: "hello",
+                name: "before_epoch",
+                arguments: vec!["1969-12-31 23:59:00"],
+                expected_results: "-60",
             },
             FunctionExample {
-                name: "number",
-                arguments: vec!["123"],
+                name: "not_a_date",
+                arguments: vec!["1400234525"],
                 expected_results: "",
             },
         ]
     }
 }
-struct Upper {}
-impl Operator for Upper {
+
+fn as_timestamp(value: Option<&SmartReference<'_, Value>>) -> Option<NaiveDateTime> {
+    match value.map(|v| v.deref()) {
+        Some(Value::Timestamp(time)) => Some(*time),
+        Some(Value::Date(date)) => Some(date.and_time(NaiveTime::default())),
+        _ => None,
+    }
+}
+
+struct DateDiff {}
+impl Operator for DateDiff {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        args.first()
-            .and_then(|f| f.as_string())
-            .map(|f| f.to_uppercase())
-            .into()
+        let (Some(end), Some(start)) = (as_timestamp(args.first()), as_timestamp(args.get(1)))
+        else {
+            return Value::Empty.into();
+        };
+        let days = (end.date() - start.date()).num_days();
+        Value::Number(days.into()).into()
     }
     fn max_args(&self) -> Option<usize> {
-        Some(1)
+        Some(2)
     }
     fn min_args(&self) -> usize {
-        1
+        2
     }
     fn name(&self) -> &str {
-        "UPPER"
+        "DATEDIFF"
     }
 
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "str",
-                arguments: vec!["HeLLo"],
-                expected_results: "HELLO",
+                name: "dates",
+                arguments: vec!["2025-03-10", "2025-02-20"],
+                expected_results: "18",
             },
             FunctionExample {
-                name: "number",
-                arguments: vec!["123"],
+                name: "negative",
+                arguments: vec!["2025-02-20", "2025-03-10"],
+                expected_results: "-18",
+            },
+            FunctionExample {
+                name: "timestamps",
+                arguments: vec!["2025-03-10 01:00:00", "2025-03-09 23:00:00"],
+                expected_results: "1",
+            },
+            FunctionExample {
+                name: "date_and_timestamp",
+                arguments: vec!["2025-03-10 20:00:00", "2025-03-01"],
+                expected_results: "9",
+            },
+            FunctionExample {
+                name: "empty",
+                arguments: vec!["", "2025-03-01"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_a_date",
+                arguments: vec!["2025-03-01", "test"],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct Least {}
-impl Operator for Least {
-    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let mut least = None;
-        for a in args.iter() {
-            if !a.is_empty() {
-                match &least {
-                    None => least = Some(a.deref().clone()),
-                    Some(val) => {
-                        if a.deref() < val {
-                            least = Some(a.deref().clone());
-                        }
-                    }
-                }
-            }
-        }
-        match least {
-            None => Value::Empty.into(),
-            Some(least) => least.into(),
-        }
-    }
-    fn max_args(&self) -> Option<usize> {
-        None
-    }
-    fn min_args(&self) -> usize {
-        0
+/// Build a function that has a unit argument in the `unit_index` position, allowing the unit to be
+/// written as a keyword (`TIMESTAMPDIFF(SECOND, ...)`) as well as a string.
+fn build_function_with_unit(
+    metadata: &Metadata,
+    engine: &Engine,
+    args: &FunctionArguments,
+    unit_index: usize,
+    operator: Box<dyn Operator>,
+) -> Result<Box<dyn Projection>, CvsSqlError> {
+    let mut args = args.clone();
+    if let FunctionArguments::List(lst) = &mut args
+        && let Some(FunctionArg::Unnamed(FunctionArgExpr::Expr(unit))) =
+            lst.args.get_mut(unit_index)
+        && let Expr::Identifier(ident) = unit
+        && ident.quote_style.is_none()
+        && (seconds_in_unit(&ident.value).is_some() || months_in_unit(&ident.value).is_some())
+    {
+        *unit = Expr::value(AstValue::SingleQuotedString(ident.value.clone()));
     }
-    fn name(&self) -> &str {
-        "LEAST"
+    build_function(metadata, engine, &args, operator)
+}
+
+fn seconds_in_unit(unit: &str) -> Option<i64> {
+    match unit.to_uppercase().as_str() {
+        "SECOND" | "SECONDS" => Some(1),
+        "MINUTE" | "MINUTES" => Some(60),
+        "HOUR" | "HOURS" => Some(60 * 60),
+        "DAY" | "DAYS" => Some(24 * 60 * 60),
+        "WEEK" | "WEEKS" => Some(7 * 24 * 60 * 60),
+        _ => None,
     }
+}
 
-    #[cfg(test)]
-    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
-        vec![
-            FunctionExample {
-                name: "least",
-                arguments: vec!["10", "400040", "1044", "-4", "-1"],
-                expected_results: "-4",
-            },
-            FunctionExample {
-                name: "empty",
-                arguments: vec![],
-                expected_results: "",
-            },
-        ]
+fn months_in_unit(unit: &str) -> Option<u32> {
+    match unit.to_uppercase().as_str() {
+        "MONTH" | "MONTHS" => Some(1),
+        "QUARTER" | "QUARTERS" => Some(3),
+        "YEAR" | "YEARS" => Some(12),
+        _ => None,
     }
 }
 
-struct Left {}
-impl Operator for Left {
+struct TimestampDiff {}
+impl Operator for TimestampDiff {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let text = args.first();
-        let Some(text) = text.as_string() else {
+        let Some(Value::Str(unit)) = args.first().map(|v| v.deref()) else {
             return Value::Empty.into();
         };
-        let length = args.get(1);
-        let Some(length) = length.as_usize() else {
+        let Some(seconds_in_unit) = seconds_in_unit(unit) else {
             return Value::Empty.into();
         };
-        if text.len() < length {
-            Value::Str(text.to_string()).into()
-        } else {
-            Value::Str(text[0..length].to_string()).into()
-        }
+        let (Some(start), Some(end)) = (as_timestamp(args.get(1)), as_timestamp(args.get(2)))
+        else {
+            return Value::Empty.into();
+        };
+        let diff = (end - start).num_seconds() / seconds_in_unit;
+        Value::Number(diff.into()).into()
     }
     fn max_args(&self) -> Option<usize> {
-        Some(2)
+        Some(3)
     }
     fn min_args(&self) -> usize {
-        2
+        3
     }
     fn name(&self) -> &str {
-        "LEFT"
+        "TIMESTAMPDIFF"
     }
 
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "simple",
-                arguments: vec!["test", "2"],
-                expected_results: "te",
+                name: "seconds",
+                arguments: vec!["SECOND", "2025-03-10 20:00:00", "2025-03-10 20:01:10"],
+                expected_results: "70",
             },
             FunctionExample {
-                name: "exact",
-                arguments: vec!["test", "4"],
-                expected_results: "test",
+                name: "minutes",
+                arguments: vec!["minute", "2025-03-10 20:00:00", "2025-03-10 20:01:10"],
+                expected_results: "1",
             },
             FunctionExample {
-                name: "more",
-                arguments: vec!["test", "12"],
-                expected_results: "test",
+                name: "hours",
+                arguments: vec!["HOUR", "2025-03-10", "2025-03-10 20:01:10"],
+                expected_results: "20",
             },
             FunctionExample {
-                name: "nan",
-                arguments: vec!["test", "five"],
+                name: "days",
+                arguments: vec!["DAY", "2025-03-10 20:00:00", "2025-03-01 21:00:00"],
+                expected_results: "-8",
+            },
+            FunctionExample {
+                name: "unknown_unit",
+                arguments: vec!["YEAR", "2025-03-10", "2026-03-10"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "not_a_text",
-                arguments: vec!["10", "10"],
+                name: "not_a_date",
+                arguments: vec!["DAY", "2025-03-10", "test"],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct Right {}
-impl Operator for Right {
-    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let text = args.first();
-        let Some(text) = text.as_string() else {
-            return Value::Empty.into();
-        };
-        let length = args.get(1);
-        let Some(length) = length.as_usize() else {
-            return Value::Empty.into();
-        };
-        if text.len() < length {
-            Value::Str(text.to_string()).into()
-        } else {
-            let start = text.len() - length;
-            Value::Str(text[start..].to_string()).into()
+fn shift_time<'a>(args: &[SmartReference<'a, Value>], negate: bool) -> SmartReference<'a, Value> {
+    let Some(time) = as_timestamp(args.first()) else {
+        return Value::Empty.into();
+    };
+    let Some(amount) = args
+        .get(1)
+        .as_num()
+        .filter(|amount| amount.is_integer())
+        .and_then(|amount| amount.to_i64())
+    else {
+        return Value::Empty.into();
+    };
+    let Some(Value::Str(unit)) = args.get(2).map(|v| v.deref()) else {
+        return Value::Empty.into();
+    };
+    let amount = if negate {
+        amount.checked_neg()
+    } else {
+        Some(amount)
+    };
+    let Some(amount) = amount else {
+        return Value::Empty.into();
+    };
+    let shifted = if let Some(months) = months_in_unit(unit) {
+        let months = u32::try_from(amount.unsigned_abs())
+            .ok()
+            .and_then(|amount| amount.checked_mul(months))
+            .map(Months::new);
+        match months {
+            Some(months) if amount >= 0 => time.checked_add_months(months),
+            Some(months) => time.checked_sub_months(months),
+            None => None,
         }
+    } else if let Some(seconds) = seconds_in_unit(unit) {
+        amount
+            .checked_mul(seconds)
+            .and_then(Duration::try_seconds)
+            .and_then(|duration| time.checked_add_signed(duration))
+    } else {
+        None
+    };
+    let Some(shifted) = shifted else {
+        return Value::Empty.into();
+    };
+    // Days, weeks, months and years keep a date a date
+    let whole_days = seconds_in_unit(unit).is_none_or(|seconds| seconds % (24 * 60 * 60) == 0);
+    match args.first().map(|v| v.deref()) {
+        Some(Value::Date(_)) if whole_days => Value::Date(shifted.date()).into(),
+        _ => Value::Timestamp(shifted).into(),
+    }
+}
+
+struct DateAdd {}
+impl Operator for DateAdd {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        shift_time(args, false)
     }
     fn max_args(&self) -> Option<usize> {
-        Some(2)
+        Some(3)
     }
     fn min_args(&self) -> usize {
-        2
+        3
     }
     fn name(&self) -> &str {
-        "RIGHT"
+        "DATE_ADD"
     }
 
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "simple",
-                arguments: vec!["test", "3"],
-                expected_results: "est",
+                name: "days",
+                arguments: vec!["2025-02-20", "10", "DAY"],
+                expected_results: "2025-03-02",
             },
             FunctionExample {
-                name: "exact",
-                arguments: vec!["test", "4"],
-                expected_results: "test",
+                name: "weeks",
+                arguments: vec!["2025-02-20", "-2", "week"],
+                expected_results: "2025-02-06",
             },
             FunctionExample {
-                name: "more",
-                arguments: vec!["test", "12"],
-                expected_results: "test",
+                name: "month_end",
+                arguments: vec!["2025-01-31", "1", "MONTH"],
+                expected_results: "2025-02-28",
             },
             FunctionExample {
-                name: "nan",
-                arguments: vec!["test", "five"],
+                name: "leap_year_month_end",
+                arguments: vec!["2024-01-31", "1", "MONTH"],
+                expected_results: "2024-02-29",
+            },
+            FunctionExample {
+                name: "leap_day_next_year",
+                arguments: vec!["2024-02-29", "1", "YEAR"],
+                expected_results: "2025-02-28",
+            },
+            FunctionExample {
+                name: "quarter",
+                arguments: vec!["2025-11-30", "1", "QUARTER"],
+                expected_results: "2026-02-28",
+            },
+            FunctionExample {
+                name: "hours_of_date",
+                arguments: vec!["2025-03-10", "25", "HOUR"],
+                expected_results: "2025-03-11 01:00:00",
+            },
+            FunctionExample {
+                name: "timestamp",
+                arguments: vec!["2025-03-31 20:00:10", "-1", "MONTH"],
+                expected_results: "2025-02-28 20:00:10",
+            },
+            FunctionExample {
+                name: "overflow",
+                arguments: vec!["2025-03-10", "9999999999", "YEAR"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "not_a_text",
-                arguments: vec!["10", "10"],
+                name: "not_an_integer",
+                arguments: vec!["2025-03-10", "1.5", "DAY"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "unknown_unit",
+                arguments: vec!["2025-03-10", "1", "CENTURY"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_a_date",
+                arguments: vec!["test", "1", "DAY"],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct Lpad {}
-impl Operator for Lpad {
+struct DateSub {}
+impl Operator for DateSub {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let text = args.first();
-        let Some(text) = text.as_string() else {
-            return Value::Empty.into();
-        };
-        let length = args.get(1);
-        let Some(length) = length.as_usize() else {
-            return Value::Empty.into();
-        };
-        let pad = args.get(2);
-
-        let Some(pad) = pad.as_string() else {
-            return Value::Empty.into();
-        };
-
-        if text.len() > length {
-            Value::Str(text[0..length].to_string()).into()
-        } else if pad.is_empty() {
-            Value::Str(text.to_string()).into()
-        } else {
-            let mut str = String::new();
-            let mut chars = pad.chars().cycle();
-            for _ in 0..length - text.len() {
-                let chr = chars.next().unwrap();
-                str.push(chr);
-            }
-            str.push_str(text);
-            Value::Str(str).into()
-        }
+        shift_time(args, true)
     }
-
     fn max_args(&self) -> Option<usize> {
         Some(3)
     }
@@ -1659,164 +2850,246 @@ impl Operator for Lpad {
         3
     }
     fn name(&self) -> &str {
-        "LPAD"
+        "DATE_SUB"
     }
 
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "simple",
-                arguments: vec!["text", "10", "pad"],
-                expected_results: "padpadtext",
+                name: "days",
+                arguments: vec!["2025-03-02", "10", "DAYS"],
+                expected_results: "2025-02-20",
             },
             FunctionExample {
-                name: "more",
-                arguments: vec!["text", "12", "pad"],
-                expected_results: "padpadpatext",
+                name: "month_end",
+                arguments: vec!["2025-03-31", "1", "MONTH"],
+                expected_results: "2025-02-28",
             },
             FunctionExample {
-                name: "less",
-                arguments: vec!["text", "3", "pad"],
-                expected_results: "tex",
+                name: "years",
+                arguments: vec!["2024-02-29", "4", "YEAR"],
+                expected_results: "2020-02-29",
             },
             FunctionExample {
-                name: "exact",
-                arguments: vec!["text", "4", "pad"],
-                expected_results: "text",
+                name: "minutes",
+                arguments: vec!["2025-03-10 00:10:00", "20", "MINUTE"],
+                expected_results: "2025-03-09 23:50:00",
             },
             FunctionExample {
                 name: "negative",
-                arguments: vec!["text", "-122", "pad"],
-                expected_results: "",
+                arguments: vec!["2025-03-10", "-1", "MONTH"],
+                expected_results: "2025-04-10",
             },
             FunctionExample {
-                name: "non_text",
-                arguments: vec!["12", "10", "pad"],
+                name: "empty",
+                arguments: vec!["", "1", "DAY"],
                 expected_results: "",
             },
+        ]
+    }
+}
+
+struct Greatest {}
+impl Operator for Greatest {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let mut greatest = None;
+        for a in args.iter() {
+            match &greatest {
+                None => greatest = Some(a.deref().clone()),
+                Some(val) => {
+                    if a.deref() > val {
+                        greatest = Some(a.deref().clone());
+                    }
+                }
+            }
+        }
+        match greatest {
+            None => Value::Empty.into(),
+            Some(greatest) => greatest.into(),
+        }
+    }
+    fn max_args(&self) -> Option<usize> {
+        None
+    }
+    fn min_args(&self) -> usize {
+        0
+    }
+    fn name(&self) -> &str {
+        "GREATEST"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
             FunctionExample {
-                name: "non_number",
-                arguments: vec!["text", "me", "pad"],
-                expected_results: "",
+                name: "greatest",
+                arguments: vec!["10", "400040", "1044", "-134522352"],
+                expected_results: "400040",
             },
             FunctionExample {
-                name: "not_pad",
-                arguments: vec!["text", "10", "2"],
+                name: "empty",
+                arguments: vec![],
                 expected_results: "",
             },
+        ]
+    }
+}
+struct If {}
+impl Operator for If {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let first = args.first();
+        let Some(condition) = first.as_bool() else {
+            return Value::Empty.into();
+        };
+        let value = if *condition { args.get(1) } else { args.get(2) };
+        match value {
+            Some(v) => v.deref().clone().into(),
+            None => Value::Empty.into(),
+        }
+    }
+    fn max_args(&self) -> Option<usize> {
+        Some(3)
+    }
+    fn min_args(&self) -> usize {
+        3
+    }
+    fn name(&self) -> &str {
+        "IF"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "true",
+                arguments: vec!["TRUE", "100", "-100"],
+                expected_results: "100",
+            },
             FunctionExample {
-                name: "empty_pad",
-                arguments: vec!["text", "10", "2"],
+                name: "false",
+                arguments: vec!["FALSE", "100", "-100"],
+                expected_results: "-100",
+            },
+            FunctionExample {
+                name: "not_bool",
+                arguments: vec!["test", "100", "-100"],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct Rpad {}
-impl Operator for Rpad {
+struct NullIf {}
+impl Operator for NullIf {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let text = args.first();
-        let Some(text) = text.as_string() else {
-            return Value::Empty.into();
-        };
-        let length = args.get(1);
-        let Some(length) = length.as_usize() else {
+        let Some(value_one) = args.first() else {
             return Value::Empty.into();
         };
-        let pad = args.get(2);
-
-        let Some(pad) = pad.as_string() else {
+        let Some(value_two) = args.get(1) else {
             return Value::Empty.into();
         };
-
-        if text.len() > length {
-            Value::Str(text[0..length].to_string()).into()
-        } else if pad.is_empty() {
-            Value::Str(text.to_string()).into()
+        if *value_one != *value_two {
+            value_one.deref().clone().into()
         } else {
-            let mut str = text.to_string();
-            let mut chars = pad.chars().cycle();
-            for _ in 0..length - text.len() {
-                let chr = chars.next().unwrap();
-                str.push(chr);
-            }
-            Value::Str(str).into()
+            Value::Empty.into()
         }
     }
-
     fn max_args(&self) -> Option<usize> {
-        Some(3)
+        Some(2)
     }
     fn min_args(&self) -> usize {
-        3
+        2
     }
     fn name(&self) -> &str {
-        "RPAD"
+        "NULLIF"
     }
 
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "simple",
-                arguments: vec!["text", "10", "pad"],
-                expected_results: "textpadpad",
-            },
-            FunctionExample {
-                name: "more",
-                arguments: vec!["text", "12", "pad"],
-                expected_results: "textpadpadpa",
-            },
-            FunctionExample {
-                name: "less",
-                arguments: vec!["text", "3", "pad"],
-                expected_results: "tex",
+                name: "eq",
+                arguments: vec!["hello", "hello"],
+                expected_results: "",
             },
             FunctionExample {
-                name: "exact",
-                arguments: vec!["text", "4", "pad"],
-                expected_results: "text",
+                name: "neq",
+                arguments: vec!["hello", "world"],
+                expected_results: "hello",
             },
+        ]
+    }
+}
+
+struct Decode {}
+impl Operator for Decode {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let Some((value, rest)) = args.split_first() else {
+            return Value::Empty.into();
+        };
+        let mut pairs = rest.chunks_exact(2);
+        for pair in pairs.by_ref() {
+            // Like Oracle, a NULL search value matches a NULL value
+            if *pair[0] == **value {
+                return pair[1].deref().clone().into();
+            }
+        }
+        match pairs.remainder().first() {
+            Some(default) => default.deref().clone().into(),
+            None => Value::Empty.into(),
+        }
+    }
+    fn max_args(&self) -> Option<usize> {
+        None
+    }
+    fn min_args(&self) -> usize {
+        3
+    }
+    fn name(&self) -> &str {
+        "DECODE"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
             FunctionExample {
-                name: "negative",
-                arguments: vec!["text", "-122", "pad"],
-                expected_results: "",
+                name: "match",
+                arguments: vec!["2", "1", "one", "2", "two", "other"],
+                expected_results: "two",
             },
             FunctionExample {
-                name: "non_text",
-                arguments: vec!["12", "10", "pad"],
-                expected_results: "",
+                name: "default",
+                arguments: vec!["3", "1", "one", "2", "two", "other"],
+                expected_results: "other",
             },
             FunctionExample {
-                name: "non_number",
-                arguments: vec!["text", "me", "pad"],
+                name: "no_default",
+                arguments: vec!["3", "1", "one", "2", "two"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "not_pad",
-                arguments: vec!["text", "10", "2"],
-                expected_results: "",
+                name: "null_search",
+                arguments: vec!["", "1", "one", "", "none", "other"],
+                expected_results: "none",
             },
             FunctionExample {
-                name: "empty_pad",
-                arguments: vec!["text", "10", "2"],
-                expected_results: "",
+                name: "null_value_not_matching",
+                arguments: vec!["1", "", "none", "other"],
+                expected_results: "other",
             },
         ]
     }
 }
 
-struct Ltrim {}
-impl Operator for Ltrim {
+struct Lower {}
+impl Operator for Lower {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
         args.first()
             .and_then(|f| f.as_string())
-            .map(|f| f.trim_start())
+            .map(|f| f.to_lowercase())
             .into()
     }
-
     fn max_args(&self) -> Option<usize> {
         Some(1)
     }
@@ -1824,34 +3097,33 @@ impl Operator for Ltrim {
         1
     }
     fn name(&self) -> &str {
-        "LTRIM"
+        "LOWER"
     }
 
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "simple",
-                arguments: vec!["  hello"],
+                name: "str",
+                arguments: vec!["HeLLo"],
                 expected_results: "hello",
             },
             FunctionExample {
-                name: "not_text",
-                arguments: vec!["12"],
+                name: "number",
+                arguments: vec!["123"],
                 expected_results: "",
             },
         ]
     }
 }
-struct Rtrim {}
-impl Operator for Rtrim {
+struct Upper {}
+impl Operator for Upper {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
         args.first()
             .and_then(|f| f.as_string())
-            .map(|f| f.trim_end())
+            .map(|f| f.to_uppercase())
             .into()
     }
-
     fn max_args(&self) -> Option<usize> {
         Some(1)
     }
@@ -1859,180 +3131,163 @@ impl Operator for Rtrim {
         1
     }
     fn name(&self) -> &str {
-        "RTRIM"
+        "UPPER"
     }
 
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "simple",
-                arguments: vec!["hello\t"],
-                expected_results: "hello",
+                name: "str",
+                arguments: vec!["HeLLo"],
+                expected_results: "HELLO",
             },
             FunctionExample {
-                name: "not_text",
-                arguments: vec!["12"],
+                name: "number",
+                arguments: vec!["123"],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct Position {}
-impl Operator for Position {
+struct InitCap {}
+impl Operator for InitCap {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let sub = args.first();
-        let Some(sub) = sub.as_string() else {
-            return Value::Empty.into();
-        };
-        let str = args.get(1);
-        let Some(str) = str.as_string() else {
+        let Some(str) = args.first().and_then(|f| f.as_string()) else {
             return Value::Empty.into();
         };
-        let start = match args.get(2) {
-            None => 0,
-            Some(val) => {
-                let Some(mut start) = val.as_usize() else {
-                    return Value::Empty.into();
-                };
-                if start == 0 {
-                    start = 1;
-                }
-                if start > str.len() {
-                    return 0.into();
-                }
-                start - 1
+        let mut capitalized = String::with_capacity(str.len());
+        let mut word_start = true;
+        for c in str.chars() {
+            if c.is_whitespace() {
+                word_start = true;
+                capitalized.push(c);
+            } else if c.is_alphanumeric() && word_start {
+                // Leading punctuation (like the quote in "'hello") is skipped
+                word_start = false;
+                capitalized.extend(c.to_uppercase());
+            } else {
+                capitalized.extend(c.to_lowercase());
             }
-        };
-        let position = str[start..].find(sub).map(|f| f + 1).unwrap_or_default();
-        (position + start).into()
+        }
+        Value::Str(capitalized).into()
     }
-
     fn max_args(&self) -> Option<usize> {
-        Some(3)
+        Some(1)
     }
     fn min_args(&self) -> usize {
-        2
+        1
     }
     fn name(&self) -> &str {
-        "POSITION"
+        "INITCAP"
     }
 
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "simple",
-                arguments: vec!["bar", "foobarbar"],
-                expected_results: "4",
-            },
-            FunctionExample {
-                name: "nop",
-                arguments: vec!["xbar", "foobarbar"],
-                expected_results: "0",
+                name: "str",
+                arguments: vec!["hELLo wORLd"],
+                expected_results: "Hello World",
             },
             FunctionExample {
-                name: "with_start",
-                arguments: vec!["bar", "foobarbar", "5"],
-                expected_results: "7",
+                name: "multiple_spaces",
+                arguments: vec!["  hello   world "],
+                expected_results: "  Hello   World ",
             },
             FunctionExample {
-                name: "not_a_sub",
-                arguments: vec!["5", "foobarbar", "5"],
-                expected_results: "",
+                name: "apostrophe",
+                arguments: vec!["o'BRIEN"],
+                expected_results: "O'brien",
             },
             FunctionExample {
-                name: "with_str_as_num",
-                arguments: vec!["bar", "20", "5"],
-                expected_results: "",
+                name: "leading_punctuation",
+                arguments: vec!["(hello) 'WORLD'"],
+                expected_results: "(Hello) 'World'",
             },
             FunctionExample {
-                name: "with_start_as_str",
-                arguments: vec!["bar", "foobarbar", "a"],
+                name: "number",
+                arguments: vec!["123"],
                 expected_results: "",
             },
-            FunctionExample {
-                name: "with_start_larger",
-                arguments: vec!["bar", "foobarbar", "25"],
-                expected_results: "0",
-            },
         ]
     }
 }
 
-struct Repeat {}
-impl Operator for Repeat {
+struct Least {}
+impl Operator for Least {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let str = args.first();
-        let Some(str) = str.as_string() else {
-            return Value::Empty.into();
-        };
-        let count = args.get(1);
-        let Some(count) = count.as_usize() else {
-            return Value::Empty.into();
-        };
-        Value::Str(str.repeat(count)).into()
+        let mut least = None;
+        for a in args.iter() {
+            if !a.is_empty() {
+                match &least {
+                    None => least = Some(a.deref().clone()),
+                    Some(val) => {
+                        if a.deref() < val {
+                            least = Some(a.deref().clone());
+                        }
+                    }
+                }
+            }
+        }
+        match least {
+            None => Value::Empty.into(),
+            Some(least) => least.into(),
+        }
     }
-
     fn max_args(&self) -> Option<usize> {
-        Some(2)
+        None
     }
     fn min_args(&self) -> usize {
-        2
+        0
     }
     fn name(&self) -> &str {
-        "REPEAT"
+        "LEAST"
     }
 
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "simple",
-                arguments: vec!["bar", "3"],
-                expected_results: "barbarbar",
-            },
-            FunctionExample {
-                name: "not_a_string",
-                arguments: vec!["4", "3"],
-                expected_results: "",
+                name: "least",
+                arguments: vec!["10", "400040", "1044", "-4", "-1"],
+                expected_results: "-4",
             },
             FunctionExample {
-                name: "not_a_number",
-                arguments: vec!["bar", "test"],
+                name: "empty",
+                arguments: vec![],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct Replace {}
-impl Operator for Replace {
+struct Left {}
+impl Operator for Left {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let str = args.first();
-        let Some(str) = str.as_string() else {
-            return Value::Empty.into();
-        };
-        let what = args.get(1);
-        let Some(what) = what.as_string() else {
+        let text = args.first();
+        let Some(text) = text.as_string() else {
             return Value::Empty.into();
         };
-        let into = args.get(2);
-        let Some(into) = into.as_string() else {
+        let length = args.get(1);
+        let Some(length) = length.as_usize() else {
             return Value::Empty.into();
         };
-        Value::Str(str.replace(what, into)).into()
+        if text.len() < length {
+            Value::Str(text.to_string()).into()
+        } else {
+            Value::Str(text[0..length].to_string()).into()
+        }
     }
-
     fn max_args(&self) -> Option<usize> {
-        Some(3)
+        Some(2)
     }
     fn min_args(&self) -> usize {
-        3
+        2
     }
     fn name(&self) -> &str {
-        "REPLACE"
+        "LEFT"
     }
 
     #[cfg(test)]
@@ -2040,62 +3295,59 @@ impl Operator for Replace {
         vec![
             FunctionExample {
                 name: "simple",
-                arguments: vec!["hello", "l", "L"],
-                expected_results: "heLLo",
+                arguments: vec!["test", "2"],
+                expected_results: "te",
             },
             FunctionExample {
-                name: "nope",
-                arguments: vec!["test", "bar", "foo"],
+                name: "exact",
+                arguments: vec!["test", "4"],
                 expected_results: "test",
             },
             FunctionExample {
-                name: "not_a_string",
-                arguments: vec!["1", "test", "one"],
-                expected_results: "",
+                name: "more",
+                arguments: vec!["test", "12"],
+                expected_results: "test",
             },
             FunctionExample {
-                name: "not_a_what",
-                arguments: vec!["bar", "2", "one"],
+                name: "nan",
+                arguments: vec!["test", "five"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "not_a_with",
-                arguments: vec!["bar", "test", "3"],
+                name: "not_a_text",
+                arguments: vec!["10", "10"],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct RegexReplace {}
-impl Operator for RegexReplace {
+struct Right {}
+impl Operator for Right {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let str = args.first();
-        let Some(str) = str.as_string() else {
-            return Value::Empty.into();
-        };
-        let pattern = args.get(1);
-        let Some(pattern) = pattern.as_string() else {
-            return Value::Empty.into();
-        };
-        let Ok(pattern) = Regex::new(pattern) else {
+        let text = args.first();
+        let Some(text) = text.as_string() else {
             return Value::Empty.into();
         };
-        let repl = args.get(2);
-        let Some(repl) = repl.as_string() else {
+        let length = args.get(1);
+        let Some(length) = length.as_usize() else {
             return Value::Empty.into();
         };
-        Value::Str(pattern.replace(str, repl).to_string()).into()
+        if text.len() < length {
+            Value::Str(text.to_string()).into()
+        } else {
+            let start = text.len() - length;
+            Value::Str(text[start..].to_string()).into()
+        }
     }
-
     fn max_args(&self) -> Option<usize> {
-        Some(3)
+        Some(2)
     }
     fn min_args(&self) -> usize {
-        3
+        2
     }
     fn name(&self) -> &str {
-        "REGEX_REPLACE"
+        "RIGHT"
     }
 
     #[cfg(test)]
@@ -2103,292 +3355,2177 @@ impl Operator for RegexReplace {
         vec![
             FunctionExample {
                 name: "simple",
-                arguments: vec!["a b c", "b", "B"],
-                expected_results: "a B c",
-            },
-            FunctionExample {
-                name: "few",
-                arguments: vec!["test", "[a-z]", "T"],
-                expected_results: "Test",
+                arguments: vec!["test", "3"],
+                expected_results: "est",
             },
             FunctionExample {
-                name: "not_a_string",
-                arguments: vec!["1", "b", "B"],
-                expected_results: "",
+                name: "exact",
+                arguments: vec!["test", "4"],
+                expected_results: "test",
             },
             FunctionExample {
-                name: "not_a_string_pattern",
-                arguments: vec!["abc", "1", "B"],
-                expected_results: "",
+                name: "more",
+                arguments: vec!["test", "12"],
+                expected_results: "test",
             },
             FunctionExample {
-                name: "invalid_pattern",
-                arguments: vec!["abc", "[+", "B"],
+                name: "nan",
+                arguments: vec!["test", "five"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "no_replacement",
-                arguments: vec!["abc", "b", "4"],
+                name: "not_a_text",
+                arguments: vec!["10", "10"],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct RegexLike {}
-impl Operator for RegexLike {
+struct Lpad {}
+impl Operator for Lpad {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let expr = args.first();
-        let Some(expr) = expr.as_string() else {
+        let text = args.first();
+        let Some(text) = text.as_string() else {
             return Value::Empty.into();
         };
-        let pattern = args.get(1);
-        let Some(pattern) = pattern.as_string() else {
+        let length = args.get(1);
+        let Some(length) = length.as_usize() else {
             return Value::Empty.into();
         };
-        let flags = args.get(2);
-        let pattern = if flags.is_some() {
-            let Some(flags) = flags.as_string() else {
-                return Value::Empty.into();
-            };
-
-            Regex::new(format!("(?{flags}:{pattern})").as_str())
-        } else {
-            Regex::new(pattern)
-        };
+        let pad = args.get(2);
 
-        let Ok(pattern) = pattern else {
+        let Some(pad) = pad.as_string() else {
             return Value::Empty.into();
         };
 
-        Value::Bool(pattern.find(expr).is_some()).into()
+        let text_length = text.chars().count();
+        if text_length > length {
+            Value::Str(text.chars().take(length).collect()).into()
+        } else if pad.is_empty() {
+            Value::Str(text.to_string()).into()
+        } else {
+            let mut str: String = pad.chars().cycle().take(length - text_length).collect();
+            str.push_str(text);
+            Value::Str(str).into()
+        }
     }
 
     fn max_args(&self) -> Option<usize> {
         Some(3)
     }
     fn min_args(&self) -> usize {
-        2
+        3
     }
     fn name(&self) -> &str {
-        "REGEX_LIKE"
+        "LPAD"
     }
 
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "simple_true",
-                arguments: vec!["test", "es"],
-                expected_results: "TRUE",
+                name: "simple",
+                arguments: vec!["text", "10", "pad"],
+                expected_results: "padpadtext",
             },
             FunctionExample {
-                name: "simple_false",
-                arguments: vec!["test", "ES"],
-                expected_results: "FALSE",
+                name: "more",
+                arguments: vec!["text", "12", "pad"],
+                expected_results: "padpadpatext",
             },
             FunctionExample {
-                name: "no_string_one",
-                arguments: vec!["1", "op"],
-                expected_results: "",
+                name: "less",
+                arguments: vec!["text", "3", "pad"],
+                expected_results: "tex",
             },
             FunctionExample {
-                name: "no_string_pattern",
-                arguments: vec!["test", "FALSE"],
-                expected_results: "",
+                name: "exact",
+                arguments: vec!["text", "4", "pad"],
+                expected_results: "text",
             },
             FunctionExample {
-                name: "invalid_pattern",
-                arguments: vec!["test", "[+"],
-                expected_results: "",
+                name: "multi_byte",
+                arguments: vec!["héllo", "8", "·"],
+                expected_results: "···héllo",
             },
             FunctionExample {
-                name: "with_flags",
-                arguments: vec!["test", "TEST", "i"],
-                expected_results: "TRUE",
+                name: "multi_byte_less",
+                arguments: vec!["héllo", "2", "·"],
+                expected_results: "hé",
             },
             FunctionExample {
-                name: "with_flags",
-                arguments: vec!["test", "TEST", "i"],
-                expected_results: "TRUE",
+                name: "negative",
+                arguments: vec!["text", "-122", "pad"],
+                expected_results: "",
             },
             FunctionExample {
-                name: "with_invalid_flags",
-                arguments: vec!["test", "TEST", "q"],
+                name: "non_text",
+                arguments: vec!["12", "10", "pad"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "with_no_string_flags",
-                arguments: vec!["test", "TEST", "TRUE"],
+                name: "non_number",
+                arguments: vec!["text", "me", "pad"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_pad",
+                arguments: vec!["text", "10", "2"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "empty_pad",
+                arguments: vec!["text", "10", "2"],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct RegexSubstring {}
-impl Operator for RegexSubstring {
+struct Space {}
+impl Operator for Space {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let expr = args.first();
-        let Some(expr) = expr.as_string() else {
-            return Value::Empty.into();
-        };
-        let pattern = args.get(1);
-        let Some(pattern) = pattern.as_string() else {
+        let Some(count) = args.first().as_usize() else {
             return Value::Empty.into();
         };
-        let pos = args.get(2);
-        let pos = match pos {
-            None => Some(1),
-            Some(pos) => pos.as_usize(),
-        };
-        let Some(pos) = pos else {
+        Value::Str(" ".repeat(count)).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "SPACE"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["3"],
+                expected_results: "   ",
+            },
+            FunctionExample {
+                name: "zero",
+                arguments: vec!["0"],
+                expected_results: "\"\"",
+            },
+            FunctionExample {
+                name: "negative",
+                arguments: vec!["-2"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "non_number",
+                arguments: vec!["text"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Rpad {}
+impl Operator for Rpad {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let text = args.first();
+        let Some(text) = text.as_string() else {
             return Value::Empty.into();
         };
-        if pos == 0 {
+        let length = args.get(1);
+        let Some(length) = length.as_usize() else {
             return Value::Empty.into();
-        }
-        let pos = pos - 1;
-        let occurrence = args.get(3);
-        let occurrence = match occurrence {
-            None => Some(1),
-            Some(occurrence) => occurrence.as_usize(),
         };
-        let Some(occurrence) = occurrence else {
+        let pad = args.get(2);
+
+        let Some(pad) = pad.as_string() else {
             return Value::Empty.into();
         };
-        if occurrence == 0 {
-            return Value::Empty.into();
-        }
-        let occurrence = occurrence - 1;
-
-        let flags = args.get(4);
-        let pattern = if flags.is_some() {
-            let Some(flags) = flags.as_string() else {
-                return Value::Empty.into();
-            };
 
-            Regex::new(format!("(?{flags}:{pattern})").as_str())
+        let text_length = text.chars().count();
+        if text_length > length {
+            Value::Str(text.chars().take(length).collect()).into()
+        } else if pad.is_empty() {
+            Value::Str(text.to_string()).into()
         } else {
-            Regex::new(pattern)
-        };
-        if pos > expr.len() {
-            return Value::Str(String::new()).into();
+            let mut str = text.to_string();
+            str.extend(pad.chars().cycle().take(length - text_length));
+            Value::Str(str).into()
         }
-
-        let Ok(pattern) = pattern else {
-            return Value::Empty.into();
-        };
-
-        let matches = pattern
-            .find_iter(&expr[pos..])
-            .nth(occurrence)
-            .map(|f| f.as_str())
-            .unwrap_or("");
-
-        Value::Str(matches.into()).into()
     }
 
     fn max_args(&self) -> Option<usize> {
-        Some(5)
+        Some(3)
     }
     fn min_args(&self) -> usize {
-        2
+        3
     }
     fn name(&self) -> &str {
-        "REGEXP_SUBSTR"
+        "RPAD"
     }
 
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "simple_true",
-                arguments: vec!["abc def ghi", "[a-z]+"],
-                expected_results: "abc",
+                name: "simple",
+                arguments: vec!["text", "10", "pad"],
+                expected_results: "textpadpad",
             },
             FunctionExample {
-                name: "can_not_find",
-                arguments: vec!["test", "ES"],
-                expected_results: "\"\"",
+                name: "more",
+                arguments: vec!["text", "12", "pad"],
+                expected_results: "textpadpadpa",
             },
             FunctionExample {
-                name: "with_pos",
-                arguments: vec!["abc def ghi", "[a-z]+", "2"],
-                expected_results: "bc",
+                name: "less",
+                arguments: vec!["text", "3", "pad"],
+                expected_results: "tex",
             },
             FunctionExample {
-                name: "with_pos_and_oc",
-                arguments: vec!["abc def ghi", "[a-z]+", "2", "2"],
-                expected_results: "def",
+                name: "exact",
+                arguments: vec!["text", "4", "pad"],
+                expected_results: "text",
             },
             FunctionExample {
-                name: "with_pos_and_oc_to_large",
-                arguments: vec!["abc def ghi", "[a-z]+", "2", "10"],
-                expected_results: "\"\"",
+                name: "multi_byte",
+                arguments: vec!["héllo", "8", "·"],
+                expected_results: "héllo···",
             },
             FunctionExample {
-                name: "with_flags",
-                arguments: vec!["abc def ghi", "[A-Z]+", "1", "1", "i"],
-                expected_results: "abc",
+                name: "multi_byte_less",
+                arguments: vec!["héllo", "2", "·"],
+                expected_results: "hé",
             },
             FunctionExample {
-                name: "invalid_str",
-                arguments: vec!["1", "[A-Z]+", "1", "1", "i"],
+                name: "negative",
+                arguments: vec!["text", "-122", "pad"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "no_regex",
-                arguments: vec!["abc def ghi", "2", "1", "1", "i"],
+                name: "non_text",
+                arguments: vec!["12", "10", "pad"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "invalid_regex",
-                arguments: vec!["abc def ghi", "[A-", "1", "1", "i"],
+                name: "non_number",
+                arguments: vec!["text", "me", "pad"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "invalid_pos",
-                arguments: vec!["abc def ghi", "[A-Z]+", "a", "1", "i"],
+                name: "not_pad",
+                arguments: vec!["text", "10", "2"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "post_zero",
-                arguments: vec!["abc def ghi", "[A-Z]+", "0", "1", "i"],
+                name: "empty_pad",
+                arguments: vec!["text", "10", "2"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Ltrim {}
+impl Operator for Ltrim {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        args.first()
+            .and_then(|f| f.as_string())
+            .map(|f| f.trim_start())
+            .into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "LTRIM"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["  hello"],
+                expected_results: "hello",
+            },
+            FunctionExample {
+                name: "not_text",
+                arguments: vec!["12"],
                 expected_results: "",
             },
+        ]
+    }
+}
+struct Rtrim {}
+impl Operator for Rtrim {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        args.first()
+            .and_then(|f| f.as_string())
+            .map(|f| f.trim_end())
+            .into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "RTRIM"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["hello\t"],
+                expected_results: "hello",
+            },
             FunctionExample {
-                name: "invalid_oc",
-                arguments: vec!["abc def ghi", "[A-Z]+", "1", "A", "i"],
+                name: "not_text",
+                arguments: vec!["12"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Position {}
+impl Operator for Position {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let sub = args.first();
+        let Some(sub) = sub.as_string() else {
+            return Value::Empty.into();
+        };
+        let str = args.get(1);
+        let Some(str) = str.as_string() else {
+            return Value::Empty.into();
+        };
+        let start = match args.get(2) {
+            None => 0,
+            Some(val) => {
+                let Some(mut start) = val.as_usize() else {
+                    return Value::Empty.into();
+                };
+                if start == 0 {
+                    start = 1;
+                }
+                if start > str.len() {
+                    return 0.into();
+                }
+                start - 1
+            }
+        };
+        let position = str[start..].find(sub).map(|f| f + 1).unwrap_or_default();
+        (position + start).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(3)
+    }
+    fn min_args(&self) -> usize {
+        2
+    }
+    fn name(&self) -> &str {
+        "POSITION"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["bar", "foobarbar"],
+                expected_results: "4",
+            },
+            FunctionExample {
+                name: "nop",
+                arguments: vec!["xbar", "foobarbar"],
+                expected_results: "0",
+            },
+            FunctionExample {
+                name: "with_start",
+                arguments: vec!["bar", "foobarbar", "5"],
+                expected_results: "7",
+            },
+            FunctionExample {
+                name: "not_a_sub",
+                arguments: vec!["5", "foobarbar", "5"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "with_str_as_num",
+                arguments: vec!["bar", "20", "5"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "with_start_as_str",
+                arguments: vec!["bar", "foobarbar", "a"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "with_start_larger",
+                arguments: vec!["bar", "foobarbar", "25"],
+                expected_results: "0",
+            },
+        ]
+    }
+}
+
+struct Instr {}
+impl Operator for Instr {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let str = args.first();
+        let Some(str) = str.as_string() else {
+            return Value::Empty.into();
+        };
+        let sub = args.get(1);
+        let Some(sub) = sub.as_string() else {
+            return Value::Empty.into();
+        };
+        let position = str
+            .find(sub)
+            .map(|byte| str[..byte].chars().count() + 1)
+            .unwrap_or_default();
+        position.into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(2)
+    }
+    fn min_args(&self) -> usize {
+        2
+    }
+    fn name(&self) -> &str {
+        "INSTR"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["foobarbar", "bar"],
+                expected_results: "4",
+            },
+            FunctionExample {
+                name: "locate_order",
+                arguments: vec!["bar", "foobarbar"],
+                expected_results: "0",
+            },
+            FunctionExample {
+                name: "non_ascii",
+                arguments: vec!["héllo wörld", "wör"],
+                expected_results: "7",
+            },
+            FunctionExample {
+                name: "nop",
+                arguments: vec!["foobarbar", "xbar"],
+                expected_results: "0",
+            },
+            FunctionExample {
+                name: "not_a_sub",
+                arguments: vec!["foobarbar", "5"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_a_str",
+                arguments: vec!["5", "bar"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+/// Quoted digits (like `'12345'`) are read as numbers, so `TRANSLATE` uses the text of numbers.
+fn translate_text(value: Option<&SmartReference<'_, Value>>) -> Option<String> {
+    match value?.deref() {
+        Value::Str(str) => Some(str.clone()),
+        Value::Number(num) => Some(num.to_string()),
+        _ => None,
+    }
+}
+
+struct Translate {}
+impl Operator for Translate {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let str = args.first();
+        let Some(str) = translate_text(str) else {
+            return Value::Empty.into();
+        };
+        let from = args.get(1);
+        let Some(from) = translate_text(from) else {
+            return Value::Empty.into();
+        };
+        let to = args.get(2);
+        let Some(to) = translate_text(to) else {
+            return Value::Empty.into();
+        };
+        let to: Vec<char> = to.chars().collect();
+        let translated = str
+            .chars()
+            .filter_map(|c| match from.chars().position(|f| f == c) {
+                // Characters without a replacement are removed
+                Some(index) => to.get(index).copied(),
+                None => Some(c),
+            })
+            .collect();
+        Value::Str(translated).into()
+    }
+    fn max_args(&self) -> Option<usize> {
+        Some(3)
+    }
+    fn min_args(&self) -> usize {
+        3
+    }
+    fn name(&self) -> &str {
+        "TRANSLATE"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "shorter_to",
+                arguments: vec!["a1b2c3d4e", "abc", "xy"],
+                expected_results: "x1y23d4e",
+            },
+            FunctionExample {
+                name: "same_length",
+                arguments: vec!["hello world", "lo", "LO"],
+                expected_results: "heLLO wOrLd",
+            },
+            FunctionExample {
+                name: "first_match_wins",
+                arguments: vec!["aaa", "aa", "xy"],
+                expected_results: "xxx",
+            },
+            FunctionExample {
+                name: "multi_byte",
+                arguments: vec!["héllo wörld", "éö", "eo"],
+                expected_results: "hello world",
+            },
+            FunctionExample {
+                name: "digits",
+                arguments: vec!["12345", "143", "ax"],
+                expected_results: "a2x5",
+            },
+            FunctionExample {
+                name: "not_a_str",
+                arguments: vec!["2024-01-01", "-", "/"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+fn hex_digest<'a, D: Digest>(args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+    let Some(text) = args.first().and_then(|f| f.as_string()) else {
+        return Value::Empty.into();
+    };
+    let digest = D::digest(text.as_bytes());
+    Value::Str(digest.iter().map(|byte| format!("{byte:02x}")).collect()).into()
+}
+
+struct Md5 {}
+impl Operator for Md5 {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        hex_digest::<md5::Md5>(args)
+    }
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "MD5"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "abc",
+                arguments: vec!["abc"],
+                expected_results: "900150983cd24fb0d6963f7d28e17f72",
+            },
+            FunctionExample {
+                name: "not_a_text",
+                arguments: vec!["12"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Sha1 {}
+impl Operator for Sha1 {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        hex_digest::<sha1::Sha1>(args)
+    }
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "SHA1"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "abc",
+                arguments: vec!["abc"],
+                expected_results: "a9993e364706816aba3e25717850c26c9cd0d89d",
+            },
+            FunctionExample {
+                name: "not_a_text",
+                arguments: vec!["12"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Sha256 {}
+impl Operator for Sha256 {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let Some(text) = args.first().and_then(|f| f.as_string()) else {
+            return Value::Empty.into();
+        };
+        Value::Str(sha256::digest(text)).into()
+    }
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "SHA256"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "abc",
+                arguments: vec!["abc"],
+                expected_results: "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            },
+            FunctionExample {
+                name: "not_a_text",
+                arguments: vec!["12"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct ToBase64 {}
+impl Operator for ToBase64 {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        args.first()
+            .and_then(|f| f.as_string())
+            .map(|f| BASE64_STANDARD.encode(f))
+            .into()
+    }
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "TO_BASE64"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["hello"],
+                expected_results: "aGVsbG8=",
+            },
+            FunctionExample {
+                name: "multi_byte",
+                arguments: vec!["héllo"],
+                expected_results: "aMOpbGxv",
+            },
+            FunctionExample {
+                name: "not_a_text",
+                arguments: vec!["12"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct FromBase64 {}
+impl Operator for FromBase64 {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let Some(text) = args.first().and_then(|f| f.as_string()) else {
+            return Value::Empty.into();
+        };
+        let Ok(bytes) = BASE64_STANDARD.decode(text) else {
+            return Value::Empty.into();
+        };
+        match String::from_utf8(bytes) {
+            Ok(decoded) => Value::Str(decoded).into(),
+            Err(_) => Value::Empty.into(),
+        }
+    }
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "FROM_BASE64"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["aGVsbG8="],
+                expected_results: "hello",
+            },
+            FunctionExample {
+                name: "multi_byte",
+                arguments: vec!["aMOpbGxv"],
+                expected_results: "héllo",
+            },
+            FunctionExample {
+                name: "not_base64",
+                arguments: vec!["hello!"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_utf8",
+                arguments: vec!["/w=="],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_a_text",
+                arguments: vec!["12"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+/// Formats an integer argument, where negative numbers are formatted as their 64 bits two's complement.
+fn format_integer<'a>(
+    args: &[SmartReference<'a, Value>],
+    format: fn(u64) -> String,
+) -> SmartReference<'a, Value> {
+    let Some(num) = args
+        .first()
+        .as_num()
+        .filter(|num| num.is_integer())
+        .and_then(|num| num.to_i64())
+    else {
+        return Value::Empty.into();
+    };
+    Value::Str(format(num as u64)).into()
+}
+
+struct Hex {}
+impl Operator for Hex {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        format_integer(args, |num| format!("{num:X}"))
+    }
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "HEX"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["255"],
+                expected_results: "FF",
+            },
+            FunctionExample {
+                name: "negative",
+                arguments: vec!["-1"],
+                expected_results: "FFFFFFFFFFFFFFFF",
+            },
+            FunctionExample {
+                name: "fraction",
+                arguments: vec!["1.5"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_a_number",
+                arguments: vec!["test"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Bin {}
+impl Operator for Bin {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        format_integer(args, |num| format!("{num:b}"))
+    }
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "BIN"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "fraction",
+                arguments: vec!["1.5"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_a_number",
+                arguments: vec!["test"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Oct {}
+impl Operator for Oct {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        format_integer(args, |num| format!("{num:o}"))
+    }
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "OCT"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "fraction",
+                arguments: vec!["1.5"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_a_number",
+                arguments: vec!["test"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Unhex {}
+impl Operator for Unhex {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        // Hex strings with only digits (like '41') are read as numbers
+        let hex = match args.first().map(|f| f.deref()) {
+            Some(Value::Str(hex)) => hex.clone(),
+            Some(Value::Number(num)) if num.is_integer() && num >= &BigDecimal::zero() => {
+                num.with_scale(0).to_string()
+            }
+            _ => return Value::Empty.into(),
+        };
+        let hex = if hex.len() % 2 == 1 {
+            format!("0{hex}")
+        } else {
+            hex
+        };
+        let bytes: Option<Vec<u8>> = hex
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                let pair = std::str::from_utf8(pair).ok()?;
+                u8::from_str_radix(pair, 16).ok()
+            })
+            .collect();
+        let Some(bytes) = bytes else {
+            return Value::Empty.into();
+        };
+        match String::from_utf8(bytes) {
+            Ok(text) => Value::Str(text).into(),
+            Err(_) => Value::Empty.into(),
+        }
+    }
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "UNHEX"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["68656C6C6F"],
+                expected_results: "hello",
+            },
+            FunctionExample {
+                name: "lower_case",
+                arguments: vec!["68656c6c6f"],
+                expected_results: "hello",
+            },
+            FunctionExample {
+                name: "digits_only",
+                arguments: vec!["4142"],
+                expected_results: "AB",
+            },
+            FunctionExample {
+                name: "odd_length",
+                arguments: vec!["14142"],
+                expected_results: "\u{1}AB",
+            },
+            FunctionExample {
+                name: "not_hex",
+                arguments: vec!["hello"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_utf8",
+                arguments: vec!["FF"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct SubstringIndex {}
+impl Operator for SubstringIndex {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let text = args.first();
+        let Some(text) = text.as_string() else {
+            return Value::Empty.into();
+        };
+        let delimiter = args.get(1);
+        let Some(delimiter) = delimiter.as_string() else {
+            return Value::Empty.into();
+        };
+        let Some(count) = args.get(2).as_i64() else {
+            return Value::Empty.into();
+        };
+        if count == 0 || delimiter.is_empty() {
+            return Value::Str(String::new()).into();
+        }
+        let skip = usize::try_from(count.unsigned_abs() - 1).unwrap_or(usize::MAX);
+        let substring = if count > 0 {
+            match text.match_indices(delimiter).nth(skip) {
+                Some((index, _)) => &text[..index],
+                None => text,
+            }
+        } else {
+            match text.rmatch_indices(delimiter).nth(skip) {
+                Some((index, _)) => &text[index + delimiter.len()..],
+                None => text,
+            }
+        };
+        Value::Str(substring.to_string()).into()
+    }
+    fn max_args(&self) -> Option<usize> {
+        Some(3)
+    }
+    fn min_args(&self) -> usize {
+        3
+    }
+    fn name(&self) -> &str {
+        "SUBSTRING_INDEX"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "from_left",
+                arguments: vec!["a.b.c.d", ".", "2"],
+                expected_results: "a.b",
+            },
+            FunctionExample {
+                name: "from_right",
+                arguments: vec!["a.b.c.d", ".", "-2"],
+                expected_results: "c.d",
+            },
+            FunctionExample {
+                name: "long_delimiter",
+                arguments: vec!["a::b::c", "::", "-1"],
+                expected_results: "c",
+            },
+            FunctionExample {
+                name: "more_than_found",
+                arguments: vec!["a.b.c.d", ".", "10"],
+                expected_results: "a.b.c.d",
+            },
+            FunctionExample {
+                name: "zero",
+                arguments: vec!["a.b.c.d", ".", "0"],
+                expected_results: "\"\"",
+            },
+            FunctionExample {
+                name: "nan",
+                arguments: vec!["a.b.c.d", ".", "two"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_a_text",
+                arguments: vec!["10", ".", "1"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Repeat {}
+impl Operator for Repeat {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let str = args.first();
+        let Some(str) = str.as_string() else {
+            return Value::Empty.into();
+        };
+        let count = args.get(1);
+        let Some(count) = count.as_usize() else {
+            return Value::Empty.into();
+        };
+        Value::Str(str.repeat(count)).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(2)
+    }
+    fn min_args(&self) -> usize {
+        2
+    }
+    fn name(&self) -> &str {
+        "REPEAT"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["bar", "3"],
+                expected_results: "barbarbar",
+            },
+            FunctionExample {
+                name: "not_a_string",
+                arguments: vec!["4", "3"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_a_number",
+                arguments: vec!["bar", "test"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Replace {}
+impl Operator for Replace {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let str = args.first();
+        let Some(str) = str.as_string() else {
+            return Value::Empty.into();
+        };
+        let what = args.get(1);
+        let Some(what) = what.as_string() else {
+            return Value::Empty.into();
+        };
+        let into = args.get(2);
+        let Some(into) = into.as_string() else {
+            return Value::Empty.into();
+        };
+        Value::Str(str.replace(what, into)).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(3)
+    }
+    fn min_args(&self) -> usize {
+        3
+    }
+    fn name(&self) -> &str {
+        "REPLACE"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["hello", "l", "L"],
+                expected_results: "heLLo",
+            },
+            FunctionExample {
+                name: "nope",
+                arguments: vec!["test", "bar", "foo"],
+                expected_results: "test",
+            },
+            FunctionExample {
+                name: "not_a_string",
+                arguments: vec!["1", "test", "one"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_a_what",
+                arguments: vec!["bar", "2", "one"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_a_with",
+                arguments: vec!["bar", "test", "3"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct RegexReplace {}
+impl Operator for RegexReplace {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let str = args.first();
+        let Some(str) = str.as_string() else {
+            return Value::Empty.into();
+        };
+        let pattern = args.get(1);
+        let Some(pattern) = pattern.as_string() else {
+            return Value::Empty.into();
+        };
+        let Ok(pattern) = Regex::new(pattern) else {
+            return Value::Empty.into();
+        };
+        let repl = args.get(2);
+        let Some(repl) = repl.as_string() else {
+            return Value::Empty.into();
+        };
+        Value::Str(pattern.replace(str, repl).to_string()).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(3)
+    }
+    fn min_args(&self) -> usize {
+        3
+    }
+    fn name(&self) -> &str {
+        "REGEX_REPLACE"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["a b c", "b", "B"],
+                expected_results: "a B c",
+            },
+            FunctionExample {
+                name: "few",
+                arguments: vec!["test", "[a-z]", "T"],
+                expected_results: "Test",
+            },
+            FunctionExample {
+                name: "not_a_string",
+                arguments: vec!["1", "b", "B"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_a_string_pattern",
+                arguments: vec!["abc", "1", "B"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "invalid_pattern",
+                arguments: vec!["abc", "[+", "B"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "no_replacement",
+                arguments: vec!["abc", "b", "4"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct RegexLike {}
+impl Operator for RegexLike {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let expr = args.first();
+        let Some(expr) = expr.as_string() else {
+            return Value::Empty.into();
+        };
+        let pattern = args.get(1);
+        let Some(pattern) = pattern.as_string() else {
+            return Value::Empty.into();
+        };
+        let flags = args.get(2);
+        let pattern = if flags.is_some() {
+            let Some(flags) = flags.as_string() else {
+                return Value::Empty.into();
+            };
+
+            Regex::new(format!("(?{flags}:{pattern})").as_str())
+        } else {
+            Regex::new(pattern)
+        };
+
+        let Ok(pattern) = pattern else {
+            return Value::Empty.into();
+        };
+
+        Value::Bool(pattern.find(expr).is_some()).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(3)
+    }
+    fn min_args(&self) -> usize {
+        2
+    }
+    fn name(&self) -> &str {
+        "REGEX_LIKE"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple_true",
+                arguments: vec!["test", "es"],
+                expected_results: "TRUE",
+            },
+            FunctionExample {
+                name: "simple_false",
+                arguments: vec!["test", "ES"],
+                expected_results: "FALSE",
+            },
+            FunctionExample {
+                name: "no_string_one",
+                arguments: vec!["1", "op"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "no_string_pattern",
+                arguments: vec!["test", "FALSE"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "invalid_pattern",
+                arguments: vec!["test", "[+"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "with_flags",
+                arguments: vec!["test", "TEST", "i"],
+                expected_results: "TRUE",
+            },
+            FunctionExample {
+                name: "with_flags",
+                arguments: vec!["test", "TEST", "i"],
+                expected_results: "TRUE",
+            },
+            FunctionExample {
+                name: "with_invalid_flags",
+                arguments: vec!["test", "TEST", "q"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "with_no_string_flags",
+                arguments: vec!["test", "TEST", "TRUE"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct RegexSubstring {}
+impl Operator for RegexSubstring {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let expr = args.first();
+        let Some(expr) = expr.as_string() else {
+            return Value::Empty.into();
+        };
+        let pattern = args.get(1);
+        let Some(pattern) = pattern.as_string() else {
+            return Value::Empty.into();
+        };
+        let pos = args.get(2);
+        let pos = match pos {
+            None => Some(1),
+            Some(pos) => pos.as_usize(),
+        };
+        let Some(pos) = pos else {
+            return Value::Empty.into();
+        };
+        if pos == 0 {
+            return Value::Empty.into();
+        }
+        let pos = pos - 1;
+        let occurrence = args.get(3);
+        let occurrence = match occurrence {
+            None => Some(1),
+            Some(occurrence) => occurrence.as_usize(),
+        };
+        let Some(occurrence) = occurrence else {
+            return Value::Empty.into();
+        };
+        if occurrence == 0 {
+            return Value::Empty.into();
+        }
+        let occurrence = occurrence - 1;
+
+        let flags = args.get(4);
+        let pattern = if flags.is_some() {
+            let Some(flags) = flags.as_string() else {
+                return Value::Empty.into();
+            };
+
+            Regex::new(format!("(?{flags}:{pattern})").as_str())
+        } else {
+            Regex::new(pattern)
+        };
+        if pos > expr.len() {
+            return Value::Str(String::new()).into();
+        }
+
+        let Ok(pattern) = pattern else {
+            return Value::Empty.into();
+        };
+
+        let matches = pattern
+            .find_iter(&expr[pos..])
+            .nth(occurrence)
+            .map(|f| f.as_str())
+            .unwrap_or("");
+
+        Value::Str(matches.into()).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(5)
+    }
+    fn min_args(&self) -> usize {
+        2
+    }
+    fn name(&self) -> &str {
+        "REGEXP_SUBSTR"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple_true",
+                arguments: vec!["abc def ghi", "[a-z]+"],
+                expected_results: "abc",
+            },
+            FunctionExample {
+                name: "can_not_find",
+                arguments: vec!["test", "ES"],
+                expected_results: "\"\"",
+            },
+            FunctionExample {
+                name: "with_pos",
+                arguments: vec!["abc def ghi", "[a-z]+", "2"],
+                expected_results: "bc",
+            },
+            FunctionExample {
+                name: "with_pos_and_oc",
+                arguments: vec!["abc def ghi", "[a-z]+", "2", "2"],
+                expected_results: "def",
+            },
+            FunctionExample {
+                name: "with_pos_and_oc_to_large",
+                arguments: vec!["abc def ghi", "[a-z]+", "2", "10"],
+                expected_results: "\"\"",
+            },
+            FunctionExample {
+                name: "with_flags",
+                arguments: vec!["abc def ghi", "[A-Z]+", "1", "1", "i"],
+                expected_results: "abc",
+            },
+            FunctionExample {
+                name: "invalid_str",
+                arguments: vec!["1", "[A-Z]+", "1", "1", "i"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "no_regex",
+                arguments: vec!["abc def ghi", "2", "1", "1", "i"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "invalid_regex",
+                arguments: vec!["abc def ghi", "[A-", "1", "1", "i"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "invalid_pos",
+                arguments: vec!["abc def ghi", "[A-Z]+", "a", "1", "i"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "post_zero",
+                arguments: vec!["abc def ghi", "[A-Z]+", "0", "1", "i"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "invalid_oc",
+                arguments: vec!["abc def ghi", "[A-Z]+", "1", "A", "i"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "zero_oc",
+                arguments: vec!["abc def ghi", "[A-Z]+", "1", "0", "i"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "invalid_flags",
+                arguments: vec!["abc def ghi", "[A-Z]+", "1", "0", "2"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "unknown_flags",
+                arguments: vec!["abc def ghi", "[A-Z]+", "1", "0", "1"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Reverse {}
+impl Operator for Reverse {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let str = args.first();
+        let Some(str) = str.as_string() else {
+            return Value::Empty.into();
+        };
+        Value::Str(str.chars().rev().collect()).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "REVERSE"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["simple"],
+                expected_results: "elpmis",
+            },
+            FunctionExample {
+                name: "nopre",
+                arguments: vec!["323"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+struct Round {}
+impl Operator for Round {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let num = args.first();
+        let Some(num) = num.as_num() else {
+            return Value::Empty.into();
+        };
+        let digit = args.get(1);
+        let digit = if digit.is_some() {
+            digit.as_i64()
+        } else {
+            Some(0)
+        };
+        let Some(digit) = digit else {
+            return Value::Empty.into();
+        };
+
+        Value::Number(num.with_scale_round(digit, bigdecimal::RoundingMode::HalfDown)).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(2)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "ROUND"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["-1.23"],
+                expected_results: "-1",
+            },
+            FunctionExample {
+                name: "larger",
+                arguments: vec!["43.123"],
+                expected_results: "43",
+            },
+            FunctionExample {
+                name: "go_up",
+                arguments: vec!["43.6123"],
+                expected_results: "44",
+            },
+            FunctionExample {
+                name: "with_arg",
+                arguments: vec![".1234567890123456789012345678901234567890", "35"],
+                expected_results: "0.12345678901234567890123456789012346",
+            },
+            FunctionExample {
+                name: "with_negative_arg",
+                arguments: vec!["23.298", "-1"],
+                expected_results: "20",
+            },
+            FunctionExample {
+                name: "nan1",
+                arguments: vec!["test"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "nan2",
+                arguments: vec!["1", "test"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Truncate {}
+impl Operator for Truncate {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let num = args.first();
+        let Some(num) = num.as_num() else {
+            return Value::Empty.into();
+        };
+        let digit = args.get(1);
+        let digit = if digit.is_some() {
+            digit.as_i64()
+        } else {
+            Some(0)
+        };
+        let Some(digit) = digit else {
+            return Value::Empty.into();
+        };
+
+        Value::Number(num.with_scale_round(digit, bigdecimal::RoundingMode::Down)).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(2)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "TRUNCATE"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["43.6123"],
+                expected_results: "43",
+            },
+            FunctionExample {
+                name: "negative",
+                arguments: vec!["-1.99", "0"],
+                expected_results: "-1",
+            },
+            FunctionExample {
+                name: "with_arg",
+                arguments: vec!["3.14159", "3"],
+                expected_results: "3.141",
+            },
+            FunctionExample {
+                name: "with_negative_arg",
+                arguments: vec!["29.9", "-1"],
+                expected_results: "20",
+            },
+            FunctionExample {
+                name: "nan1",
+                arguments: vec!["test"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "nan2",
+                arguments: vec!["1", "test"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Sign {}
+impl Operator for Sign {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let num = args.first();
+        let Some(num) = num.as_num() else {
+            return Value::Empty.into();
+        };
+        let sign = match num.sign() {
+            bigdecimal::num_bigint::Sign::Minus => -1,
+            bigdecimal::num_bigint::Sign::NoSign => 0,
+            bigdecimal::num_bigint::Sign::Plus => 1,
+        };
+
+        Value::Number(sign.into()).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "SIGN"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "positive",
+                arguments: vec!["12.5"],
+                expected_results: "1",
+            },
+            FunctionExample {
+                name: "negative",
+                arguments: vec!["-0.01"],
+                expected_results: "-1",
+            },
+            FunctionExample {
+                name: "zero",
+                arguments: vec!["0.00"],
+                expected_results: "0",
+            },
+            FunctionExample {
+                name: "nan",
+                arguments: vec!["test"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Cbrt {}
+impl Operator for Cbrt {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let num = args.first();
+        let Some(num) = num.as_num() else {
+            return Value::Empty.into();
+        };
+
+        Value::Number(num.cbrt()).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "CBRT"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["27"],
+                expected_results: "3",
+            },
+            FunctionExample {
+                name: "negative",
+                arguments: vec!["-8"],
+                expected_results: "-2",
+            },
+            FunctionExample {
+                name: "fraction",
+                arguments: vec!["0.125"],
+                expected_results: "0.5",
+            },
+            FunctionExample {
+                name: "nan",
+                arguments: vec!["test"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+struct Sqrt {}
+impl Operator for Sqrt {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let num = args.first();
+        let Some(num) = num.as_num() else {
+            return Value::Empty.into();
+        };
+        match num.sqrt() {
+            Some(num) => Value::Number(num),
+            None => Value::Empty,
+        }
+        .into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "SQRT"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["16"],
+                expected_results: "4",
+            },
+            FunctionExample {
+                name: "larger",
+                arguments: vec!["121"],
+                expected_results: "11",
+            },
+            FunctionExample {
+                name: "neg",
+                arguments: vec!["-4"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "nan",
+                arguments: vec!["test"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct WidthBucket {}
+impl Operator for WidthBucket {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let operand = args.first();
+        let Some(operand) = operand.as_num() else {
+            return Value::Empty.into();
+        };
+        let low = args.get(1);
+        let Some(low) = low.as_num() else {
+            return Value::Empty.into();
+        };
+        let high = args.get(2);
+        let Some(high) = high.as_num() else {
+            return Value::Empty.into();
+        };
+        let Some(count) = args.get(3).as_usize() else {
+            return Value::Empty.into();
+        };
+        if count == 0 || low == high {
+            return Value::Empty.into();
+        }
+        let (offset, width) = if low < high {
+            if operand < low {
+                return 0.into();
+            }
+            if operand >= high {
+                return (count + 1).into();
+            }
+            (operand - low, high - low)
+        } else {
+            if operand > low {
+                return 0.into();
+            }
+            if operand <= high {
+                return (count + 1).into();
+            }
+            (low - operand, low - high)
+        };
+        let bucket = (offset * BigDecimal::from(count as u64) / width)
+            .with_scale_round(0, bigdecimal::RoundingMode::Floor);
+        Value::Number(bucket + BigDecimal::from(1)).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(4)
+    }
+    fn min_args(&self) -> usize {
+        4
+    }
+    fn name(&self) -> &str {
+        "WIDTH_BUCKET"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["125", "0", "600", "10"],
+                expected_results: "3",
+            },
+            FunctionExample {
+                name: "low_boundary",
+                arguments: vec!["0", "0", "600", "10"],
+                expected_results: "1",
+            },
+            FunctionExample {
+                name: "bucket_boundary",
+                arguments: vec!["60", "0", "600", "10"],
+                expected_results: "2",
+            },
+            FunctionExample {
+                name: "just_below_high",
+                arguments: vec!["599.99", "0", "600", "10"],
+                expected_results: "10",
+            },
+            FunctionExample {
+                name: "high_boundary",
+                arguments: vec!["600", "0", "600", "10"],
+                expected_results: "11",
+            },
+            FunctionExample {
+                name: "below_range",
+                arguments: vec!["-0.5", "0", "600", "10"],
+                expected_results: "0",
+            },
+            FunctionExample {
+                name: "above_range",
+                arguments: vec!["1000", "0", "600", "10"],
+                expected_results: "11",
+            },
+            FunctionExample {
+                name: "reversed_range",
+                arguments: vec!["125", "600", "0", "10"],
+                expected_results: "8",
+            },
+            FunctionExample {
+                name: "reversed_below_range",
+                arguments: vec!["700", "600", "0", "10"],
+                expected_results: "0",
+            },
+            FunctionExample {
+                name: "reversed_above_range",
+                arguments: vec!["0", "600", "0", "10"],
+                expected_results: "11",
+            },
+            FunctionExample {
+                name: "empty_range",
+                arguments: vec!["5", "10", "10", "10"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "zero_buckets",
+                arguments: vec!["5", "0", "10", "0"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "negative_buckets",
+                arguments: vec!["5", "0", "10", "-2"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "nan",
+                arguments: vec!["test", "0", "10", "2"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Pi {}
+impl Operator for Pi {
+    fn get<'a>(&'a self, _: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let pi = BigDecimal::from_str("3.1415926535897932384626433832795").unwrap();
+        Value::Number(pi).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(0)
+    }
+    fn min_args(&self) -> usize {
+        0
+    }
+    fn name(&self) -> &str {
+        "PI"
+    }
+}
+
+struct Degrees {}
+impl Operator for Degrees {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let num = args.first();
+        let Some(num) = num.as_f64() else {
+            return Value::Empty.into();
+        };
+
+        num.to_degrees().into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "DEGREES"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "zero",
+                arguments: vec!["0"],
+                expected_results: "0",
+            },
+            FunctionExample {
+                name: "pi",
+                arguments: vec!["3.1415926535897932384626433832795"],
+                expected_results: "180",
+            },
+            FunctionExample {
+                name: "nan",
+                arguments: vec!["test"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Radians {}
+impl Operator for Radians {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let num = args.first();
+        let Some(num) = num.as_f64() else {
+            return Value::Empty.into();
+        };
+
+        num.to_radians().into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "RADIANS"
+    }
+
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "zero",
+                arguments: vec!["0"],
+                expected_results: "0",
+            },
+            FunctionExample {
+                name: "nan",
+                arguments: vec!["test"],
+                expected_results: "",
+            },
+        ]
+    }
+}
+
+struct Exp {}
+impl Operator for Exp {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let num = args.first();
+        let Some(num) = num.as_num() else {
+            return Value::Empty.into();
+        };
+
+        Value::Number(num.exp()).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "EXP"
+    }
+}
+
+struct Ln {}
+impl Operator for Ln {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let num = args.first();
+        let Some(num) = num.as_f64() else {
+            return Value::Empty.into();
+        };
+        if num <= 0.0 {
+            return Value::Empty.into();
+        }
+
+        num.ln().into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "LN"
+    }
+}
+struct Log {}
+impl Operator for Log {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let (base, num) = if args.len() == 2 {
+            let base = args.first();
+            let Some(base) = base.as_f64() else {
+                return Value::Empty.into();
+            };
+            let num = args.get(1);
+            let Some(num) = num.as_f64() else {
+                return Value::Empty.into();
+            };
+            if base <= 0.0 {
+                return Value::Empty.into();
+            }
+            (base, num)
+        } else {
+            let num = args.first();
+            let Some(num) = num.as_f64() else {
+                return Value::Empty.into();
+            };
+            (10.0, num)
+        };
+        if num <= 0.0 {
+            return Value::Empty.into();
+        }
+
+        num.log(base).into()
+    }
+
+    fn max_args(&self) -> Option<usize> {
+        Some(2)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "LOG"
+    }
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["100"],
+                expected_results: "2",
+            },
+            FunctionExample {
+                name: "zero",
+                arguments: vec!["0"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "zero_oc",
-                arguments: vec!["abc def ghi", "[A-Z]+", "1", "0", "i"],
+                name: "not_a_num",
+                arguments: vec!["test"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "invalid_flags",
-                arguments: vec!["abc def ghi", "[A-Z]+", "1", "0", "2"],
+                name: "two_args",
+                arguments: vec!["2", "8"],
+                expected_results: "3",
+            },
+            FunctionExample {
+                name: "invalid_base",
+                arguments: vec!["0", "8"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "unknown_flags",
-                arguments: vec!["abc def ghi", "[A-Z]+", "1", "0", "1"],
+                name: "no_base",
+                arguments: vec!["", "8"],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct Reverse {}
-impl Operator for Reverse {
+struct Log2 {}
+impl Operator for Log2 {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let str = args.first();
-        let Some(str) = str.as_string() else {
+        let num = args.first();
+        let Some(num) = num.as_f64() else {
             return Value::Empty.into();
         };
-        Value::Str(str.chars().rev().collect()).into()
+        if num <= 0.0 {
+            return Value::Empty.into();
+        }
+
+        num.log2().into()
     }
 
     fn max_args(&self) -> Option<usize> {
@@ -2398,199 +5535,211 @@ impl Operator for Reverse {
         1
     }
     fn name(&self) -> &str {
-        "REVERSE"
+        "LOG2"
     }
-
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
                 name: "simple",
-                arguments: vec!["simple"],
-                expected_results: "elpmis",
+                arguments: vec!["16"],
+                expected_results: "4",
             },
             FunctionExample {
-                name: "nopre",
-                arguments: vec!["323"],
+                name: "zero",
+                arguments: vec!["0"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_a_num",
+                arguments: vec!["test"],
                 expected_results: "",
             },
         ]
     }
 }
-struct Round {}
-impl Operator for Round {
+struct Log10 {}
+impl Operator for Log10 {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
         let num = args.first();
-        let Some(num) = num.as_num() else {
+        let Some(num) = num.as_f64() else {
             return Value::Empty.into();
         };
-        let digit = args.get(1);
-        let digit = if digit.is_some() {
-            digit.as_i64()
-        } else {
-            Some(0)
-        };
-        let Some(digit) = digit else {
+        if num <= 0.0 {
             return Value::Empty.into();
-        };
+        }
 
-        Value::Number(num.with_scale_round(digit, bigdecimal::RoundingMode::HalfDown)).into()
+        num.log10().into()
     }
 
     fn max_args(&self) -> Option<usize> {
-        Some(2)
+        Some(1)
     }
     fn min_args(&self) -> usize {
         1
     }
     fn name(&self) -> &str {
-        "ROUND"
+        "LOG10"
     }
-
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
                 name: "simple",
-                arguments: vec!["-1.23"],
-                expected_results: "-1",
-            },
-            FunctionExample {
-                name: "larger",
-                arguments: vec!["43.123"],
-                expected_results: "43",
-            },
-            FunctionExample {
-                name: "go_up",
-                arguments: vec!["43.6123"],
-                expected_results: "44",
-            },
-            FunctionExample {
-                name: "with_arg",
-                arguments: vec![".1234567890123456789012345678901234567890", "35"],
-                expected_results: "0.12345678901234567890123456789012346",
-            },
-            FunctionExample {
-                name: "with_negative_arg",
-                arguments: vec!["23.298", "-1"],
-                expected_results: "20",
+                arguments: vec!["100"],
+                expected_results: "2",
             },
             FunctionExample {
-                name: "nan1",
-                arguments: vec!["test"],
+                name: "zero",
+                arguments: vec!["0"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "nan2",
-                arguments: vec!["1", "test"],
+                name: "not_a_num",
+                arguments: vec!["test"],
                 expected_results: "",
             },
         ]
     }
 }
-struct Sqrt {}
-impl Operator for Sqrt {
+
+struct Power {}
+impl Operator for Power {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let num = args.first();
-        let Some(num) = num.as_num() else {
+        let base = args.first();
+        let Some(base) = base.as_f64() else {
             return Value::Empty.into();
         };
-        match num.sqrt() {
-            Some(num) => Value::Number(num),
-            None => Value::Empty,
+        let num = args.get(1);
+        let Some(num) = num.as_f64() else {
+            return Value::Empty.into();
+        };
+        if base <= 0.0 {
+            return Value::Empty.into();
         }
-        .into()
+
+        base.powf(num).into()
     }
 
     fn max_args(&self) -> Option<usize> {
-        Some(1)
+        Some(2)
     }
     fn min_args(&self) -> usize {
-        1
+        2
     }
     fn name(&self) -> &str {
-        "SQRT"
+        "POWER"
     }
-
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
                 name: "simple",
-                arguments: vec!["16"],
-                expected_results: "4",
+                arguments: vec!["2", "3"],
+                expected_results: "8",
             },
             FunctionExample {
-                name: "larger",
-                arguments: vec!["121"],
-                expected_results: "11",
+                name: "negative",
+                arguments: vec!["2", "-2"],
+                expected_results: "0.25",
             },
             FunctionExample {
-                name: "neg",
-                arguments: vec!["-4"],
+                name: "not_a_num_base",
+                arguments: vec!["a", "2"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "nan",
-                arguments: vec!["test"],
+                name: "not_a_num_exp",
+                arguments: vec!["2", "a"],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct Pi {}
-impl Operator for Pi {
-    fn get<'a>(&'a self, _: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let pi = BigDecimal::from_str("3.1415926535897932384626433832795").unwrap();
-        Value::Number(pi).into()
+struct Mod {}
+impl Operator for Mod {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let (Some(num), Some(divisor)) = (args.first(), args.get(1)) else {
+            return Value::Empty.into();
+        };
+        (num.deref() % divisor.deref()).into()
     }
 
     fn max_args(&self) -> Option<usize> {
-        Some(0)
+        Some(2)
     }
     fn min_args(&self) -> usize {
-        0
+        2
     }
     fn name(&self) -> &str {
-        "PI"
+        "MOD"
+    }
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
+            FunctionExample {
+                name: "simple",
+                arguments: vec!["7", "3"],
+                expected_results: "1",
+            },
+            FunctionExample {
+                name: "negative",
+                arguments: vec!["-7", "3"],
+                expected_results: "-1",
+            },
+            FunctionExample {
+                name: "decimal",
+                arguments: vec!["7.5", "2"],
+                expected_results: "1.5",
+            },
+            FunctionExample {
+                name: "by_zero",
+                arguments: vec!["7", "0"],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "by_null",
+                arguments: vec!["7", ""],
+                expected_results: "",
+            },
+            FunctionExample {
+                name: "not_a_num",
+                arguments: vec!["a", "2"],
+                expected_results: "",
+            },
+        ]
     }
 }
 
-struct Exp {}
-impl Operator for Exp {
-    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let num = args.first();
-        let Some(num) = num.as_num() else {
-            return Value::Empty.into();
-        };
-
-        Value::Number(num.exp()).into()
-    }
+fn integer_pair(args: &[SmartReference<'_, Value>]) -> Option<(u64, u64)> {
+    let mut values = args.iter().map(|arg| {
+        arg.as_num()
+            .filter(|num| num.is_integer())
+            .and_then(|num| num.to_i64())
+            .map(|num| num.unsigned_abs())
+    });
+    Some((values.next()??, values.next()??))
+}
 
-    fn max_args(&self) -> Option<usize> {
-        Some(1)
-    }
-    fn min_args(&self) -> usize {
-        1
-    }
-    fn name(&self) -> &str {
-        "EXP"
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
     }
+    a
 }
 
-struct Ln {}
-impl Operator for Ln {
+/// A single field of a date or a timestamp (like `YEAR(date)`), same as `EXTRACT(field FROM date)`.
+struct DatePart {
+    name: &'static str,
+    field: Field,
+}
+impl Operator for DatePart {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let num = args.first();
-        let Some(num) = num.as_f64() else {
+        let Some(value) = args.first() else {
             return Value::Empty.into();
         };
-        if num <= 0.0 {
-            return Value::Empty.into();
-        }
-
-        num.ln().into()
+        extract_field(&self.field, value.deref())
     }
 
     fn max_args(&self) -> Option<usize> {
@@ -2600,99 +5749,127 @@ impl Operator for Ln {
         1
     }
     fn name(&self) -> &str {
-        "LN"
+        self.name
     }
-}
-struct Log {}
-impl Operator for Log {
-    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let (base, num) = if args.len() == 2 {
-            let base = args.first();
-            let Some(base) = base.as_f64() else {
-                return Value::Empty.into();
-            };
-            let num = args.get(1);
-            let Some(num) = num.as_f64() else {
-                return Value::Empty.into();
-            };
-            if base <= 0.0 {
-                return Value::Empty.into();
-            }
-            (base, num)
-        } else {
-            let num = args.first();
-            let Some(num) = num.as_f64() else {
-                return Value::Empty.into();
-            };
-            (10.0, num)
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        let (timestamp, date) = match self.field {
+            Field::Year => ("2024", "2023"),
+            Field::Month => ("1", "12"),
+            Field::Day => ("13", "31"),
+            Field::Hour => ("9", "0"),
+            Field::Minute => ("59", "0"),
+            Field::Quarter => ("1", "4"),
+            Field::DayOfYear => ("13", "365"),
+            _ => ("32", "0"),
         };
-        if num <= 0.0 {
-            return Value::Empty.into();
-        }
-
-        num.log(base).into()
+        vec![
+            FunctionExample {
+                name: "timestamp",
+                arguments: vec!["2024-01-13 09:59:32"],
+                expected_results: timestamp,
+            },
+            FunctionExample {
+                name: "date",
+                arguments: vec!["2023-12-31"],
+                expected_results: date,
+            },
+            FunctionExample {
+                name: "not_a_date",
+                arguments: vec!["test"],
+                expected_results: "",
+            },
+        ]
     }
+}
 
+struct DayOfWeek {}
+impl Operator for DayOfWeek {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let Some(time) = as_timestamp(args.first()) else {
+            return Value::Empty.into();
+        };
+        let day = time.weekday().number_from_sunday();
+        Value::Number(day.into()).into()
+    }
     fn max_args(&self) -> Option<usize> {
-        Some(2)
+        Some(1)
     }
     fn min_args(&self) -> usize {
         1
     }
     fn name(&self) -> &str {
-        "LOG"
+        "DAYOFWEEK"
     }
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "simple",
-                arguments: vec!["100"],
-                expected_results: "2",
+                name: "sunday",
+                arguments: vec!["2024-01-14"],
+                expected_results: "1",
             },
             FunctionExample {
-                name: "zero",
-                arguments: vec!["0"],
-                expected_results: "",
+                name: "saturday",
+                arguments: vec!["2024-01-13 09:59:32"],
+                expected_results: "7",
             },
             FunctionExample {
-                name: "not_a_num",
+                name: "not_a_date",
                 arguments: vec!["test"],
                 expected_results: "",
             },
+        ]
+    }
+}
+
+struct DayName {}
+impl Operator for DayName {
+    fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
+        let Some(time) = as_timestamp(args.first()) else {
+            return Value::Empty.into();
+        };
+        Value::Str(time.format("%A").to_string()).into()
+    }
+    fn max_args(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn min_args(&self) -> usize {
+        1
+    }
+    fn name(&self) -> &str {
+        "DAYNAME"
+    }
+    #[cfg(test)]
+    fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
+        vec![
             FunctionExample {
-                name: "two_args",
-                arguments: vec!["2", "8"],
-                expected_results: "3",
+                name: "date",
+                arguments: vec!["2024-01-14"],
+                expected_results: "Sunday",
             },
             FunctionExample {
-                name: "invalid_base",
-                arguments: vec!["0", "8"],
-                expected_results: "",
+                name: "timestamp",
+                arguments: vec!["2024-08-28 10:43:32"],
+                expected_results: "Wednesday",
             },
             FunctionExample {
-                name: "no_base",
-                arguments: vec!["", "8"],
+                name: "not_a_date",
+                arguments: vec!["12"],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct Log2 {}
-impl Operator for Log2 {
+struct MonthName {}
+impl Operator for MonthName {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let num = args.first();
-        let Some(num) = num.as_f64() else {
+        let Some(time) = as_timestamp(args.first()) else {
             return Value::Empty.into();
         };
-        if num <= 0.0 {
-            return Value::Empty.into();
-        }
-
-        num.log2().into()
+        Value::Str(time.format("%B").to_string()).into()
     }
-
     fn max_args(&self) -> Option<usize> {
         Some(1)
     }
@@ -2700,90 +5877,94 @@ impl Operator for Log2 {
         1
     }
     fn name(&self) -> &str {
-        "LOG2"
+        "MONTHNAME"
     }
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
-                name: "simple",
-                arguments: vec!["16"],
-                expected_results: "4",
+                name: "date",
+                arguments: vec!["2024-01-13"],
+                expected_results: "January",
             },
             FunctionExample {
-                name: "zero",
-                arguments: vec!["0"],
-                expected_results: "",
+                name: "timestamp",
+                arguments: vec!["2024-08-28 10:43:32"],
+                expected_results: "August",
             },
             FunctionExample {
-                name: "not_a_num",
+                name: "not_a_date",
                 arguments: vec!["test"],
                 expected_results: "",
             },
         ]
     }
 }
-struct Log10 {}
-impl Operator for Log10 {
+
+struct Gcd {}
+impl Operator for Gcd {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let num = args.first();
-        let Some(num) = num.as_f64() else {
+        let Some((a, b)) = integer_pair(args) else {
             return Value::Empty.into();
         };
-        if num <= 0.0 {
-            return Value::Empty.into();
-        }
-
-        num.log10().into()
+        Value::Number(gcd(a, b).into()).into()
     }
 
     fn max_args(&self) -> Option<usize> {
-        Some(1)
+        Some(2)
     }
     fn min_args(&self) -> usize {
-        1
+        2
     }
     fn name(&self) -> &str {
-        "LOG10"
+        "GCD"
     }
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
                 name: "simple",
-                arguments: vec!["100"],
-                expected_results: "2",
+                arguments: vec!["12", "18"],
+                expected_results: "6",
             },
             FunctionExample {
-                name: "zero",
-                arguments: vec!["0"],
+                name: "negative",
+                arguments: vec!["-12", "18"],
+                expected_results: "6",
+            },
+            FunctionExample {
+                name: "with_zero",
+                arguments: vec!["0", "5"],
+                expected_results: "5",
+            },
+            FunctionExample {
+                name: "decimal",
+                arguments: vec!["7.5", "2"],
                 expected_results: "",
             },
             FunctionExample {
                 name: "not_a_num",
-                arguments: vec!["test"],
+                arguments: vec!["a", "2"],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct Power {}
-impl Operator for Power {
+struct Lcm {}
+impl Operator for Lcm {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let base = args.first();
-        let Some(base) = base.as_f64() else {
-            return Value::Empty.into();
-        };
-        let num = args.get(1);
-        let Some(num) = num.as_f64() else {
+        let Some((a, b)) = integer_pair(args) else {
             return Value::Empty.into();
         };
-        if base <= 0.0 {
-            return Value::Empty.into();
+        if a == 0 || b == 0 {
+            return Value::Number(0.into()).into();
         }
-
-        base.powf(num).into()
+        match (a / gcd(a, b)).checked_mul(b) {
+            Some(lcm) => Value::Number(lcm.into()),
+            None => Value::Empty,
+        }
+        .into()
     }
 
     fn max_args(&self) -> Option<usize> {
@@ -2793,39 +5974,46 @@ impl Operator for Power {
         2
     }
     fn name(&self) -> &str {
-        "POWER"
+        "LCM"
     }
     #[cfg(test)]
     fn examples<'a>(&'a self) -> Vec<FunctionExample<'a>> {
         vec![
             FunctionExample {
                 name: "simple",
-                arguments: vec!["2", "3"],
-                expected_results: "8",
+                arguments: vec!["4", "6"],
+                expected_results: "12",
             },
             FunctionExample {
                 name: "negative",
-                arguments: vec!["2", "-2"],
-                expected_results: "0.25",
+                arguments: vec!["-4", "6"],
+                expected_results: "12",
             },
             FunctionExample {
-                name: "not_a_num_base",
-                arguments: vec!["a", "2"],
+                name: "with_zero",
+                arguments: vec!["0", "5"],
+                expected_results: "0",
+            },
+            FunctionExample {
+                name: "decimal",
+                arguments: vec!["4", "1.5"],
                 expected_results: "",
             },
             FunctionExample {
-                name: "not_a_num_exp",
-                arguments: vec!["2", "a"],
+                name: "not_a_num",
+                arguments: vec!["4", "a"],
                 expected_results: "",
             },
         ]
     }
 }
 
-struct Random {}
+struct Random {
+    rng: Rc<RefCell<StdRng>>,
+}
 impl Operator for Random {
     fn get<'a>(&'a self, args: &[SmartReference<'a, Value>]) -> SmartReference<'a, Value> {
-        let rnd: f64 = rand::random();
+        let rnd: f64 = self.rng.borrow_mut().random();
         if args.is_empty() {
             rnd.into()
         } else {
@@ -2854,9 +6042,10 @@ impl Operator for Random {
 #[cfg(test)]
 mod tests_functions {
     use std::fs::{self, OpenOptions};
+    use std::ops::Deref;
 
     use bigdecimal::ToPrimitive;
-    use chrono::{TimeDelta, Utc};
+    use chrono::{NaiveDate, TimeDelta, Utc};
     use itertools::Itertools;
     use sqlparser::{
         ast::{
@@ -2871,6 +6060,7 @@ mod tests_functions {
         dialect::FilesDialect,
         engine::Engine,
         error::CvsSqlError,
+        extract_time::Field,
         projections::SingleConvert,
         result_set_metadata::{Metadata, SimpleResultSetMetadata},
         results::Column,
@@ -2879,10 +6069,14 @@ mod tests_functions {
     use std::io::Write;
 
     use super::{
-        Abs, Ascii, Chr, Coalece, Concat, ConcatWs, CurrentDate, Exp, Format, Greatest, If, Least,
-        Left, Length, Ln, Log, Log2, Log10, Lower, Lpad, Ltrim, Now, NullIf, Operator, Pi,
-        Position, Power, Random, RegexLike, RegexReplace, RegexSubstring, Repeat, Replace, Reverse,
-        Right, Round, Rpad, Rtrim, Sqrt, ToTimestamp, Upper, User,
+        Abs, Ascii, Bin, Cbrt, Chr, Coalece, Concat, ConcatWs, CurrentDate, DateAdd, DateDiff,
+        DatePart, DateSub, DayName, DayOfWeek, Decode, Degrees, Exp, Format, FormatBytes,
+        FromBase64, Gcd, Greatest, Hex, If, InitCap, Instr, Lcm, Least, Left, Length, Ln, Log,
+        Log2, Log10, Lower, Lpad, Ltrim, Md5, Mod, MonthName, Now, NullIf, Oct, Operator, Pi,
+        Position, Power, Radians, Random, RegexLike, RegexReplace, RegexSubstring, Repeat, Replace,
+        Reverse, Right, Round, Rpad, Rtrim, Sha1, Sha256, Sign, Space, Sqrt, StrToDate,
+        SubstringIndex, TimestampDiff, ToBase64, ToTimestamp, Translate, Truncate, Unhex,
+        UnixTimestamp, Upper, User, WidthBucket,
     };
 
     fn test_func(operator: &impl Operator) -> Result<(), CvsSqlError> {
@@ -2991,11 +6185,29 @@ mod tests_functions {
         test_func(&Length {})
     }
 
+    #[test]
+    fn test_decode() -> Result<(), CvsSqlError> {
+        test_func(&Decode {})
+    }
+
     #[test]
     fn test_coalece() -> Result<(), CvsSqlError> {
         test_func(&Coalece {})
     }
 
+    #[test]
+    fn coalece_keeps_the_value_type() {
+        let date = Value::Date(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap());
+        let number = Value::Number(12.into());
+        let coalece = Coalece {};
+
+        let results = coalece.get(&[Value::Empty.into(), date.clone().into()]);
+        assert_eq!(results.deref(), &date);
+
+        let results = coalece.get(&[Value::Empty.into(), number.clone().into(), date.into()]);
+        assert_eq!(results.deref(), &number);
+    }
+
     #[test]
     fn test_concat() -> Result<(), CvsSqlError> {
         test_func(&Concat {})
@@ -3047,11 +6259,136 @@ mod tests_functions {
         test_func(&Format {})
     }
 
+    #[test]
+    fn test_str_to_date() -> Result<(), CvsSqlError> {
+        test_func(&StrToDate {})
+    }
+
+    #[test]
+    fn test_unix_timestamp() -> Result<(), CvsSqlError> {
+        test_func(&UnixTimestamp {})
+    }
+
+    #[test]
+    fn test_unix_timestamp_without_arguments() -> Result<(), CvsSqlError> {
+        let now = Utc::now().timestamp();
+        let Value::Number(results) = UnixTimestamp {}.get(&[]).deref().clone() else {
+            panic!("UNIX_TIMESTAMP() is not a number");
+        };
+        assert!((now..now + 2).contains(&results.to_i64().unwrap()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_init_cap() -> Result<(), CvsSqlError> {
+        test_func(&InitCap {})
+    }
+
+    #[test]
+    fn test_instr() -> Result<(), CvsSqlError> {
+        test_func(&Instr {})
+    }
+
+    #[test]
+    fn test_space() -> Result<(), CvsSqlError> {
+        test_func(&Space {})
+    }
+
+    #[test]
+    fn test_md5() -> Result<(), CvsSqlError> {
+        test_func(&Md5 {})
+    }
+
+    #[test]
+    fn test_sha1() -> Result<(), CvsSqlError> {
+        test_func(&Sha1 {})
+    }
+
+    #[test]
+    fn test_sha256() -> Result<(), CvsSqlError> {
+        test_func(&Sha256 {})
+    }
+
+    #[test]
+    fn test_to_base64() -> Result<(), CvsSqlError> {
+        test_func(&ToBase64 {})
+    }
+
+    #[test]
+    fn test_from_base64() -> Result<(), CvsSqlError> {
+        test_func(&FromBase64 {})
+    }
+
+    #[test]
+    fn test_hex() -> Result<(), CvsSqlError> {
+        test_func(&Hex {})
+    }
+
+    #[test]
+    fn test_numeric_looking_radix_strings() -> Result<(), CvsSqlError> {
+        for (operator, number, expected) in [
+            (&Hex {} as &dyn Operator, "0", "0"),
+            (&Bin {}, "5", "101"),
+            (&Bin {}, "0", "0"),
+            (&Oct {}, "8", "10"),
+            (&Oct {}, "511", "777"),
+        ] {
+            let results = operator.get(&[Value::Number(number.parse().unwrap()).into()]);
+            assert_eq!(results.deref(), &Value::Str(expected.into()));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_unhex() -> Result<(), CvsSqlError> {
+        test_func(&Unhex {})
+    }
+
+    #[test]
+    fn test_bin() -> Result<(), CvsSqlError> {
+        test_func(&Bin {})
+    }
+
+    #[test]
+    fn test_oct() -> Result<(), CvsSqlError> {
+        test_func(&Oct {})
+    }
+
+    #[test]
+    fn test_translate() -> Result<(), CvsSqlError> {
+        test_func(&Translate {})
+    }
+
+    #[test]
+    fn test_substring_index() -> Result<(), CvsSqlError> {
+        test_func(&SubstringIndex {})
+    }
+
     #[test]
     fn test_to_timestamp() -> Result<(), CvsSqlError> {
         test_func(&ToTimestamp {})
     }
 
+    #[test]
+    fn test_date_diff() -> Result<(), CvsSqlError> {
+        test_func(&DateDiff {})
+    }
+
+    #[test]
+    fn test_timestamp_diff() -> Result<(), CvsSqlError> {
+        test_func(&TimestampDiff {})
+    }
+
+    #[test]
+    fn test_date_add() -> Result<(), CvsSqlError> {
+        test_func(&DateAdd {})
+    }
+
+    #[test]
+    fn test_date_sub() -> Result<(), CvsSqlError> {
+        test_func(&DateSub {})
+    }
+
     #[test]
     fn test_greatest() -> Result<(), CvsSqlError> {
         test_func(&Greatest {})
@@ -3123,6 +6460,29 @@ mod tests_functions {
         })
     }
 
+    #[test]
+    fn test_degrees() -> Result<(), CvsSqlError> {
+        test_func(&Degrees {})?;
+        test_with_details(&Degrees {}, "one", &["1"], |r| match r {
+            Some(Value::Number(num)) => {
+                num.to_f64().unwrap() > 57.2957 && num.to_f64().unwrap() < 57.2958
+            }
+            _ => false,
+        })
+    }
+
+    #[test]
+    fn test_radians() -> Result<(), CvsSqlError> {
+        test_func(&Radians {})?;
+        test_with_details(&Radians {}, "half_circle", &["180"], |r| match r {
+            Some(Value::Number(num)) => {
+                num.to_f64().unwrap() > std::f64::consts::PI - 0.001
+                    && num.to_f64().unwrap() < std::f64::consts::PI + 0.001
+            }
+            _ => false,
+        })
+    }
+
     #[test]
     fn test_ln() -> Result<(), CvsSqlError> {
         test_with_details(&Ln {}, "ln", &["10"], |r| match r {
@@ -3208,16 +6568,29 @@ mod tests_functions {
     }
     #[test]
     fn test_rand() -> Result<(), CvsSqlError> {
-        test_with_details(&Random {}, "no_args", &[], |r| match r {
+        let random = Random {
+            rng: std::rc::Rc::new(std::cell::RefCell::new(rand::make_rng())),
+        };
+        test_with_details(&random, "no_args", &[], |r| match r {
             Some(Value::Number(num)) => num.to_f64().unwrap() > 0.0 && num.to_f64().unwrap() < 1.0,
             _ => false,
         })?;
-        test_with_details(&Random {}, "one_args", &["20"], |r| match r {
+        test_with_details(&random, "one_args", &["20"], |r| match r {
             Some(Value::Number(num)) => num.to_usize().unwrap() < 20,
             _ => false,
         })?;
-        test_with_details(&Random {}, "nan", &["t"], |r| r == Some(&Value::Empty))?;
-        test_with_details(&Random {}, "neg", &["-10"], |r| r == Some(&Value::Empty))
+        test_with_details(&random, "nan", &["t"], |r| r == Some(&Value::Empty))?;
+        test_with_details(&random, "neg", &["-10"], |r| r == Some(&Value::Empty))
+    }
+
+    #[test]
+    fn test_format_bytes() -> Result<(), CvsSqlError> {
+        test_func(&FormatBytes {})
+    }
+
+    #[test]
+    fn test_mod() -> Result<(), CvsSqlError> {
+        test_func(&Mod {})
     }
 
     #[test]
@@ -3225,11 +6598,73 @@ mod tests_functions {
         test_func(&Round {})
     }
 
+    #[test]
+    fn test_truncate() -> Result<(), CvsSqlError> {
+        test_func(&Truncate {})
+    }
+
+    #[test]
+    fn test_sign() -> Result<(), CvsSqlError> {
+        test_func(&Sign {})
+    }
+
+    #[test]
+    fn test_cbrt() -> Result<(), CvsSqlError> {
+        test_func(&Cbrt {})
+    }
+
+    #[test]
+    fn test_day_of_week() -> Result<(), CvsSqlError> {
+        test_func(&DayOfWeek {})
+    }
+
+    #[test]
+    fn test_day_name() -> Result<(), CvsSqlError> {
+        test_func(&DayName {})
+    }
+
+    #[test]
+    fn test_month_name() -> Result<(), CvsSqlError> {
+        test_func(&MonthName {})
+    }
+
+    #[test]
+    fn test_date_parts() -> Result<(), CvsSqlError> {
+        for (name, field) in [
+            ("YEAR", Field::Year),
+            ("MONTH", Field::Month),
+            ("DAY", Field::Day),
+            ("HOUR", Field::Hour),
+            ("MINUTE", Field::Minute),
+            ("SECOND", Field::Second),
+            ("QUARTER", Field::Quarter),
+            ("DAYOFYEAR", Field::DayOfYear),
+        ] {
+            test_func(&DatePart { name, field })?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_gcd() -> Result<(), CvsSqlError> {
+        test_func(&Gcd {})
+    }
+
+    #[test]
+    fn test_lcm() -> Result<(), CvsSqlError> {
+        test_func(&Lcm {})
+    }
+
     #[test]
     fn test_sqrt() -> Result<(), CvsSqlError> {
         test_func(&Sqrt {})
     }
 
+    #[test]
+    fn test_width_bucket() -> Result<(), CvsSqlError> {
+        test_func(&WidthBucket {})
+    }
+
     fn test_unsupported(change: fn(&mut Function)) -> Result<(), CvsSqlError> {
         let args = Args {
             write_mode: true,
@@ -0,0 +1,307 @@
+use std::fs;
+use std::panic::resume_unwind;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::thread;
+
+use csv::{ReaderBuilder, StringRecord};
+use sqlparser::ast::ObjectName;
+
+use crate::engine::Engine;
+use crate::error::CvsSqlError;
+use crate::file_results::{add_header, is_blank, quoted_fields, read_row};
+use crate::result_set_metadata::SimpleResultSetMetadata;
+use crate::results::{Name, ResultSet};
+use crate::results_data::ResultsData;
+
+struct Job {
+    index: usize,
+    path: PathBuf,
+    builder: ReaderBuilder,
+    delimiter: u8,
+}
+
+#[derive(Clone, Copy)]
+struct ReadOptions {
+    has_headers: bool,
+    empty_as_string: bool,
+    rows_to_read: usize,
+}
+
+struct FileRecords {
+    header: Option<StringRecord>,
+    records: Vec<(StringRecord, Vec<bool>)>,
+}
+
+/// A table made of all the CSV files that match a name with wildcards (like `"sales_*"`), one
+/// after the other in file name order. The files are read by `--threads` threads.
+pub(crate) fn read_glob_table(
+    engine: &Engine,
+    name: &ObjectName,
+) -> Result<Option<ResultSet>, CvsSqlError> {
+    let parts: Vec<String> = name
+        .0
+        .iter()
+        .map(|part| match part.as_ident() {
+            Some(ident) => ident.value.clone(),
+            None => part.to_string(),
+        })
+        .collect();
+    let Some((pattern, dirs)) = parts.split_last() else {
+        return Ok(None);
+    };
+    if !pattern.contains(['*', '?']) {
+        return Ok(None);
+    }
+    let home = engine.home();
+    let dir = dirs.iter().fold(home.clone(), |dir, part| dir.join(part));
+    let pattern: Vec<char> = format!("{pattern}.csv").chars().collect();
+    let mut paths = vec![];
+    if dir.is_dir() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let file_name: Option<Vec<char>> = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.chars().collect());
+            if path.is_file() && file_name.is_some_and(|name| matches(&pattern, &name)) {
+                paths.push(path);
+            }
+        }
+    }
+    let result_name: Name = parts.into();
+    if paths.is_empty() {
+        return Err(CvsSqlError::TableNotExists(result_name.full_name()));
+    }
+    paths.sort();
+
+    let mut metadata = SimpleResultSetMetadata::new(Some(result_name));
+    let mut column_types = vec![];
+    let mut first_header: Option<StringRecord> = None;
+    let mut data = vec![];
+    for (path, file) in read_files(engine, paths)? {
+        if let Some(header) = file.header {
+            match &first_header {
+                None => {
+                    add_header(engine, &mut metadata, &mut column_types, &header);
+                    first_header = Some(header);
+                }
+                Some(first) if *first != header => {
+                    return Err(CvsSqlError::TableFileError(
+                        display_path(&path, &home),
+                        "the columns are not the same as in the first file".into(),
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+        for (record, quoted) in &file.records {
+            data.push(read_row(
+                engine,
+                &mut metadata,
+                &mut column_types,
+                record,
+                quoted,
+            )?);
+        }
+    }
+    let metadata = Rc::new(metadata.build());
+    let data = ResultsData::new(data);
+    Ok(Some(ResultSet { metadata, data }))
+}
+
+/// Only the reading of the records is done in parallel, the values are parsed later by the engine.
+fn read_files(
+    engine: &Engine,
+    paths: Vec<PathBuf>,
+) -> Result<Vec<(PathBuf, FileRecords)>, CvsSqlError> {
+    let options = ReadOptions {
+        has_headers: engine.first_line_as_name,
+        empty_as_string: engine.empty_as_string && !engine.headers_only,
+        rows_to_read: match (engine.headers_only, engine.first_line_as_name) {
+            (false, _) => usize::MAX,
+            (true, true) => 0,
+            (true, false) => 1,
+        },
+    };
+    let threads = engine.threads.clamp(1, paths.len().max(1));
+    let mut queues: Vec<Vec<Job>> = (0..threads).map(|_| vec![]).collect();
+    for (index, path) in paths.into_iter().enumerate() {
+        let mut builder = engine.csv_reader(&path);
+        builder.has_headers(options.has_headers);
+        let delimiter = engine.delimiter(&path);
+        queues[index % threads].push(Job {
+            index,
+            path,
+            builder,
+            delimiter,
+        });
+    }
+
+    let mut results = thread::scope(|scope| {
+        let handles: Vec<_> = queues
+            .into_iter()
+            .map(|queue| {
+                scope.spawn(move || {
+                    queue
+                        .into_iter()
+                        .map(|job| {
+                            let records = read_records(&job, options);
+                            (job.index, job.path, records)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|err| resume_unwind(err)))
+            .collect::<Vec<_>>()
+    });
+    results.sort_by_key(|(index, _, _)| *index);
+
+    let home = engine.home();
+    results
+        .into_iter()
+        .map(|(_, path, records)| match records {
+            Ok(records) => Ok((path, records)),
+            Err(err) => Err(CvsSqlError::TableFileError(
+                display_path(&path, &home),
+                err.to_string(),
+            )),
+        })
+        .collect()
+}
+
+fn read_records(job: &Job, options: ReadOptions) -> Result<FileRecords, CvsSqlError> {
+    let raw = fs::read(&job.path)?;
+    let mut reader = job.builder.from_reader(raw.as_slice());
+    let header = if options.has_headers {
+        Some(reader.headers()?.clone())
+    } else {
+        None
+    };
+    let mut records = vec![];
+    for record in reader.records().take(options.rows_to_read) {
+        let record = record?;
+        if is_blank(&record) {
+            continue;
+        }
+        let quoted = match record.position() {
+            Some(position) if options.empty_as_string => {
+                quoted_fields(&raw[position.byte() as usize..], job.delimiter)
+            }
+            _ => vec![],
+        };
+        records.push((record, quoted));
+    }
+    Ok(FileRecords { header, records })
+}
+
+fn display_path(path: &Path, home: &Path) -> String {
+    path.strip_prefix(home)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// A file name pattern, where `*` is any number of characters and `?` is a single one.
+fn matches(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, None) => true,
+        (Some(('*', rest)), _) => {
+            matches(rest, name) || (!name.is_empty() && matches(pattern, &name[1..]))
+        }
+        (Some(('?', rest)), Some((_, name))) => matches(rest, name),
+        (Some((expected, rest)), Some((actual, name))) => expected == actual && matches(rest, name),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::{args::Args, engine::Engine, error::CvsSqlError, value::Value};
+
+    fn rows(engine: &Engine, sql: &str) -> Result<Vec<Vec<Value>>, CvsSqlError> {
+        let results = engine.execute_commands(sql)?.remove(0).results;
+        Ok(results
+            .data
+            .iter()
+            .map(|row| results.columns().map(|col| row.get(&col).clone()).collect())
+            .collect())
+    }
+
+    #[test]
+    fn glob_table_is_the_same_with_any_number_of_threads() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        let dir = working_dir.path().join("daily");
+        fs::create_dir_all(&dir)?;
+        for day in (1..=12).rev() {
+            let mut content = String::from("day,amount\n");
+            for amount in 0..day {
+                content.push_str(&format!("{day},{amount}\n"));
+            }
+            fs::write(dir.join(format!("sales_{day:02}.csv")), content)?;
+        }
+        fs::write(dir.join("other.csv"), "day,amount\n100,100\n")?;
+
+        let sql = "SELECT * FROM daily.\"sales_*\"";
+        let mut results = vec![];
+        for threads in [1, 3, 16] {
+            let args = Args {
+                home: Some(working_dir.path().to_path_buf()),
+                threads,
+                ..Args::default()
+            };
+            let engine = Engine::try_from(&args)?;
+            results.push(rows(&engine, sql)?);
+        }
+
+        assert_eq!(results[0].len(), (1..=12).sum::<usize>());
+        assert_eq!(results[0].first().unwrap()[0], Value::Number(1.into()));
+        assert_eq!(results[0].last().unwrap()[0], Value::Number(12.into()));
+        assert_eq!(results[0], results[1]);
+        assert_eq!(results[0], results[2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn glob_table_errors_name_the_file() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        fs::write(working_dir.path().join("part_1.csv"), "a,b\n1,2\n")?;
+        fs::write(working_dir.path().join("part_2.csv"), b"a,b\n\xff\xfe,3\n")?;
+        fs::write(working_dir.path().join("other_1.csv"), "a,b\n1,2\n")?;
+        fs::write(working_dir.path().join("other_2.csv"), "a,c\n1,2\n")?;
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            threads: 2,
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let err = engine
+            .execute_commands("SELECT * FROM \"part_?\"")
+            .err()
+            .unwrap();
+        assert!(matches!(err, CvsSqlError::TableFileError(ref path, _) if path == "part_2.csv"));
+
+        let err = engine
+            .execute_commands("SELECT * FROM \"other_*\"")
+            .err()
+            .unwrap();
+        assert!(matches!(err, CvsSqlError::TableFileError(ref path, _) if path == "other_2.csv"));
+
+        let err = engine
+            .execute_commands("SELECT * FROM \"nothing_*\"")
+            .err()
+            .unwrap();
+        assert!(matches!(err, CvsSqlError::TableNotExists(_)));
+
+        Ok(())
+    }
+}
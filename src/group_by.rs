@@ -1,11 +1,12 @@
 use std::{collections::HashMap, rc::Rc};
 
-use sqlparser::ast::Expr;
+use itertools::Itertools;
+use sqlparser::ast::{Expr, GroupByWithModifier};
 
 use crate::{
     engine::Engine,
     error::CvsSqlError,
-    projections::SingleConvert,
+    projections::{Projection, SingleConvert},
     result_set_metadata::{Metadata, SimpleResultSetMetadata},
     results::ResultSet,
     results_data::DataRow,
@@ -38,14 +39,16 @@ impl From<ResultSet> for GroupedResultSet {
 pub fn group_by(
     engine: &Engine,
     group_by: &[Expr],
+    modifiers: &[GroupByWithModifier],
     results: ResultSet,
 ) -> Result<GroupedResultSet, CvsSqlError> {
     if group_by.is_empty() {
         return Ok(results.into());
     }
+    let (keys, sets) = grouping_sets(group_by, modifiers)?;
     let mut projections = Vec::new();
 
-    for expr in group_by {
+    for expr in keys {
         let item = expr.convert_single(&results.metadata, engine)?;
         projections.push(item);
     }
@@ -55,36 +58,162 @@ pub fn group_by(
     }
     let metadata = Metadata::Simple(metadata);
 
+    let data = results.data.into_iter().map(|data| GroupRow {
+        data,
+        group_rows: vec![],
+    });
+    let rows = match sets.as_slice() {
+        [set] => group_rows(&projections, set, data),
+        _ => {
+            let data: Vec<_> = data.collect();
+            let mut rows = vec![];
+            for set in &sets {
+                let data = data.iter().map(|row| GroupRow {
+                    data: row.data.clone(),
+                    group_rows: vec![],
+                });
+                rows.extend(group_rows(&projections, set, data));
+            }
+            rows
+        }
+    };
+    let metadata = Rc::new(Metadata::Grouped {
+        parent: results.metadata.clone(),
+        this: Box::new(metadata),
+    });
+
+    Ok(GroupedResultSet { rows, metadata })
+}
+
+fn group_rows(
+    projections: &[Box<dyn Projection>],
+    set: &[usize],
+    data: impl Iterator<Item = GroupRow>,
+) -> Vec<GroupRow> {
     let mut groups: HashMap<Vec<Value>, Vec<GroupRow>> = HashMap::new();
-    for row in results.data.into_iter() {
-        let row = GroupRow {
-            data: row,
-            group_rows: vec![],
-        };
+    for row in data {
         let mut key = Vec::new();
-        for item in &projections {
-            let item = item.get(&row).clone();
-            key.push(item);
+        for (index, item) in projections.iter().enumerate() {
+            if set.contains(&index) {
+                key.push(item.get(&row).clone());
+            } else {
+                key.push(Value::Empty);
+            }
         }
-        groups.entry(key).or_default().push(GroupRow {
-            data: row.data,
-            group_rows: vec![],
-        });
+        groups.entry(key).or_default().push(row);
+    }
+    if groups.is_empty() && set.is_empty() {
+        groups.insert(vec![Value::Empty; projections.len()], vec![]);
     }
 
-    let rows: Vec<GroupRow> = groups
+    groups
         .into_iter()
         .map(|(k, group_rows)| {
             let data = DataRow::new(k);
             GroupRow { data, group_rows }
         })
-        .collect();
-    let metadata = Rc::new(Metadata::Grouped {
-        parent: results.metadata.clone(),
-        this: Box::new(metadata),
-    });
+        .collect()
+}
 
-    Ok(GroupedResultSet { rows, metadata })
+/// Returns all the expressions to group by and, for each grouping set, the indices of the
+/// expressions that are part of it (the rest will be NULL in that set).
+fn grouping_sets<'a>(
+    group_by: &'a [Expr],
+    modifiers: &[GroupByWithModifier],
+) -> Result<(Vec<&'a Expr>, Vec<Vec<usize>>), CvsSqlError> {
+    let mut keys: Vec<&Expr> = vec![];
+    let mut index_of = |expr: &'a Expr| match keys.iter().position(|key| *key == expr) {
+        Some(index) => index,
+        None => {
+            keys.push(expr);
+            keys.len() - 1
+        }
+    };
+    let mut elements = vec![];
+    for expr in group_by {
+        let element = match expr {
+            Expr::Rollup(items) => {
+                let items: Vec<Vec<usize>> = items
+                    .iter()
+                    .map(|item| item.iter().map(&mut index_of).collect())
+                    .collect();
+                rollup(&items)
+            }
+            Expr::Cube(items) => {
+                let items: Vec<Vec<usize>> = items
+                    .iter()
+                    .map(|item| item.iter().map(&mut index_of).collect())
+                    .collect();
+                cube(&items)
+            }
+            Expr::GroupingSets(sets) => sets
+                .iter()
+                .map(|set| set.iter().map(&mut index_of).collect())
+                .collect(),
+            _ => vec![vec![index_of(expr)]],
+        };
+        elements.push(element);
+    }
+    let sets = match modifiers {
+        [] => elements
+            .iter()
+            .fold(vec![vec![]], |sets, element| cross_product(&sets, element)),
+        [GroupByWithModifier::Rollup] | [GroupByWithModifier::Cube] => {
+            let mut items = vec![];
+            for element in &elements {
+                let [item] = element.as_slice() else {
+                    return Err(CvsSqlError::Unsupported(format!(
+                        "GROUP BY {} with grouping sets",
+                        modifiers[0]
+                    )));
+                };
+                items.push(item.clone());
+            }
+            if matches!(modifiers, [GroupByWithModifier::Rollup]) {
+                rollup(&items)
+            } else {
+                cube(&items)
+            }
+        }
+        _ => {
+            return Err(CvsSqlError::Unsupported(format!(
+                "GROUP BY {}",
+                modifiers.iter().join(" ")
+            )));
+        }
+    };
+    Ok((keys, sets))
+}
+
+fn rollup(items: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    (0..=items.len())
+        .rev()
+        .map(|size| items[..size].concat())
+        .collect()
+}
+
+fn cube(items: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut sets = vec![];
+    for mask in (0..(1usize << items.len())).rev() {
+        let set = items
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| mask & (1 << (items.len() - 1 - index)) != 0)
+            .flat_map(|(_, item)| item.iter().copied())
+            .collect();
+        sets.push(set);
+    }
+    sets
+}
+
+fn cross_product(sets: &[Vec<usize>], element: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut product = vec![];
+    for set in sets {
+        for other in element {
+            product.push([set.as_slice(), other.as_slice()].concat());
+        }
+    }
+    product
 }
 
 pub fn force_group_by(results: ResultSet) -> GroupedResultSet {
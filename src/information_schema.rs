@@ -0,0 +1,224 @@
+use std::fs;
+use std::path::{self, Path, PathBuf};
+use std::rc::Rc;
+
+use sqlparser::ast::ObjectName;
+
+use crate::engine::Engine;
+use crate::error::CvsSqlError;
+use crate::file_results::{is_blank, read_cell};
+use crate::result_set_metadata::SimpleResultSetMetadata;
+use crate::results::{Name, ResultSet};
+use crate::results_data::{DataRow, ResultsData};
+use crate::schema::{ColumnType, InferredType};
+use crate::show::get_table_name;
+use crate::value::Value;
+
+const SCHEMA_NAME: &str = "information_schema";
+const SAMPLE_SIZE: usize = 100;
+
+pub(crate) fn read_information_schema(
+    engine: &Engine,
+    name: &ObjectName,
+) -> Result<Option<ResultSet>, CvsSqlError> {
+    let [schema, table] = name.0.as_slice() else {
+        return Ok(None);
+    };
+    if schema.to_string().to_lowercase() != SCHEMA_NAME {
+        return Ok(None);
+    }
+    match table.to_string().to_lowercase().as_str() {
+        "tables" => Ok(Some(tables(engine)?)),
+        "columns" => Ok(Some(columns(engine)?)),
+        _ => Ok(None),
+    }
+}
+
+fn tables(engine: &Engine) -> Result<ResultSet, CvsSqlError> {
+    let mut metadata = SimpleResultSetMetadata::new(Some(result_name("tables")));
+    metadata.add_column("table_name");
+    metadata.add_column("number_of_columns");
+    metadata.add_column("path");
+
+    let mut rows = vec![];
+    for (name, path) in find_tables(&engine.home())? {
+        let mut reader = engine
+            .csv_reader(&path)
+            .has_headers(false)
+            .from_path(&path)?;
+        let number_of_columns = match reader.records().next() {
+            Some(record) => record?.len(),
+            None => 0,
+        };
+        let absolute = path::absolute(path)?;
+        let absolute = absolute.to_str().unwrap_or_default().to_string();
+        rows.push(DataRow::new(vec![
+            Value::Str(name),
+            Value::Number((number_of_columns as u64).into()),
+            Value::Str(absolute),
+        ]));
+    }
+
+    let metadata = Rc::new(metadata.build());
+    let data = ResultsData::new(rows);
+    Ok(ResultSet { metadata, data })
+}
+
+fn columns(engine: &Engine) -> Result<ResultSet, CvsSqlError> {
+    let mut metadata = SimpleResultSetMetadata::new(Some(result_name("columns")));
+    metadata.add_column("table_name");
+    metadata.add_column("column_name");
+    metadata.add_column("ordinal_position");
+    metadata.add_column("inferred_type");
+
+    let mut rows = vec![];
+    for (name, path) in find_tables(&engine.home())? {
+        let mut reader = engine
+            .csv_reader(&path)
+            .has_headers(engine.first_line_as_name)
+            .from_path(&path)?;
+        let mut columns: Vec<(String, Option<ColumnType>, InferredType)> = vec![];
+        if engine.first_line_as_name {
+            for title in reader.headers()? {
+                let title = engine.header_title(title);
+                let column_type = engine.column_type(&title);
+                columns.push((title, column_type, InferredType::Unknown));
+            }
+        }
+        for record in reader.records().take(SAMPLE_SIZE) {
+            let record = record?;
+            if is_blank(&record) {
+                continue;
+            }
+            for (index, cell) in record.iter().enumerate() {
+                if index >= columns.len() {
+                    let title = engine.default_header(index);
+                    let column_type = engine.column_type(&title);
+                    columns.push((title, column_type, InferredType::Unknown));
+                }
+                let (title, column_type, inferred) = &mut columns[index];
+                let value = read_cell(engine, title, *column_type, cell)?;
+                inferred.add(&value);
+            }
+        }
+        for (index, (title, column_type, inferred)) in columns.into_iter().enumerate() {
+            let inferred_type = match column_type {
+                Some(column_type) => column_type.name(),
+                None => inferred.name(),
+            };
+            rows.push(DataRow::new(vec![
+                Value::Str(name.clone()),
+                Value::Str(title),
+                Value::Number((index as u64 + 1).into()),
+                Value::Str(inferred_type.to_string()),
+            ]));
+        }
+    }
+
+    let metadata = Rc::new(metadata.build());
+    let data = ResultsData::new(rows);
+    Ok(ResultSet { metadata, data })
+}
+
+fn result_name(table: &str) -> Name {
+    vec![SCHEMA_NAME.to_string(), table.to_string()].into()
+}
+
+fn find_tables(home: &Path) -> Result<Vec<(String, PathBuf)>, CvsSqlError> {
+    let mut tables = vec![];
+    walk(home, "", &mut tables)?;
+    tables.sort();
+    Ok(tables)
+}
+
+fn walk(path: &Path, root: &str, tables: &mut Vec<(String, PathBuf)>) -> Result<(), CvsSqlError> {
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        let path = entry.path();
+        if metadata.is_dir() {
+            let name = entry.file_name();
+            let name = name.to_str().unwrap_or_default();
+            if name.starts_with('.') {
+                continue;
+            }
+            walk(&path, &format!("{root}{name}."), tables)?;
+        } else if metadata.is_file()
+            && let Some(name) = get_table_name(&path)
+        {
+            tables.push((format!("{root}{name}"), path));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{args::Args, results::Column};
+
+    use super::*;
+
+    #[test]
+    fn list_tables() -> Result<(), CvsSqlError> {
+        let args = Args {
+            home: Some("tests/data".into()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands(
+            "SELECT table_name, number_of_columns FROM information_schema.tables",
+        )?;
+        let results = &results.first().unwrap().results;
+        let tables: Vec<_> = results
+            .data
+            .iter()
+            .map(|row| {
+                (
+                    row.get(&Column::from_index(0)).to_string(),
+                    row.get(&Column::from_index(1)).to_string(),
+                )
+            })
+            .collect();
+
+        assert!(tables.contains(&("customers".to_string(), "7".to_string())));
+        assert!(tables.contains(&("sales".to_string(), "7".to_string())));
+
+        Ok(())
+    }
+
+    #[test]
+    fn list_id_columns() -> Result<(), CvsSqlError> {
+        let args = Args {
+            home: Some("tests/data".into()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands(
+            "SELECT table_name, ordinal_position, inferred_type FROM information_schema.columns WHERE column_name = 'id' ORDER BY table_name",
+        )?;
+        let results = &results.first().unwrap().results;
+        let columns: Vec<_> = results
+            .data
+            .iter()
+            .map(|row| {
+                (0..3)
+                    .map(|index| row.get(&Column::from_index(index)).to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        assert_eq!(
+            columns,
+            vec![
+                vec!["customers", "1", "number"],
+                vec!["empty_strings", "1", "number"],
+                vec!["sales", "1", "string"],
+                vec!["zip_codes", "1", "number"],
+            ]
+        );
+
+        Ok(())
+    }
+}
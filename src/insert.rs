@@ -14,7 +14,7 @@ use crate::{
     result_set_metadata::SimpleResultSetMetadata,
     results::{Name, ResultSet},
     results_data::{DataRow, ResultsData},
-    writer::new_csv_writer,
+    writer::new_delimited_csv_writer,
 };
 
 impl Extractor for Insert {
@@ -78,7 +78,6 @@ impl Extractor for Insert {
             }
         } else {
             for name in &self.columns {
-                let name = name.to_string();
                 let name: Name = name.into();
                 let col = current_data.metadata.column_index(&name)?;
                 columns.push(col.clone());
@@ -111,9 +110,15 @@ impl Extractor for Insert {
         let metadata = Rc::new(metadata.build());
         let data = ResultsData::new(rows);
         let results = ResultSet { metadata, data };
-        let file = OpenOptions::new().append(true).open(file.path)?;
-        let mut writer = new_csv_writer(file, engine.first_line_as_name);
-        writer.append(&results)?;
+        if file.read_only {
+            return Err(CvsSqlError::ReadOnlyMode);
+        }
+        if engine.should_write(&file) {
+            let delimiter = engine.delimiter(&file.path);
+            let file = OpenOptions::new().append(true).open(file.path)?;
+            let mut writer = new_delimited_csv_writer(file, engine.first_line_as_name, delimiter);
+            writer.append(&results)?;
+        }
 
         build_simple_results(vec![
             ("action", Value::Str("INSERT".to_string())),
@@ -1,29 +1,42 @@
-use std::collections::HashSet;
+use std::collections::HashMap;
 use std::ops::Deref;
 use std::rc::Rc;
 
-use sqlparser::ast::{Join, JoinConstraint, JoinOperator, TableWithJoins};
+use sqlparser::ast::{
+    BinaryOperator, Expr, Join, JoinConstraint, JoinOperator, TableFactor, TableWithJoins,
+};
 
 use crate::engine::Engine;
 use crate::error::CvsSqlError;
 use crate::extractor::Extractor;
 use crate::group_by::GroupRow;
 use crate::projections::{Projection, SingleConvert};
-use crate::result_set_metadata::Metadata;
-use crate::results::{Column, Name, ResultSet};
+use crate::result_set_metadata::{Metadata, SimpleResultSetMetadata};
+use crate::results::{Column, ColumnIndexError, Name, ResultSet};
 use crate::results_data::{DataRow, ResultsData};
+use crate::table_functions::extract_lateral_table_function;
 use crate::value::Value;
 
 pub fn create_join(from: &[TableWithJoins], engine: &Engine) -> Result<ResultSet, CvsSqlError> {
     let mut result = None;
 
     for from in from {
-        let mut res = from.relation.extract(engine)?;
+        let (mut res, lateral) = match (&result, from.relation.extract(engine)) {
+            // A table function that uses the columns of the tables before it
+            (Some(left), Err(CvsSqlError::ColumnIndexError(err))) => {
+                match extract_lateral_table_function(engine, left, &from.relation)? {
+                    Some(res) => (res, true),
+                    None => return Err(CvsSqlError::ColumnIndexError(err)),
+                }
+            }
+            (_, res) => (res?, false),
+        };
         for j in &from.joins {
             res = join(res, j, engine)?;
         }
         result = match result {
             None => Some(res),
+            Some(_) if lateral => Some(res),
             Some(left) => {
                 let joiner = Joiner {
                     joiner_type: JoinerType {
@@ -41,25 +54,57 @@ pub fn create_join(from: &[TableWithJoins], engine: &Engine) -> Result<ResultSet
 }
 fn product(left: ResultSet, right: ResultSet, joiner: Joiner) -> ResultSet {
     let mut data = Vec::new();
-    let mut missing_right_rows = HashSet::new();
-    if joiner.joiner_type.right_outer {
-        for (i, _) in right.data.iter().enumerate() {
-            missing_right_rows.insert(i);
+    let right_rows: Vec<&DataRow> = right.data.iter().collect();
+    // The unmatched right rows are added in their original order
+    let mut matched_right_rows = vec![false; right_rows.len()];
+    let right_keys = match joiner.constraint {
+        JoinerConstraint::Equals { right: ref key, .. } => {
+            let mut keys: HashMap<Value, Vec<usize>> = HashMap::new();
+            for (r_index, r) in right_rows.iter().enumerate() {
+                let value = key_value(key.as_ref(), r);
+                if value != Value::Empty {
+                    keys.entry(value).or_default().push(r_index);
+                }
+            }
+            Some(keys)
         }
-    }
+        _ => None,
+    };
+    // The right columns of a USING are collapsed into the left ones
+    let right_columns: Vec<Column> = match joiner.constraint {
+        JoinerConstraint::Using(ref indices) => right
+            .columns()
+            .filter(|column| {
+                indices
+                    .iter()
+                    .all(|(_, right_col)| right_col.get_index() != column.get_index())
+            })
+            .collect(),
+        _ => right.columns().collect(),
+    };
     for l in left.data.iter() {
         let mut right_added = false;
-        for (r_index, r) in right.data.iter().enumerate() {
+        let candidates: Box<dyn Iterator<Item = usize>> = match (&joiner.constraint, &right_keys) {
+            (JoinerConstraint::Equals { left: key, .. }, Some(keys)) => {
+                match keys.get(&key_value(key.as_ref(), l)) {
+                    Some(indices) => Box::new(indices.iter().copied()),
+                    None => Box::new(std::iter::empty()),
+                }
+            }
+            _ => Box::new(0..right_rows.len()),
+        };
+        for r_index in candidates {
+            let r = right_rows[r_index];
             let mut row = Vec::new();
             for left_column in left.columns() {
                 row.push(l.get(&left_column).clone());
             }
-            for right_column in right.columns() {
-                row.push(r.get(&right_column).clone());
+            for right_column in &right_columns {
+                row.push(r.get(right_column).clone());
             }
             let mut row = DataRow::new(row);
             let use_row = match joiner.constraint {
-                JoinerConstraint::All => true,
+                JoinerConstraint::All | JoinerConstraint::Equals { .. } => true,
                 JoinerConstraint::On(ref filter) => {
                     let grouped_row = GroupRow {
                         data: row,
@@ -88,7 +133,7 @@ fn product(left: ResultSet, right: ResultSet, joiner: Joiner) -> ResultSet {
             };
             if use_row {
                 data.push(row);
-                missing_right_rows.remove(&r_index);
+                matched_right_rows[r_index] = true;
                 right_added = true;
             }
         }
@@ -97,7 +142,7 @@ fn product(left: ResultSet, right: ResultSet, joiner: Joiner) -> ResultSet {
             for left_column in left.columns() {
                 row.push(l.get(&left_column).clone());
             }
-            for _ in right.columns() {
+            for _ in &right_columns {
                 row.push(Value::Empty);
             }
             let row = DataRow::new(row);
@@ -105,27 +150,79 @@ fn product(left: ResultSet, right: ResultSet, joiner: Joiner) -> ResultSet {
         }
     }
     if joiner.joiner_type.right_outer {
-        for index in missing_right_rows {
-            let mut row = Vec::new();
-            for _ in left.columns() {
-                row.push(Value::Empty);
+        for (r, _) in right
+            .data
+            .iter()
+            .zip(matched_right_rows)
+            .filter(|(_, matched)| !matched)
+        {
+            let mut row = vec![Value::Empty; left.metadata.number_of_columns()];
+            if let JoinerConstraint::Using(ref indices) = joiner.constraint {
+                for (left_col, right_col) in indices {
+                    row[left_col.get_index()] = r.get(right_col).clone();
+                }
             }
-            let empty = DataRow::new(vec![]);
-            let r = right.data.get(index).unwrap_or(&empty);
-            for right_column in right.columns() {
-                row.push(r.get(&right_column).clone());
+            for right_column in &right_columns {
+                row.push(r.get(right_column).clone());
             }
             let row = DataRow::new(row);
             data.push(row);
         }
     }
-    let metadata = Rc::new(Metadata::product(&left.metadata, &right.metadata));
+    let metadata = match joiner.constraint {
+        JoinerConstraint::Using(ref indices) => {
+            using_metadata(&left.metadata, &right.metadata, &right_columns, indices)
+        }
+        _ => Metadata::product(&left.metadata, &right.metadata),
+    };
+    let metadata = Rc::new(metadata);
     let data = ResultsData::new(data);
     ResultSet { data, metadata }
 }
 
+/// The metadata of a `USING` join, with a single column for every pair of joined columns.
+/// The qualified name of the dropped right column (like `b.id`) refers to the left one.
+fn using_metadata(
+    left: &Metadata,
+    right: &Metadata,
+    right_columns: &[Column],
+    indices: &[(Column, Column)],
+) -> Metadata {
+    let mut metadata = SimpleResultSetMetadata::new(None);
+    for column in left.columns() {
+        if let Some(name) = left.column_name(&column) {
+            metadata.add_qualified_column(name);
+        }
+    }
+    for column in right_columns {
+        if let Some(name) = right.column_name(column) {
+            metadata.add_qualified_column(name);
+        }
+    }
+    for (left_col, right_col) in indices {
+        if let Some(name) = right.column_name(right_col) {
+            metadata.add_alias(left_col, name);
+        }
+    }
+    metadata.build()
+}
+
+fn key_value(key: &dyn Projection, row: &DataRow) -> Value {
+    let row = GroupRow {
+        data: row.clone(),
+        group_rows: vec![],
+    };
+    key.get(&row).deref().clone()
+}
+
 enum JoinerConstraint {
     On(Box<dyn Projection>),
+    /// An `ON` that compares an expression of the left side to an expression of the right side,
+    /// joined by looking up the keys of the right rows instead of checking every pair of rows.
+    Equals {
+        left: Box<dyn Projection>,
+        right: Box<dyn Projection>,
+    },
     Using(Vec<(Column, Column)>),
     All,
 }
@@ -137,26 +234,76 @@ impl JoinerConstraint {
         right_metadata: &Rc<Metadata>,
     ) -> Result<Self, CvsSqlError> {
         match constraint {
-            JoinConstraint::Natural => Err(CvsSqlError::Unsupported("Natural join".into())),
+            JoinConstraint::Natural => {
+                let mut names: Vec<Name> = vec![];
+                for column in left_metadata.columns() {
+                    let name: Name = left_metadata.column_title(&column).into();
+                    if !names.contains(&name) && right_metadata.column_index(&name).is_ok() {
+                        names.push(name);
+                    }
+                }
+                Self::using(&names, left_metadata, right_metadata)
+            }
             JoinConstraint::None => Ok(Self::All),
             JoinConstraint::On(expr) => {
                 let metadata = Metadata::product(left_metadata, right_metadata);
                 let on = expr.convert_single(&metadata, engine)?;
+                if let Some(equals) = Self::equals(expr, engine, left_metadata, right_metadata) {
+                    return Ok(equals);
+                }
                 Ok(Self::On(on))
             }
             JoinConstraint::Using(using) => {
-                let mut indices = vec![];
-                for name in using {
-                    let name: Name = name.to_string().into();
-                    let left_index = left_metadata.column_index(&name)?;
-                    let right_index = right_metadata.column_index(&name)?;
-                    indices.push((left_index.clone(), right_index.clone()));
-                }
-
-                Ok(Self::Using(indices))
+                let names: Vec<Name> = using.iter().map(|name| name.into()).collect();
+                Self::using(&names, left_metadata, right_metadata)
             }
         }
     }
+
+    fn using(
+        names: &[Name],
+        left_metadata: &Metadata,
+        right_metadata: &Metadata,
+    ) -> Result<Self, CvsSqlError> {
+        let column_index =
+            |metadata: &Metadata, name: &Name, side: &str| match metadata.column_index(name) {
+                Ok(index) => Ok(index.clone()),
+                Err(ColumnIndexError::NoSuchColumn(name)) => {
+                    Err(CvsSqlError::NoUsingColumn(name, side.to_string()))
+                }
+                Err(e) => Err(e.into()),
+            };
+        let mut indices = vec![];
+        for name in names {
+            let left_index = column_index(left_metadata, name, "left")?;
+            let right_index = column_index(right_metadata, name, "right")?;
+            indices.push((left_index, right_index));
+        }
+
+        Ok(Self::Using(indices))
+    }
+
+    fn equals(
+        expr: &Expr,
+        engine: &Engine,
+        left_metadata: &Metadata,
+        right_metadata: &Metadata,
+    ) -> Option<Self> {
+        let Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Eq,
+            right,
+        } = expr
+        else {
+            return None;
+        };
+        let convert = |left: &Expr, right: &Expr| {
+            let left = left.convert_single(left_metadata, engine).ok()?;
+            let right = right.convert_single(right_metadata, engine).ok()?;
+            Some(Self::Equals { left, right })
+        };
+        convert(left, right).or_else(|| convert(right, left))
+    }
 }
 struct JoinerType {
     left_outer: bool,
@@ -172,7 +319,17 @@ fn join(left: ResultSet, join: &Join, engine: &Engine) -> Result<ResultSet, CvsS
             "SELECT ... JOIN ... GLOBAL".to_string(),
         ));
     }
-    let right = join.relation.extract(engine)?;
+    let right = match join.relation.extract(engine) {
+        Err(CvsSqlError::ColumnIndexError(_))
+            if matches!(join.relation, TableFactor::Table { args: Some(_), .. }) =>
+        {
+            return Err(CvsSqlError::Unsupported(format!(
+                "JOIN {} with the columns of the tables before it (list it after a comma instead)",
+                join.relation
+            )));
+        }
+        right => right?,
+    };
     let (left_outer, right_outer, constraint) = match &join.join_operator {
         JoinOperator::Join(c) => (false, false, c),
         JoinOperator::Inner(c) => (false, false, c),
@@ -201,3 +358,97 @@ fn join(left: ResultSet, join: &Join, engine: &Engine) -> Result<ResultSet, CvsS
 
     Ok(product(left, right, joiner))
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{args::Args, engine::Engine, error::CvsSqlError, results::ResultSet, value::Value};
+
+    fn rows(results: &ResultSet) -> Vec<Vec<Value>> {
+        results
+            .data
+            .iter()
+            .map(|row| results.columns().map(|col| row.get(&col).clone()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn join_on_equality_matches_where_filter() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+        for (on, filter) in [
+            (
+                "SELECT * FROM tests.data.sales INNER JOIN tests.data.customers ON sales.\"customer id\" = customers.id",
+                "SELECT * FROM tests.data.sales, tests.data.customers WHERE sales.\"customer id\" = customers.id",
+            ),
+            (
+                "SELECT * FROM tests.data.sales JOIN tests.data.customers ON customers.id = sales.\"customer id\"",
+                "SELECT * FROM tests.data.sales, tests.data.customers WHERE customers.id = sales.\"customer id\"",
+            ),
+            (
+                "SELECT * FROM tests.data.artists AS a JOIN tests.data.albums AS b ON a.artist_id + 1 = b.artist_id",
+                "SELECT * FROM tests.data.artists AS a, tests.data.albums AS b WHERE a.artist_id + 1 = b.artist_id",
+            ),
+            (
+                "SELECT * FROM tests.data.artists AS a JOIN tests.data.albums AS b ON a.name = b.artist_id",
+                "SELECT * FROM tests.data.artists AS a, tests.data.albums AS b WHERE a.name = b.artist_id",
+            ),
+            (
+                "SELECT * FROM tests.data.artists AS a JOIN tests.data.albums AS b ON a.artist_id = b.artist_id AND b.title LIKE 'A%'",
+                "SELECT * FROM tests.data.artists AS a, tests.data.albums AS b WHERE a.artist_id = b.artist_id AND b.title LIKE 'A%'",
+            ),
+        ] {
+            let joined = engine.execute_commands(on)?.remove(0).results;
+            let filtered = engine.execute_commands(filter)?.remove(0).results;
+
+            assert_eq!(
+                joined.data.iter().count(),
+                filtered.data.iter().count(),
+                "{on}"
+            );
+            assert_eq!(rows(&joined), rows(&filtered), "{on}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn using_join_keeps_a_single_joined_column() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+        for sql in [
+            "SELECT * FROM tests.data.artists AS a JOIN tests.data.albums AS b USING (artist_id)",
+            "SELECT * FROM tests.data.artists AS a NATURAL JOIN tests.data.albums AS b",
+        ] {
+            let results = engine.execute_commands(sql)?.remove(0).results;
+            let titles: Vec<_> = results
+                .columns()
+                .map(|col| results.metadata.column_title(&col).to_string())
+                .collect();
+
+            assert_eq!(
+                titles,
+                vec!["artist_id", "name", "album_id", "title"],
+                "{sql}"
+            );
+            assert_eq!(results.data.iter().count(), 4, "{sql}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn join_on_equality_never_matches_empty_keys() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+        let results = engine
+            .execute_commands(
+                "SELECT a.value FROM GENERATE_SERIES(1, 3) AS a JOIN GENERATE_SERIES(1, 3) AS b ON NULLIF(a.value, 2) = NULLIF(b.value, 2)",
+            )?
+            .remove(0)
+            .results;
+
+        assert_eq!(
+            rows(&results),
+            vec![vec![Value::Number(1.into())], vec![Value::Number(3.into())]]
+        );
+
+        Ok(())
+    }
+}
@@ -10,30 +10,43 @@ mod dialect;
 mod drop;
 pub mod engine;
 pub mod error;
+mod explain;
 mod extract_time;
 mod extract_values;
 mod extractor;
 mod file_results;
 mod filter_results;
 mod functions;
+mod glob_table;
 mod group_by;
+mod information_schema;
 mod insert;
 mod join;
+mod list_agg;
+mod merge;
 mod named_results;
 mod order_by_results;
 pub mod outputer;
+mod pivot;
 mod projections;
 mod result_set_metadata;
 pub mod results;
 mod results_builder;
 mod results_data;
+mod sampler;
+mod schema;
+mod select_into;
 pub mod session;
 mod show;
 mod stdin_as_table;
+pub mod streaming;
 mod table;
+mod table_functions;
+mod to_json;
 mod transaction;
 mod trimmer;
 mod update;
 pub mod util;
 pub mod value;
+mod window;
 pub mod writer;
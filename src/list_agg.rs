@@ -0,0 +1,260 @@
+use std::ops::Deref;
+
+use itertools::Itertools;
+use sqlparser::ast::{
+    DuplicateTreatment, Expr, FunctionArg, FunctionArgExpr, FunctionArgumentClause,
+    FunctionArguments, ListAggOnOverflow, OrderByExpr,
+};
+
+use crate::{
+    engine::Engine,
+    error::CvsSqlError,
+    group_by::GroupRow,
+    order_by_results::{OrderByItem, compare_keys},
+    projections::{Projection, SingleConvert},
+    result_set_metadata::{Metadata, SimpleResultSetMetadata},
+    results_data::DataRow,
+    util::SmartReference,
+    value::Value,
+};
+
+/// The maximal length (in characters) of a `LISTAGG` result with `ON OVERFLOW TRUNCATE`.
+const MAX_LENGTH: usize = 4000;
+
+struct Truncate {
+    filler: String,
+    with_count: bool,
+}
+
+struct ListAgg {
+    argument: Box<dyn Projection>,
+    separator: String,
+    distinct: bool,
+    order_by: Vec<OrderByItem>,
+    truncate: Option<Truncate>,
+    name: String,
+}
+
+impl Projection for ListAgg {
+    fn get<'a>(&'a self, row: &'a GroupRow) -> SmartReference<'a, Value> {
+        let mut values: Vec<_> = row
+            .group_rows
+            .iter()
+            .filter_map(|row| {
+                let value = self.argument.get(row);
+                if value.is_empty() {
+                    return None;
+                }
+                let keys: Vec<_> = self
+                    .order_by
+                    .iter()
+                    .map(|item| item.by.get(row).deref().clone())
+                    .collect();
+                Some((keys, value.to_string()))
+            })
+            .collect();
+        if values.is_empty() {
+            return Value::Empty.into();
+        }
+        values.sort_by(|(left, _), (right, _)| compare_keys(&self.order_by, left, right));
+        let mut values: Vec<_> = values.into_iter().map(|(_, value)| value).collect();
+        if self.distinct {
+            values = values.into_iter().unique().collect();
+        }
+        let list = values.iter().join(&self.separator);
+        let Some(truncate) = &self.truncate else {
+            return Value::Str(list).into();
+        };
+        if list.chars().count() <= MAX_LENGTH {
+            return Value::Str(list).into();
+        }
+        let separator_length = self.separator.chars().count();
+        let mut best = None;
+        let mut prefix_length = 0;
+        for size in 0..values.len() {
+            if size > 0 {
+                prefix_length += values[size - 1].chars().count() + separator_length;
+            }
+            let mut length = prefix_length + truncate.filler.chars().count();
+            if truncate.with_count {
+                length += format!("({})", values.len() - size).chars().count();
+            }
+            if length <= MAX_LENGTH {
+                best = Some(size);
+            }
+        }
+        if let Some(size) = best {
+            let mut list = values[..size].iter().join(&self.separator);
+            if size > 0 {
+                list.push_str(&self.separator);
+            }
+            list.push_str(&truncate.filler);
+            if truncate.with_count {
+                list.push_str(&format!("({})", values.len() - size));
+            }
+            return Value::Str(list).into();
+        }
+        Value::Empty.into()
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+pub(crate) fn create_list_agg(
+    metadata: &Metadata,
+    engine: &Engine,
+    args: &FunctionArguments,
+    within_group: &[OrderByExpr],
+) -> Result<Box<dyn Projection>, CvsSqlError> {
+    let parent_metadata = match metadata {
+        Metadata::Grouped { parent, this: _ } => parent,
+        _ => return Err(CvsSqlError::NoGroupBy),
+    };
+    let FunctionArguments::List(lst) = args else {
+        return Err(CvsSqlError::Unsupported(
+            "Function LISTAGG must have an argument".into(),
+        ));
+    };
+    let distinct = matches!(lst.duplicate_treatment, Some(DuplicateTreatment::Distinct));
+    let mut truncate = None;
+    for clause in &lst.clauses {
+        match clause {
+            FunctionArgumentClause::OnOverflow(ListAggOnOverflow::Truncate {
+                filler,
+                with_count,
+            }) => {
+                let filler = match filler {
+                    Some(filler) => constant_string(filler, engine)?,
+                    None => "...".to_string(),
+                };
+                truncate = Some(Truncate {
+                    filler,
+                    with_count: *with_count,
+                });
+            }
+            _ => return Err(CvsSqlError::Unsupported(format!("LISTAGG with {clause}"))),
+        }
+    }
+    let (argument, separator) = match lst.args.as_slice() {
+        [argument] => (argument, None),
+        [argument, separator] => (argument, Some(separator)),
+        _ => {
+            return Err(CvsSqlError::Unsupported(format!(
+                "Function LISTAGG with {} arguments",
+                lst.args.len()
+            )));
+        }
+    };
+    let FunctionArg::Unnamed(FunctionArgExpr::Expr(argument)) = argument else {
+        return Err(CvsSqlError::Unsupported(format!("{argument}")));
+    };
+    let argument = argument.convert_single(parent_metadata, engine)?;
+    let separator = match separator {
+        Some(FunctionArg::Unnamed(FunctionArgExpr::Expr(separator))) => {
+            constant_string(separator, engine)?
+        }
+        Some(separator) => return Err(CvsSqlError::Unsupported(format!("{separator}"))),
+        None => String::new(),
+    };
+    let order_by = within_group
+        .iter()
+        .map(|expr| OrderByItem::create(parent_metadata, engine, expr, &expr.expr))
+        .collect::<Result<Vec<_>, _>>()?;
+    let name = format!("LISTAGG({})", argument.name());
+
+    Ok(Box::new(ListAgg {
+        argument,
+        separator,
+        distinct,
+        order_by,
+        truncate,
+        name,
+    }))
+}
+
+fn constant_string(expr: &Expr, engine: &Engine) -> Result<String, CvsSqlError> {
+    let metadata = Metadata::Simple(SimpleResultSetMetadata::new(None));
+    let value = expr.convert_single(&metadata, engine)?;
+    let temp_row = GroupRow {
+        data: DataRow::new(vec![]),
+        group_rows: vec![],
+    };
+    Ok(value.get(&temp_row).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::{args::Args, engine::Engine, error::CvsSqlError, results::Column, value::Value};
+
+    fn list_agg(engine: &Engine, sql: &str) -> Result<Value, CvsSqlError> {
+        let results = engine.execute_commands(sql)?;
+        let results = &results.first().unwrap().results;
+        let row = results.data.iter().next().unwrap();
+        Ok(row.get(&Column::from_index(0)).clone())
+    }
+
+    #[test]
+    fn list_agg_with_ordering() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let list = list_agg(
+            &engine,
+            "SELECT LISTAGG(name, ', ') WITHIN GROUP (ORDER BY name DESC) FROM tests.data.customers WHERE active",
+        )?;
+
+        assert_eq!(
+            list,
+            Value::Str("Shania Jaskolski, Lindsey Von, Lavina Bode, Fernando Johnson, Enoch Rutherford, Amely Waelchi".into())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn list_agg_truncate_on_overflow() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        let mut content = String::from("id,word\n");
+        for i in 0..1000 {
+            content.push_str(&format!("{i},word{i}\n"));
+        }
+        fs::write(working_dir.path().join("words.csv"), content)?;
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let Value::Str(list) = list_agg(
+            &engine,
+            "SELECT LISTAGG(word, ',' ON OVERFLOW TRUNCATE '~' WITH COUNT) WITHIN GROUP (ORDER BY id) FROM words",
+        )?
+        else {
+            panic!("Not a string");
+        };
+        assert!(list.len() <= 4000);
+        assert!(list.starts_with("word0,word1,word2,"));
+        assert!(list.ends_with(",~(487)"), "{list}");
+
+        let Value::Str(list) = list_agg(
+            &engine,
+            "SELECT LISTAGG(word, ',' ON OVERFLOW TRUNCATE WITHOUT COUNT) FROM words",
+        )?
+        else {
+            panic!("Not a string");
+        };
+        assert!(list.len() <= 4000);
+        assert!(list.ends_with(",..."), "{list}");
+
+        let Value::Str(list) = list_agg(&engine, "SELECT LISTAGG(word, ',') FROM words")? else {
+            panic!("Not a string");
+        };
+        assert!(list.len() > 4000);
+
+        Ok(())
+    }
+}
@@ -4,7 +4,10 @@ use std::process::exit;
 
 use clap::Parser;
 use csvsql::{
-    args::Args, console::work_on_console, engine::Engine, error::CvsSqlError,
+    args::Args,
+    console::{execute_commands, work_on_console},
+    engine::Engine,
+    error::CvsSqlError,
     outputer::create_outputer,
 };
 
@@ -20,13 +23,10 @@ fn run() -> Result<(), CvsSqlError> {
     let mut outputer = create_outputer(&args)?;
     let engine = Engine::try_from(&args)?;
 
-    if let Some(commands) = args.command {
-        for command in commands {
-            for results in engine.execute_commands(&command)? {
-                if let Some(out) = outputer.write(&results)? {
-                    println!("{out}");
-                }
-            }
+    if let Some(commands) = &args.command {
+        let continue_on_error = args.continue_on_error || args.validate;
+        if !execute_commands(&engine, commands, continue_on_error, outputer.as_mut())? {
+            exit(-1);
         }
     } else {
         work_on_console(&engine, args.no_console, outputer.as_mut())?;
@@ -0,0 +1,291 @@
+use std::{collections::HashMap, fs::OpenOptions, ops::Deref};
+
+use bigdecimal::{BigDecimal, FromPrimitive};
+use sqlparser::ast::{
+    AssignmentTarget, Merge, MergeAction, MergeClauseKind, MergeInsertExpr, MergeInsertKind,
+    MergeUpdateExpr, TableFactor,
+};
+
+use crate::{
+    engine::Engine,
+    error::CvsSqlError,
+    extractor::Extractor,
+    group_by::GroupRow,
+    projections::{Projection, SingleConvert},
+    result_set_metadata::Metadata,
+    results::{Column, Name, ResultSet},
+    results_builder::build_simple_results,
+    results_data::{DataRow, ResultsData},
+    value::Value,
+    writer::{Writer, new_delimited_csv_writer},
+};
+
+enum MatchedAction {
+    Update(HashMap<usize, Box<dyn Projection>>),
+    Delete,
+}
+
+struct InsertAction {
+    columns: Vec<Column>,
+    /// The values to insert, or `None` to insert the source row as is (`INSERT ROW`).
+    values: Option<Vec<Box<dyn Projection>>>,
+}
+
+impl Extractor for Merge {
+    fn extract(&self, engine: &Engine) -> Result<ResultSet, CvsSqlError> {
+        if self.optimizer_hint.is_some() {
+            return Err(CvsSqlError::Unsupported("MERGE with hints".into()));
+        }
+        if self.output.is_some() {
+            return Err(CvsSqlError::Unsupported("MERGE with output".into()));
+        }
+
+        let table_file = match &self.table {
+            TableFactor::Table {
+                name,
+                alias: _,
+                args: None,
+                with_hints: _,
+                version: _,
+                with_ordinality: _,
+                partitions: _,
+                json_path: _,
+                sample: None,
+                index_hints: _,
+            } => engine.file_name(name)?,
+            _ => {
+                return Err(CvsSqlError::Unsupported(
+                    "MERGE not into a table".to_string(),
+                ));
+            }
+        };
+        if table_file.read_only {
+            return Err(CvsSqlError::ReadOnlyMode);
+        }
+
+        let target = self.table.extract(engine)?;
+        let source = self.source.extract(engine)?;
+        let metadata = Metadata::product(&target.metadata, &source.metadata);
+        let on = self.on.convert_single(&metadata, engine)?;
+
+        let mut matched = None;
+        let mut not_matched = None;
+        for clause in &self.clauses {
+            if let Some(predicate) = &clause.predicate {
+                return Err(CvsSqlError::Unsupported(format!(
+                    "MERGE with WHEN {} AND {predicate}",
+                    clause.clause_kind
+                )));
+            }
+            match (&clause.clause_kind, &clause.action) {
+                (MergeClauseKind::Matched, MergeAction::Update(update)) => {
+                    let action = matched_update(engine, update, &target, &metadata)?;
+                    if matched.replace(action).is_some() {
+                        return Err(CvsSqlError::Unsupported(
+                            "MERGE with more than one WHEN MATCHED".into(),
+                        ));
+                    }
+                }
+                (MergeClauseKind::Matched, MergeAction::Delete { .. }) => {
+                    if matched.replace(MatchedAction::Delete).is_some() {
+                        return Err(CvsSqlError::Unsupported(
+                            "MERGE with more than one WHEN MATCHED".into(),
+                        ));
+                    }
+                }
+                (
+                    MergeClauseKind::NotMatched | MergeClauseKind::NotMatchedByTarget,
+                    MergeAction::Insert(insert),
+                ) => {
+                    let action = not_matched_insert(engine, insert, &target, &source)?;
+                    if not_matched.replace(action).is_some() {
+                        return Err(CvsSqlError::Unsupported(
+                            "MERGE with more than one WHEN NOT MATCHED".into(),
+                        ));
+                    }
+                }
+                (kind, action) => {
+                    return Err(CvsSqlError::Unsupported(format!(
+                        "MERGE with WHEN {kind} THEN {action}"
+                    )));
+                }
+            }
+        }
+
+        let mut matched_sources = vec![false; source.data.iter().count()];
+        let mut new_data = vec![];
+        let mut count = 0;
+        for target_row in target.data.iter() {
+            let mut matching = None;
+            for (index, source_row) in source.data.iter().enumerate() {
+                let row = joined_row(&target, target_row, &source, source_row);
+                if on.get(&row).deref() != &Value::Bool(true) {
+                    continue;
+                }
+                if matching.replace(row).is_some() {
+                    return Err(CvsSqlError::MergeMultipleMatches);
+                }
+                matched_sources[index] = true;
+            }
+            match (matching, &matched) {
+                (Some(row), Some(MatchedAction::Update(to_set))) => {
+                    let mut updated: Vec<_> = target
+                        .columns()
+                        .map(|column| target_row.get(&column).clone())
+                        .collect();
+                    for (col, value) in to_set {
+                        updated[*col] = value.get(&row).clone();
+                    }
+                    new_data.push(DataRow::new(updated));
+                    count += 1;
+                }
+                (Some(_), Some(MatchedAction::Delete)) => {
+                    count += 1;
+                }
+                _ => new_data.push(target_row.clone()),
+            }
+        }
+        if let Some(insert) = &not_matched {
+            for (source_row, _) in source
+                .data
+                .iter()
+                .zip(matched_sources)
+                .filter(|(_, matched)| !matched)
+            {
+                let row = GroupRow {
+                    data: source_row.clone(),
+                    group_rows: vec![],
+                };
+                let mut values = vec![Value::Empty; target.metadata.number_of_columns()];
+                for (index, col) in insert.columns.iter().enumerate() {
+                    values[col.get_index()] = match &insert.values {
+                        Some(values) => values[index].get(&row).clone(),
+                        None => source_row.get(&Column::from_index(index)).clone(),
+                    };
+                }
+                new_data.push(DataRow::new(values));
+                count += 1;
+            }
+        }
+
+        let metadata = target.metadata.clone();
+        let data = ResultsData::new(new_data);
+        let results = ResultSet { metadata, data };
+
+        if engine.should_write(&table_file) {
+            let file = OpenOptions::new()
+                .write(true)
+                .truncate(true)
+                .open(&table_file.path)?;
+            let mut writer = new_delimited_csv_writer(
+                file,
+                engine.first_line_as_name,
+                engine.delimiter(&table_file.path),
+            );
+            writer.write(&results)?;
+        }
+
+        build_simple_results(vec![
+            ("action", Value::Str("MERGE".to_string())),
+            (
+                "number_of_rows",
+                Value::Number(BigDecimal::from_usize(count).unwrap()),
+            ),
+        ])
+    }
+}
+
+fn joined_row(
+    target: &ResultSet,
+    target_row: &DataRow,
+    source: &ResultSet,
+    source_row: &DataRow,
+) -> GroupRow {
+    let mut row = Vec::new();
+    for column in target.columns() {
+        row.push(target_row.get(&column).clone());
+    }
+    for column in source.columns() {
+        row.push(source_row.get(&column).clone());
+    }
+    GroupRow {
+        data: DataRow::new(row),
+        group_rows: vec![],
+    }
+}
+
+fn matched_update(
+    engine: &Engine,
+    update: &MergeUpdateExpr,
+    target: &ResultSet,
+    metadata: &Metadata,
+) -> Result<MatchedAction, CvsSqlError> {
+    if update.update_predicate.is_some() || update.delete_predicate.is_some() {
+        return Err(CvsSqlError::Unsupported(format!(
+            "MERGE with UPDATE {update}"
+        )));
+    }
+    let mut to_set = HashMap::new();
+    for a in &update.assignments {
+        let value = a.value.convert_single(metadata, engine)?;
+        let field = match &a.target {
+            AssignmentTarget::ColumnName(col) => target.metadata.column_index(&col.into())?,
+            AssignmentTarget::Tuple(_) => {
+                return Err(CvsSqlError::Unsupported(
+                    "MERGE with tuple assignment".to_string(),
+                ));
+            }
+        };
+        if to_set.insert(field.get_index(), value).is_some() {
+            return Err(CvsSqlError::MultiplyAssignment);
+        }
+    }
+    Ok(MatchedAction::Update(to_set))
+}
+
+fn not_matched_insert(
+    engine: &Engine,
+    insert: &MergeInsertExpr,
+    target: &ResultSet,
+    source: &ResultSet,
+) -> Result<InsertAction, CvsSqlError> {
+    if insert.insert_predicate.is_some() {
+        return Err(CvsSqlError::Unsupported(format!(
+            "MERGE with INSERT {insert}"
+        )));
+    }
+    let mut columns = vec![];
+    if insert.columns.is_empty() {
+        columns.extend(target.columns());
+    } else {
+        for name in &insert.columns {
+            let name: Name = name.into();
+            let col = target.metadata.column_index(&name)?;
+            columns.push(col.clone());
+        }
+    }
+    let values = match &insert.kind {
+        MergeInsertKind::Row => {
+            if source.metadata.number_of_columns() != columns.len() {
+                return Err(CvsSqlError::InsertMismatch);
+            }
+            None
+        }
+        MergeInsertKind::Values(values) => {
+            let [row] = values.rows.as_slice() else {
+                return Err(CvsSqlError::Unsupported(
+                    "MERGE with INSERT of more than one row".to_string(),
+                ));
+            };
+            if row.len() != columns.len() {
+                return Err(CvsSqlError::InsertMismatch);
+            }
+            let values = row
+                .iter()
+                .map(|expr| expr.convert_single(&source.metadata, engine))
+                .collect::<Result<Vec<_>, _>>()?;
+            Some(values)
+        }
+    };
+    Ok(InsertAction { columns, values })
+}
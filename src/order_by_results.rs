@@ -2,13 +2,16 @@ use std::cmp::Ordering;
 use std::ops::Deref;
 
 use crate::error::CvsSqlError;
-use crate::group_by::{GroupRow, GroupedResultSet};
+use crate::group_by::GroupedResultSet;
 use crate::projections::Projection;
+use crate::result_set_metadata::Metadata;
+use crate::results::Name;
+use crate::value::Value;
 use crate::{engine::Engine, projections::SingleConvert};
-use sqlparser::ast::{OrderBy, OrderByExpr, OrderByKind};
+use sqlparser::ast::{Expr, OrderBy, OrderByExpr, OrderByKind, SelectItem};
 
-struct OrderByItem {
-    by: Box<dyn Projection>,
+pub(crate) struct OrderByItem {
+    pub(crate) by: Box<dyn Projection>,
     asc: bool,
     empty_first: bool,
 }
@@ -17,11 +20,22 @@ impl OrderByItem {
         parent: &GroupedResultSet,
         engine: &Engine,
         expr: &OrderByExpr,
+        projection: &[SelectItem],
+    ) -> Result<Self, CvsSqlError> {
+        let by = resolve_alias(&expr.expr, projection, &parent.metadata);
+        Self::create(&parent.metadata, engine, expr, by)
+    }
+
+    pub(crate) fn create(
+        metadata: &Metadata,
+        engine: &Engine,
+        expr: &OrderByExpr,
+        by: &Expr,
     ) -> Result<Self, CvsSqlError> {
         if expr.with_fill.is_some() {
             return Err(CvsSqlError::Unsupported("ORDER BY with fill".into()));
         }
-        let by = expr.expr.convert_single(&parent.metadata, engine)?;
+        let by = by.convert_single(metadata, engine)?;
         let asc = expr.options.asc.unwrap_or(true);
         let empty_first = expr.options.nulls_first.unwrap_or(false);
 
@@ -32,13 +46,11 @@ impl OrderByItem {
         })
     }
 
-    fn compare(&self, left: &GroupRow, right: &GroupRow) -> Ordering {
+    fn compare(&self, left: &Value, right: &Value) -> Ordering {
         let ret = self.compare_as_is(left, right);
         if self.asc { ret } else { ret.reverse() }
     }
-    fn compare_as_is(&self, left: &GroupRow, right: &GroupRow) -> Ordering {
-        let left = self.by.get(left);
-        let right = self.by.get(right);
+    fn compare_as_is(&self, left: &Value, right: &Value) -> Ordering {
         if left.is_empty() {
             if right.is_empty() {
                 Ordering::Equal
@@ -54,13 +66,45 @@ impl OrderByItem {
                 Ordering::Less
             }
         } else {
-            left.cmp(right.deref())
+            left.cmp(right)
+        }
+    }
+}
+fn resolve_alias<'a>(
+    expr: &'a Expr,
+    projection: &'a [SelectItem],
+    metadata: &Metadata,
+) -> &'a Expr {
+    let Expr::Identifier(ident) = expr else {
+        return expr;
+    };
+    let name: Name = ident.value.to_string().into();
+    if metadata.column_index(&name).is_ok() {
+        return expr;
+    }
+    projection
+        .iter()
+        .find_map(|item| match item {
+            SelectItem::ExprWithAlias { expr, alias } if alias.value == ident.value => Some(expr),
+            _ => None,
+        })
+        .unwrap_or(expr)
+}
+
+pub(crate) fn compare_keys(items: &[OrderByItem], left: &[Value], right: &[Value]) -> Ordering {
+    for (item, (left, right)) in items.iter().zip(left.iter().zip(right)) {
+        let order = item.compare(left, right);
+        if order != Ordering::Equal {
+            return order;
         }
     }
+    Ordering::Equal
 }
+
 pub fn order_by(
     engine: &Engine,
     order_by: &Option<OrderBy>,
+    projection: &[SelectItem],
     results: &mut GroupedResultSet,
 ) -> Result<(), CvsSqlError> {
     let Some(order_by) = order_by else {
@@ -74,21 +118,25 @@ pub fn order_by(
     };
     let items = exprs
         .iter()
-        .map(|expr| OrderByItem::new(results, engine, expr))
+        .map(|expr| OrderByItem::new(results, engine, expr, projection))
         .collect::<Result<Vec<_>, _>>()?;
     if items.is_empty() {
         return Ok(());
     }
 
-    results.rows.sort_by(|left, right| {
-        for item in &items {
-            let order = item.compare(left, right);
-            if order != Ordering::Equal {
-                return order;
-            }
-        }
-        Ordering::Equal
-    });
+    let mut rows_with_keys: Vec<_> = results
+        .rows
+        .drain(..)
+        .map(|row| {
+            let keys: Vec<_> = items
+                .iter()
+                .map(|item| item.by.get(&row).deref().clone())
+                .collect();
+            (keys, row)
+        })
+        .collect();
+    rows_with_keys.sort_by(|(left, _), (right, _)| compare_keys(&items, left, right));
+    results.rows = rows_with_keys.into_iter().map(|(_, row)| row).collect();
     Ok(())
 }
 
@@ -99,7 +147,7 @@ mod tests {
         parser::Parser,
     };
 
-    use crate::{args::Args, dialect::FilesDialect, extractor::Extractor};
+    use crate::{args::Args, dialect::FilesDialect, extractor::Extractor, results::Column};
 
     use super::*;
 
@@ -165,4 +213,69 @@ mod tests {
             order_by.kind = OrderByKind::Expressions(vec![expr]);
         })
     }
+
+    fn ids(engine: &Engine, sql: &str) -> Result<Vec<String>, CvsSqlError> {
+        let results = engine.execute_commands(sql)?;
+        let results = &results.first().unwrap().results;
+        Ok(results
+            .data
+            .iter()
+            .map(|row| row.get(&Column::from_index(0)).to_string())
+            .collect())
+    }
+
+    #[test]
+    fn order_by_random_is_a_permutation() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let mut shuffled = ids(&engine, "SELECT id FROM tests.data.sales ORDER BY RANDOM()")?;
+        let mut expected = ids(&engine, "SELECT id FROM tests.data.sales")?;
+        assert_eq!(shuffled.len(), expected.len());
+
+        shuffled.sort();
+        expected.sort();
+        assert_eq!(shuffled, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn order_by_random_with_seed_is_reproducible() -> Result<(), CvsSqlError> {
+        let args = Args {
+            seed: Some(12),
+            ..Args::default()
+        };
+        let sql = "SELECT id FROM tests.data.sales ORDER BY RAND() LIMIT 10";
+
+        let first = ids(&Engine::try_from(&args)?, sql)?;
+        let second = ids(&Engine::try_from(&args)?, sql)?;
+        let not_shuffled = ids(
+            &Engine::try_from(&args)?,
+            "SELECT id FROM tests.data.sales LIMIT 10",
+        )?;
+
+        assert_eq!(first, second);
+        assert_ne!(first, not_shuffled);
+
+        Ok(())
+    }
+
+    #[test]
+    fn order_by_aggregation_alias() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let by_alias = ids(
+            &engine,
+            "SELECT \"customer id\", SUM(price) AS total FROM tests.data.sales GROUP BY \"customer id\" ORDER BY total DESC",
+        )?;
+        let by_aggregation = ids(
+            &engine,
+            "SELECT \"customer id\" FROM tests.data.sales GROUP BY \"customer id\" ORDER BY SUM(price) DESC",
+        )?;
+
+        assert_eq!(by_alias, by_aggregation);
+        assert_eq!(by_alias.first().unwrap(), "-5783077230795473732");
+
+        Ok(())
+    }
 }
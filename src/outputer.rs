@@ -5,64 +5,366 @@ use serde_json::{Map, Number, Value as JsonValue};
 use std::collections::HashMap;
 use std::fs::{self, File, OpenOptions};
 use std::io::{self, BufWriter, IsTerminal};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use csv::WriterBuilder;
+use flate2::Compression;
+use flate2::write::GzEncoder;
 use std::io::Write;
 
 use crate::args::OutputFormat;
 use crate::engine::CommandExecution;
+use crate::results::{Column, ResultSet};
+use crate::streaming::RowStream;
 use crate::value::Value;
 use crate::writer::Writer;
-use crate::{args::Args, error::CvsSqlError, table::draw_table, writer::new_csv_writer};
-use bigdecimal::ToPrimitive;
+use crate::{
+    args::Args,
+    error::CvsSqlError,
+    table::draw_table,
+    writer::{display_value, new_csv_writer, new_typed_csv_writer},
+};
+use bigdecimal::{BigDecimal, ToPrimitive};
+use itertools::Itertools;
+use unicode_width::UnicodeWidthStr;
 
 pub trait Outputer {
     fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError>;
+
+    /// Writes the results of a streamed query. By default, all the rows are collected and written together.
+    fn write_stream(&mut self, sql: &str, rows: RowStream) -> Result<Option<String>, CvsSqlError> {
+        let results = rows.collect_results()?;
+        self.write(&CommandExecution {
+            sql: sql.to_string(),
+            results,
+        })
+    }
 }
 
 pub fn create_outputer(args: &Args) -> Result<Box<dyn Outputer>, CvsSqlError> {
+    if args.gzip && args.output.is_none() {
+        return Err(CvsSqlError::OutputCreationError(
+            "Only output files can be compressed with gzip (use --output)".into(),
+        ));
+    }
+    if args.gzip
+        && !matches!(
+            args.output_format,
+            OutputFormat::Csv | OutputFormat::Txt | OutputFormat::Json
+        )
+    {
+        return Err(CvsSqlError::OutputCreationError(
+            "Only CSV, TXT and JSON outputs can be compressed with gzip".into(),
+        ));
+    }
+    if args.validate {
+        return Ok(Box::new(SilentOutputer {}));
+    }
+    if args.scalar {
+        return Ok(Box::new(ScalarOutputer {}));
+    }
     let Some(output) = &args.output else {
-        return Ok(create_console_output(args));
+        return Ok(match args.output_format {
+            OutputFormat::Txt => Box::new(TxtStreamOutputer::new(
+                io::stdout(),
+                args.typed_header,
+                &args.null_string,
+            )),
+            OutputFormat::Html => Box::new(HtmlStreamOutputer::new(io::stdout())),
+            OutputFormat::Json => Box::new(JsonStreamOutputer::new(io::stdout())),
+            OutputFormat::Markdown => Box::new(MarkdownStreamOutputer::new(io::stdout())),
+            OutputFormat::Sql => Box::new(SqlStreamOutputer::new(io::stdout())),
+            OutputFormat::Csv | OutputFormat::Xls => create_console_output(args)?,
+        });
     };
     match args.output_format {
-        OutputFormat::Csv => Ok(Box::new(CsvOutputer::new(output)?)),
-        OutputFormat::Txt => Ok(Box::new(TxtOutputer::new(output)?)),
+        OutputFormat::Csv => Ok(Box::new(CsvOutputer::new(
+            output,
+            args.typed_header,
+            csv_delimiter(args)?,
+            &args.null_string,
+            args.gzip,
+        )?)),
+        OutputFormat::Txt => Ok(Box::new(TxtOutputer::new(
+            output,
+            args.typed_header,
+            &args.null_string,
+            args.gzip,
+        )?)),
         OutputFormat::Html => Ok(Box::new(HtmlOutputer::new(output)?)),
-        OutputFormat::Json => Ok(Box::new(JsonOutputer::new(output)?)),
+        OutputFormat::Json => Ok(Box::new(JsonOutputer::new(output, args.gzip)?)),
         OutputFormat::Xls => Ok(Box::new(XlsxOutputer::new(output)?)),
+        OutputFormat::Markdown => Ok(Box::new(MarkdownOutputer::new(output)?)),
+        OutputFormat::Sql => Ok(Box::new(SqlOutputer::new(output)?)),
     }
 }
 
-fn create_console_output(args: &Args) -> Box<dyn Outputer> {
+/// The delimiter of the CSV outputs must be a single byte (like `;` or a tab).
+fn csv_delimiter(args: &Args) -> Result<u8, CvsSqlError> {
+    match args.delimiter {
+        None => Ok(b','),
+        Some(delimiter) => u8::try_from(delimiter)
+            .ok()
+            .filter(u8::is_ascii)
+            .ok_or_else(|| {
+                CvsSqlError::OutputCreationError(format!(
+                    "The delimiter '{delimiter}' must be a single byte character"
+                ))
+            }),
+    }
+}
+
+fn create_console_output(args: &Args) -> Result<Box<dyn Outputer>, CvsSqlError> {
+    if args.expanded {
+        return Ok(Box::new(ExpandedOutputer::new(io::stdout())));
+    }
     if !args.display_as_csv
         && !args.no_console
         && io::stdout().is_terminal()
         && io::stdin().is_terminal()
         && io::stderr().is_terminal()
     {
-        Box::new(TableOutputer {})
+        Ok(Box::new(TableOutputer {
+            null_string: args.null_string.clone(),
+        }))
     } else {
-        Box::new(StdoutOutputer {})
+        Ok(Box::new(StdoutOutputer {
+            typed_header: args.typed_header,
+            delimiter: csv_delimiter(args)?,
+            null_string: args.null_string.clone(),
+        }))
     }
 }
-struct TableOutputer {}
+struct TableOutputer {
+    null_string: String,
+}
 impl Outputer for TableOutputer {
     fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
-        draw_table(&results.results)?;
+        draw_table(&results.results, &self.null_string)?;
         Ok(None)
     }
 }
 
-struct StdoutOutputer {}
+struct StdoutOutputer {
+    typed_header: bool,
+    delimiter: u8,
+    null_string: String,
+}
 impl Outputer for StdoutOutputer {
     fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
         let stdout = io::stdout().lock();
-        let mut writer = new_csv_writer(stdout, true);
+        let mut writer =
+            new_typed_csv_writer(stdout, self.typed_header, self.delimiter, &self.null_string);
         writer.write(&results.results).ok();
         Ok(None)
     }
+    fn write_stream(&mut self, sql: &str, rows: RowStream) -> Result<Option<String>, CvsSqlError> {
+        if self.typed_header {
+            // The types are inferred from all the rows
+            let results = rows.collect_results()?;
+            let sql = sql.to_string();
+            return self.write(&CommandExecution { sql, results });
+        }
+        write_csv_stream(io::stdout().lock(), rows, self.delimiter, &self.null_string)?;
+        Ok(None)
+    }
+}
+
+fn write_csv_stream<W: Write>(
+    writer: W,
+    rows: RowStream,
+    delimiter: u8,
+    null_string: &str,
+) -> Result<(), CvsSqlError> {
+    let mut writer = WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(writer);
+    let metadata = rows.metadata.clone();
+    let headers: Vec<_> = metadata
+        .columns()
+        .map(|column| metadata.column_title(&column))
+        .collect();
+    writer.write_record(&headers)?;
+    for row in rows {
+        let row = row?;
+        let line: Vec<_> = metadata
+            .columns()
+            .map(|column| display_value(row.get(&column), null_string))
+            .collect();
+        writer.write_record(line)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+struct SilentOutputer {}
+impl Outputer for SilentOutputer {
+    fn write(&mut self, _: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
+        Ok(None)
+    }
+}
+
+struct ScalarOutputer {}
+impl Outputer for ScalarOutputer {
+    fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
+        let results = &results.results;
+        let number_of_columns = results.metadata.number_of_columns();
+        let mut rows = results.data.iter();
+        match (rows.next(), rows.next()) {
+            (Some(row), None) if number_of_columns == 1 => {
+                Ok(Some(row.get(&Column::from_index(0)).to_string()))
+            }
+            _ => Err(CvsSqlError::NotAScalar(
+                results.data.iter().count(),
+                number_of_columns,
+            )),
+        }
+    }
+}
+
+struct TxtStreamOutputer<W: Write> {
+    writer: W,
+    typed_header: bool,
+    null_string: String,
+}
+impl<W: Write> TxtStreamOutputer<W> {
+    fn new(writer: W, typed_header: bool, null_string: &str) -> Self {
+        Self {
+            writer,
+            typed_header,
+            null_string: null_string.to_string(),
+        }
+    }
+}
+impl<W: Write> Outputer for TxtStreamOutputer<W> {
+    fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
+        write_txt_results(
+            &mut self.writer,
+            &results.results,
+            self.typed_header,
+            &self.null_string,
+        )?;
+        self.writer.flush()?;
+        Ok(None)
+    }
+}
+
+/// Prints every column on its own line (like the expanded display of `psql`), which is easier to read for wide results.
+struct ExpandedOutputer<W: Write> {
+    writer: W,
+}
+impl<W: Write> ExpandedOutputer<W> {
+    fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+impl<W: Write> Outputer for ExpandedOutputer<W> {
+    fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
+        write_expanded_results(&mut self.writer, &results.results)?;
+        self.writer.flush()?;
+        Ok(None)
+    }
+}
+
+fn write_expanded_results<W: Write>(mut writer: W, results: &ResultSet) -> Result<(), CvsSqlError> {
+    let titles: Vec<_> = results
+        .columns()
+        .map(|column| results.metadata.column_title(&column))
+        .collect();
+    let width = titles
+        .iter()
+        .map(|title| UnicodeWidthStr::width(*title))
+        .max()
+        .unwrap_or_default();
+    let rows: Vec<_> = results.data.iter().collect();
+    if rows.is_empty() {
+        writeln!(writer, "(0 rows)")?;
+    }
+    for (index, row) in rows.iter().enumerate() {
+        if rows.len() > 1 {
+            writeln!(writer, "-[ RECORD {} ]-", index + 1)?;
+        }
+        for (column, title) in results.columns().zip(&titles) {
+            let padding = width - UnicodeWidthStr::width(*title);
+            writeln!(
+                writer,
+                "{title}{} | {}",
+                " ".repeat(padding),
+                row.get(&column)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+struct HtmlStreamOutputer<W: Write> {
+    writer: W,
+}
+impl<W: Write> HtmlStreamOutputer<W> {
+    fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+impl<W: Write> Outputer for HtmlStreamOutputer<W> {
+    fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
+        write_html_results(&mut self.writer, &results.results)?;
+        self.writer.flush()?;
+        Ok(None)
+    }
+}
+
+struct JsonStreamOutputer<W: Write> {
+    writer: W,
+}
+impl<W: Write> JsonStreamOutputer<W> {
+    fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+impl<W: Write> Outputer for JsonStreamOutputer<W> {
+    fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
+        let data = results_as_json(&results.results);
+        if let Err(e) = serde_json::to_writer_pretty(&mut self.writer, &data) {
+            return Err(CvsSqlError::OutputCreationError(format!(
+                "Can not write json: {e}"
+            )));
+        }
+        writeln!(&mut self.writer)?;
+        self.writer.flush()?;
+        Ok(None)
+    }
+}
+
+pub(crate) fn write_results_to_file(path: &Path, results: &ResultSet) -> Result<(), CvsSqlError> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    match extension.as_str() {
+        "csv" => {
+            let mut writer = new_csv_writer(File::create(path)?, true);
+            writer.write(results)?;
+        }
+        "txt" => write_txt_results(File::create(path)?, results, false, "")?,
+        "html" => write_html_results(&mut BufWriter::new(File::create(path)?), results)?,
+        "json" => {
+            let data = results_as_json(results);
+            if let Err(e) = serde_json::to_writer_pretty(File::create(path)?, &data) {
+                return Err(CvsSqlError::OutputCreationError(format!(
+                    "Can not write json: {e}"
+                )));
+            }
+        }
+        _ => {
+            return Err(CvsSqlError::OutputCreationError(format!(
+                "File {} must have csv, txt, html or json extension",
+                path.to_str().unwrap_or_default()
+            )));
+        }
+    }
+    Ok(())
 }
 
 fn create_root_file_in_dir(dir: &PathBuf, file_name: &str) -> Result<PathBuf, CvsSqlError> {
@@ -87,16 +389,76 @@ fn create_root_file_in_dir(dir: &PathBuf, file_name: &str) -> Result<PathBuf, Cv
     }
 }
 
+/// A file of the outputs, that is compressed if `gzip` is set.
+enum OutputFile {
+    Plain(BufWriter<File>),
+    Gzip(Box<GzEncoder<BufWriter<File>>>),
+}
+impl OutputFile {
+    fn create(path: &Path, gzip: bool) -> Result<Self, CvsSqlError> {
+        let file = BufWriter::new(File::create(path)?);
+        if gzip {
+            Ok(Self::Gzip(Box::new(GzEncoder::new(
+                file,
+                Compression::default(),
+            ))))
+        } else {
+            Ok(Self::Plain(file))
+        }
+    }
+
+    /// The name of the output file number `index`, with a `.gz` extension if it is compressed.
+    fn name(index: usize, extension: &str, gzip: bool) -> String {
+        if gzip {
+            format!("{index}.{extension}.gz")
+        } else {
+            format!("{index}.{extension}")
+        }
+    }
+
+    fn finish(self) -> Result<(), CvsSqlError> {
+        match self {
+            Self::Plain(mut file) => file.flush()?,
+            Self::Gzip(encoder) => encoder.finish()?.flush()?,
+        }
+        Ok(())
+    }
+}
+impl Write for OutputFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(file) => file.write(buf),
+            Self::Gzip(encoder) => encoder.write(buf),
+        }
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Plain(file) => file.flush(),
+            Self::Gzip(encoder) => encoder.flush(),
+        }
+    }
+}
+
 struct CsvOutputer {
     index: usize,
     root: PathBuf,
     all: PathBuf,
+    typed_header: bool,
+    delimiter: u8,
+    null_string: String,
+    gzip: bool,
 }
 impl CsvOutputer {
-    fn new(dir: &PathBuf) -> Result<Self, CvsSqlError> {
+    fn new(
+        dir: &PathBuf,
+        typed_header: bool,
+        delimiter: u8,
+        null_string: &str,
+        gzip: bool,
+    ) -> Result<Self, CvsSqlError> {
         let all = create_root_file_in_dir(dir, "all.csv")?;
         let header = vec!["index", "file", "sql"];
-        let mut writer = WriterBuilder::new().from_path(&all)?;
+        let mut writer = WriterBuilder::new().delimiter(delimiter).from_path(&all)?;
         writer.write_record(header)?;
         writer.flush()?;
 
@@ -104,26 +466,71 @@ impl CsvOutputer {
             index: 0,
             root: dir.clone(),
             all,
+            typed_header,
+            delimiter,
+            null_string: null_string.to_string(),
+            gzip,
         })
     }
 }
-impl Outputer for CsvOutputer {
-    fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
+impl CsvOutputer {
+    fn next_file(&mut self, sql: &str) -> Result<PathBuf, CvsSqlError> {
         self.index += 1;
-        let file_name = format!("{}.csv", self.index);
-
+        let file_name = OutputFile::name(self.index, "csv", self.gzip);
         let path = self.root.join(&file_name);
-        let writer = File::create(&path)?;
-        let mut writer = new_csv_writer(writer, true);
-        writer.write(&results.results)?;
 
         let file = OpenOptions::new().append(true).open(&self.all)?;
-
-        let saved = vec![format!("{}", self.index), file_name, results.sql.clone()];
-        let mut writer = WriterBuilder::new().from_writer(file);
+        let saved = vec![format!("{}", self.index), file_name, sql.to_string()];
+        let mut writer = WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .from_writer(file);
         writer.write_record(saved)?;
         writer.flush()?;
 
+        Ok(path)
+    }
+}
+impl Outputer for CsvOutputer {
+    fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
+        let path = self.next_file(&results.sql)?;
+        let mut file = OutputFile::create(&path, self.gzip)?;
+        let mut writer = new_typed_csv_writer(
+            &mut file,
+            self.typed_header,
+            self.delimiter,
+            &self.null_string,
+        );
+        writer.write(&results.results)?;
+        drop(writer);
+        file.finish()?;
+
+        Ok(Some(format!(
+            "File {} created",
+            path.to_str().unwrap_or_default()
+        )))
+    }
+    fn write_stream(&mut self, sql: &str, rows: RowStream) -> Result<Option<String>, CvsSqlError> {
+        if self.typed_header {
+            // The types are inferred from all the rows
+            let results = rows.collect_results()?;
+            let sql = sql.to_string();
+            return self.write(&CommandExecution { sql, results });
+        }
+        // The rows are read while they are written, so a failure leaves no partial file behind.
+        let temp_path = self.root.join(format!(
+            ".{}.tmp",
+            OutputFile::name(self.index + 1, "csv", self.gzip)
+        ));
+        let mut file = OutputFile::create(&temp_path, self.gzip)?;
+        let written = write_csv_stream(&mut file, rows, self.delimiter, &self.null_string)
+            .and_then(|_| file.finish());
+        if let Err(err) = written {
+            fs::remove_file(&temp_path)?;
+            return Err(err);
+        }
+        let path = self.next_file(sql)?;
+        fs::rename(&temp_path, &path)?;
+
         Ok(Some(format!(
             "File {} created",
             path.to_str().unwrap_or_default()
@@ -135,9 +542,17 @@ struct TxtOutputer {
     index: usize,
     root: PathBuf,
     all: PathBuf,
+    typed_header: bool,
+    null_string: String,
+    gzip: bool,
 }
 impl TxtOutputer {
-    fn new(dir: &PathBuf) -> Result<Self, CvsSqlError> {
+    fn new(
+        dir: &PathBuf,
+        typed_header: bool,
+        null_string: &str,
+        gzip: bool,
+    ) -> Result<Self, CvsSqlError> {
         let all = create_root_file_in_dir(dir, "all.txt")?;
         let header = vec!["index", "file", "sql"];
         let mut writer = WriterBuilder::new()
@@ -151,34 +566,25 @@ impl TxtOutputer {
             index: 0,
             root: dir.clone(),
             all,
+            typed_header,
+            null_string: null_string.to_string(),
+            gzip,
         })
     }
 }
 impl Outputer for TxtOutputer {
     fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
         self.index += 1;
-        let file_name = format!("{}.txt", self.index);
+        let file_name = OutputFile::name(self.index, "txt", self.gzip);
         let path = self.root.join(&file_name);
-        let writer = File::create(&path)?;
-        let mut writer = WriterBuilder::new()
-            .delimiter(b'\t')
-            .quote_style(csv::QuoteStyle::Never)
-            .from_writer(writer);
-        let headers: Vec<_> = results
-            .results
-            .columns()
-            .map(|column| results.results.metadata.column_title(&column))
-            .collect();
-        writer.write_record(&headers)?;
-        for row in results.results.data.iter() {
-            let line: Vec<_> = results
-                .results
-                .columns()
-                .map(|column| row.get(&column))
-                .map(|f| f.to_string())
-                .collect();
-            writer.write_record(line)?
-        }
+        let mut file = OutputFile::create(&path, self.gzip)?;
+        write_txt_results(
+            &mut file,
+            &results.results,
+            self.typed_header,
+            &self.null_string,
+        )?;
+        file.finish()?;
 
         let saved = vec![format!("{}", self.index), file_name, results.sql.clone()];
         let file = OpenOptions::new().append(true).open(&self.all)?;
@@ -196,6 +602,222 @@ impl Outputer for TxtOutputer {
     }
 }
 
+fn write_txt_results<W: Write>(
+    writer: W,
+    results: &ResultSet,
+    typed_header: bool,
+    null_string: &str,
+) -> Result<(), CvsSqlError> {
+    let mut writer = WriterBuilder::new()
+        .delimiter(b'\t')
+        .quote_style(csv::QuoteStyle::Never)
+        .from_writer(writer);
+    let headers: Vec<_> = results
+        .columns()
+        .map(|column| results.metadata.column_title(&column))
+        .collect();
+    writer.write_record(&headers)?;
+    if typed_header {
+        writer.write_record(results.column_types())?;
+    }
+    for row in results.data.iter() {
+        let line: Vec<_> = results
+            .columns()
+            .map(|column| display_value(row.get(&column), null_string))
+            .collect();
+        writer.write_record(line)?
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+struct MarkdownStreamOutputer<W: Write> {
+    writer: W,
+}
+impl<W: Write> MarkdownStreamOutputer<W> {
+    fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+impl<W: Write> Outputer for MarkdownStreamOutputer<W> {
+    fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
+        write_markdown_results(&mut self.writer, &results.results)?;
+        writeln!(self.writer)?;
+        self.writer.flush()?;
+        Ok(None)
+    }
+}
+
+struct MarkdownOutputer {
+    index: usize,
+    root: PathBuf,
+    all: PathBuf,
+}
+impl MarkdownOutputer {
+    fn new(dir: &PathBuf) -> Result<Self, CvsSqlError> {
+        let all = create_root_file_in_dir(dir, "all.md")?;
+        let mut writer = File::create(&all)?;
+        write_markdown_row(&mut writer, ["index", "file", "sql"])?;
+        write_markdown_row(&mut writer, ["---", "---", "---"])?;
+
+        Ok(Self {
+            index: 0,
+            root: dir.clone(),
+            all,
+        })
+    }
+}
+impl Outputer for MarkdownOutputer {
+    fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
+        self.index += 1;
+        let file_name = format!("{}.md", self.index);
+        let path = self.root.join(&file_name);
+        let mut writer = BufWriter::new(File::create(&path)?);
+        write_markdown_results(&mut writer, &results.results)?;
+        writer.flush()?;
+
+        let mut writer = OpenOptions::new().append(true).open(&self.all)?;
+        let link = format!("[{file_name}]({file_name})");
+        write_markdown_row(&mut writer, [&self.index.to_string(), &link, &results.sql])?;
+
+        Ok(Some(format!(
+            "File {} created",
+            path.to_str().unwrap_or_default()
+        )))
+    }
+}
+
+fn write_markdown_results(writer: &mut impl Write, results: &ResultSet) -> Result<(), CvsSqlError> {
+    write_markdown_row(
+        writer,
+        results
+            .columns()
+            .map(|column| results.metadata.column_title(&column)),
+    )?;
+    write_markdown_row(writer, results.columns().map(|_| "---"))?;
+    for row in results.data.iter() {
+        write_markdown_row(
+            writer,
+            results.columns().map(|column| row.get(&column).to_string()),
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes a single row of a Markdown table. The pipes in the cells are escaped and the new lines
+/// are replaced by `<br>`, so every cell stays in its place.
+fn write_markdown_row<S: AsRef<str>>(
+    writer: &mut impl Write,
+    cells: impl IntoIterator<Item = S>,
+) -> Result<(), CvsSqlError> {
+    write!(writer, "|")?;
+    for cell in cells {
+        let cell = cell
+            .as_ref()
+            .replace('|', "\\|")
+            .replace("\r\n", "<br>")
+            .replace('\n', "<br>");
+        write!(writer, " {cell} |")?;
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+struct SqlStreamOutputer<W: Write> {
+    writer: W,
+}
+impl<W: Write> SqlStreamOutputer<W> {
+    fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+impl<W: Write> Outputer for SqlStreamOutputer<W> {
+    fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
+        write_sql_results(&mut self.writer, &results.results)?;
+        self.writer.flush()?;
+        Ok(None)
+    }
+}
+
+struct SqlOutputer {
+    index: usize,
+    root: PathBuf,
+}
+impl SqlOutputer {
+    fn new(dir: &PathBuf) -> Result<Self, CvsSqlError> {
+        if dir.exists() {
+            if dir.is_file() {
+                return Err(CvsSqlError::OutputCreationError(format!(
+                    "File {} is a file and can not be a directory",
+                    dir.to_str().unwrap_or_default()
+                )));
+            }
+        } else {
+            fs::create_dir_all(dir)?
+        }
+
+        Ok(Self {
+            index: 0,
+            root: dir.clone(),
+        })
+    }
+}
+impl Outputer for SqlOutputer {
+    fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
+        self.index += 1;
+        let file_name = format!("{}.sql", self.index);
+        let path = self.root.join(file_name);
+        let mut writer = BufWriter::new(File::create(&path)?);
+        write_sql_results(&mut writer, &results.results)?;
+        writer.flush()?;
+
+        Ok(Some(format!(
+            "File {} created",
+            path.to_str().unwrap_or_default()
+        )))
+    }
+}
+
+/// Writes an `INSERT` statement for every row. The table is named after the results (or
+/// `results` if they have no name).
+fn write_sql_results(writer: &mut impl Write, results: &ResultSet) -> Result<(), CvsSqlError> {
+    let table = results
+        .metadata
+        .result_name()
+        .map(|name| name.short_name())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("results");
+    let table = sql_identifier(table);
+    let columns = results
+        .columns()
+        .map(|column| sql_identifier(results.metadata.column_title(&column)))
+        .join(", ");
+    for row in results.data.iter() {
+        let values = results
+            .columns()
+            .map(|column| sql_value(row.get(&column)))
+            .join(", ");
+        writeln!(writer, "INSERT INTO {table} ({columns}) VALUES ({values});")?;
+    }
+    Ok(())
+}
+
+fn sql_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn sql_value(value: &Value) -> String {
+    match value {
+        Value::Empty => "NULL".to_string(),
+        Value::Bool(true) => "TRUE".to_string(),
+        Value::Bool(false) => "FALSE".to_string(),
+        Value::Number(_) => value.to_string(),
+        Value::Str(_) | Value::Date(_) | Value::Timestamp(_) => {
+            format!("'{}'", value.to_string().replace('\'', "''"))
+        }
+    }
+}
+
 struct HtmlOutputer {
     root: PathBuf,
     sqls: Vec<String>,
@@ -256,29 +878,7 @@ impl Outputer for HtmlOutputer {
         let path = self.root.join(file_name);
         let writer = File::create(&path)?;
         let mut writer = BufWriter::new(&writer);
-        writeln!(&mut writer, "<!DOCTYPE html>")?;
-        writeln!(&mut writer, "<html lang='en'>")?;
-        writeln!(&mut writer, "<head></head>")?;
-        writeln!(&mut writer, "<body>")?;
-        writeln!(&mut writer, "<table style=\"width:100%\">")?;
-        writeln!(&mut writer, "<tr>")?;
-        for col in results.results.columns() {
-            let name = results.results.metadata.column_title(&col);
-            writeln!(&mut writer, "<th>{}</th>", html_escape::encode_text(name))?
-        }
-        writeln!(&mut writer, "</tr>")?;
-        for row in results.results.data.iter() {
-            writeln!(&mut writer, "<tr>")?;
-            for col in results.results.columns() {
-                let data = row.get(&col).to_string();
-                writeln!(&mut writer, "<td>{}</td>", html_escape::encode_text(&data))?
-            }
-            writeln!(&mut writer, "</tr>")?;
-        }
-
-        writeln!(&mut writer, "</table>")?;
-        writeln!(&mut writer, "</body>")?;
-        writeln!(&mut writer, "</html>")?;
+        write_html_results(&mut writer, &results.results)?;
         self.sqls.push(results.sql.clone());
 
         self.update_index()?;
@@ -289,12 +889,40 @@ impl Outputer for HtmlOutputer {
     }
 }
 
+fn write_html_results(writer: &mut impl Write, results: &ResultSet) -> Result<(), CvsSqlError> {
+    writeln!(writer, "<!DOCTYPE html>")?;
+    writeln!(writer, "<html lang='en'>")?;
+    writeln!(writer, "<head></head>")?;
+    writeln!(writer, "<body>")?;
+    writeln!(writer, "<table style=\"width:100%\">")?;
+    writeln!(writer, "<tr>")?;
+    for col in results.columns() {
+        let name = results.metadata.column_title(&col);
+        writeln!(writer, "<th>{}</th>", html_escape::encode_text(name))?
+    }
+    writeln!(writer, "</tr>")?;
+    for row in results.data.iter() {
+        writeln!(writer, "<tr>")?;
+        for col in results.columns() {
+            let data = row.get(&col).to_string();
+            writeln!(writer, "<td>{}</td>", html_escape::encode_text(&data))?
+        }
+        writeln!(writer, "</tr>")?;
+    }
+
+    writeln!(writer, "</table>")?;
+    writeln!(writer, "</body>")?;
+    writeln!(writer, "</html>")?;
+    Ok(())
+}
+
 struct JsonOutputer {
     index: usize,
     root: PathBuf,
+    gzip: bool,
 }
 impl JsonOutputer {
-    fn new(dir: &PathBuf) -> Result<Self, CvsSqlError> {
+    fn new(dir: &PathBuf, gzip: bool) -> Result<Self, CvsSqlError> {
         if dir.exists() {
             if dir.is_file() {
                 return Err(CvsSqlError::OutputCreationError(format!(
@@ -309,49 +937,85 @@ impl JsonOutputer {
         Ok(Self {
             index: 0,
             root: dir.clone(),
+            gzip,
         })
     }
 }
 impl Outputer for JsonOutputer {
     fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
-        let mut data_to_write = vec![];
-        for row in results.results.data.iter() {
-            let mut line = Map::new();
-            for col in results.results.columns() {
-                let name = results.results.metadata.column_title(&col);
-                if !line.contains_key(name) {
-                    let data = row.get(&col);
-                    let data = match data {
-                        Value::Empty => JsonValue::Null,
-                        Value::Bool(b) => JsonValue::Bool(*b),
-                        Value::Number(num) => match Number::from_str(&num.to_string()) {
-                            Ok(num) => JsonValue::Number(num),
-                            Err(_) => JsonValue::String(data.to_string()),
-                        },
-                        _ => JsonValue::String(data.to_string()),
-                    };
-                    line.insert(name.to_string(), data);
-                }
-            }
-            data_to_write.push(JsonValue::Object(line));
-        }
+        let data_to_write = results_as_json(&results.results);
 
         self.index += 1;
-        let file_name = format!("{}.json", self.index);
+        let file_name = OutputFile::name(self.index, "json", self.gzip);
         let path = self.root.join(file_name);
-        let writer = File::create(&path)?;
+        let mut file = OutputFile::create(&path, self.gzip)?;
         let mut data_with_sql = HashMap::new();
         data_with_sql.insert("sql", JsonValue::String(results.sql.to_string()));
-        data_with_sql.insert("results", JsonValue::Array(data_to_write));
-        match serde_json::to_writer_pretty(writer, &data_with_sql) {
-            Ok(_) => Ok(Some(format!(
-                "File {} created",
-                path.to_str().unwrap_or_default()
-            ))),
-            Err(e) => Err(CvsSqlError::OutputCreationError(format!(
+        data_with_sql.insert("results", data_to_write);
+        if let Err(e) = serde_json::to_writer_pretty(&mut file, &data_with_sql) {
+            return Err(CvsSqlError::OutputCreationError(format!(
                 "Can not write json: {e}"
-            ))),
+            )));
         }
+        file.finish()?;
+        Ok(Some(format!(
+            "File {} created",
+            path.to_str().unwrap_or_default()
+        )))
+    }
+}
+
+fn results_as_json(results: &ResultSet) -> JsonValue {
+    let mut data_to_write = vec![];
+    for row in results.data.iter() {
+        let line = row_as_json(
+            results
+                .columns()
+                .map(|col| (results.metadata.column_title(&col), row.get(&col))),
+        );
+        data_to_write.push(line);
+    }
+    JsonValue::Array(data_to_write)
+}
+
+pub(crate) fn row_as_json<'a>(columns: impl Iterator<Item = (&'a str, &'a Value)>) -> JsonValue {
+    let mut line = Map::new();
+    for (name, data) in columns {
+        if !line.contains_key(name) {
+            line.insert(name.to_string(), value_as_json(data));
+        }
+    }
+    JsonValue::Object(line)
+}
+
+pub(crate) fn value_as_json(data: &Value) -> JsonValue {
+    match data {
+        Value::Empty => JsonValue::Null,
+        Value::Bool(b) => JsonValue::Bool(*b),
+        Value::Number(num) => {
+            if num.is_integer() {
+                if let Some(num) = num.to_i64() {
+                    return JsonValue::Number(num.into());
+                }
+                if let Some(num) = num.to_u64() {
+                    return JsonValue::Number(num.into());
+                }
+            }
+            match exact_f64(num).and_then(Number::from_f64) {
+                Some(num) => JsonValue::Number(num),
+                None => JsonValue::String(data.to_string()),
+            }
+        }
+        _ => JsonValue::String(data.to_string()),
+    }
+}
+
+/// Returns the number as `f64` only if it can be converted back without losing precision.
+fn exact_f64(num: &BigDecimal) -> Option<f64> {
+    let float = num.to_f64()?;
+    match BigDecimal::from_str(&float.to_string()) {
+        Ok(back) if &back == num => Some(float),
+        _ => None,
     }
 }
 
@@ -421,7 +1085,7 @@ impl XlsxOutputer {
                     Value::Bool(b) => {
                         worksheet.write_boolean(row, col, *b)?;
                     }
-                    Value::Number(num) => match num.to_f64() {
+                    Value::Number(num) => match exact_f64(num) {
                         Some(num) => {
                             worksheet.write_number(row, col, num)?;
                         }
@@ -462,60 +1126,471 @@ impl XlsxOutputer {
             .worksheets_mut()
             .swap((index - 1) as usize, index as usize);
 
-        self.workbook.save(&self.path)?;
+        self.workbook.save(&self.path)?;
+        Ok(())
+    }
+}
+impl Outputer for XlsxOutputer {
+    fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
+        match self.add_worksheet(results) {
+            Ok(_) => Ok(Some(format!(
+                "Sheet was added to {}",
+                self.path.to_str().unwrap_or_default()
+            ))),
+            Err(err) => Err(CvsSqlError::OutputCreationError(format!(
+                "Xlsx error: {err}"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::path::Path;
+
+    use bigdecimal::{BigDecimal, FromPrimitive};
+    use calamine::{Data, Reader as XlsxReader, Xlsx, open_workbook};
+    use csv::{Reader, ReaderBuilder};
+    use flate2::read::GzDecoder;
+    use scraper::Html;
+    use sqlparser::ast::{Expr, SetExpr, Statement, Value as AstValue};
+    use sqlparser::dialect::GenericDialect;
+    use sqlparser::parser::Parser;
+    use std::io::Read;
+    use tempfile::{NamedTempFile, tempdir};
+
+    use crate::engine::Engine;
+    use crate::streaming::stream_statement;
+
+    use super::*;
+
+    fn run_commands_of_path(
+        path: PathBuf,
+        commands: &str,
+        format: OutputFormat,
+    ) -> Result<Vec<CommandExecution>, CvsSqlError> {
+        let args = Args {
+            output_format: format,
+            output: Some(path),
+            ..Args::default()
+        };
+        let mut outputer = create_outputer(&args)?;
+        let engine = Engine::try_from(&args)?;
+        let results = engine.execute_commands(commands)?;
+        for results in &results {
+            outputer.write(results)?;
+        }
+        Ok(results)
+    }
+
+    fn verify_csv(result: &ResultSet, path: &PathBuf) -> Result<(), CvsSqlError> {
+        let mut reader = Reader::from_path(path)?;
+        let headers = reader.headers()?;
+        for col in result.columns() {
+            let expected_header = result.metadata.column_title(&col);
+            let actual_header = headers.get(col.get_index()).unwrap_or_default();
+            assert_eq!(actual_header, expected_header);
+        }
+
+        for (index, line) in reader.records().enumerate() {
+            let actual = line?;
+            let expected = result.data.get(index).unwrap();
+
+            for col in result.columns() {
+                let actual_data = actual.get(col.get_index()).unwrap();
+                let expected_data = expected.get(&col).to_string();
+                assert_eq!(actual_data, expected_data);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn csv_outputter_test() -> Result<(), CvsSqlError> {
+        let temp_dir = tempdir()?;
+        let results = run_commands_of_path(
+            temp_dir.path().to_path_buf(),
+            "SELECT * FROM tests.data.artists; 
+            SELECT COUNT(*) FROM tests.data.artists;
+            SELECT id, price, \"delivered at\"  FROM tests.data.sales WHERE \"tax percentage\" > 10 AND \"tax percentage\" < 12; "            , 
+            OutputFormat::Csv,
+        )?;
+        for (index, results) in results.iter().enumerate() {
+            let file = temp_dir.path().join(format!("{}.csv", index + 1));
+            verify_csv(&results.results, &file)?;
+        }
+
+        let all = temp_dir.path().join("all.csv");
+        let mut reader = Reader::from_path(all)?;
+        let headers = reader.headers()?;
+        assert_eq!(&headers[0], "index");
+        assert_eq!(&headers[1], "file");
+        assert_eq!(&headers[2], "sql");
+        for (index, line) in reader.records().enumerate() {
+            let actual = line?;
+            assert_eq!(actual[0], format!("{}", index + 1));
+            assert_eq!(actual[1], format!("{}.csv", index + 1));
+            assert_eq!(actual[2], results[index].sql);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn csv_outputter_typed_header() -> Result<(), CvsSqlError> {
+        let temp_dir = tempdir()?;
+        let args = Args {
+            output: Some(temp_dir.path().to_path_buf()),
+            typed_header: true,
+            ..Args::default()
+        };
+        let mut outputer = create_outputer(&args)?;
+        let engine = Engine::try_from(&args)?;
+        for results in engine.execute_commands("SELECT * FROM tests.data.customers")? {
+            outputer.write(&results)?;
+        }
+
+        let mut reader = Reader::from_path(temp_dir.path().join("1.csv"))?;
+        let headers = reader.headers()?;
+        assert_eq!(&headers[0], "id");
+        let mut records = reader.records();
+        let types = records.next().unwrap()?;
+        assert_eq!(
+            types.iter().collect::<Vec<_>>(),
+            vec![
+                "number",
+                "string",
+                "string",
+                "string",
+                "string",
+                "bool",
+                "timestamp"
+            ]
+        );
+        assert_ne!(&records.next().unwrap()?[0], "number");
+
+        Ok(())
+    }
+
+    #[test]
+    fn csv_outputter_with_delimiter() -> Result<(), CvsSqlError> {
+        let temp_dir = tempdir()?;
+        let args = Args {
+            output: Some(temp_dir.path().to_path_buf()),
+            delimiter: Some(';'),
+            ..Args::default()
+        };
+        let mut outputer = create_outputer(&args)?;
+        let engine = Engine::try_from(&args)?;
+        let sql = "SELECT * FROM tests.data.customers";
+        let results = engine.execute_commands(sql)?;
+        for results in &results {
+            outputer.write(results)?;
+        }
+
+        let results = &results[0].results;
+        let mut reader = ReaderBuilder::new()
+            .delimiter(b';')
+            .from_path(temp_dir.path().join("1.csv"))?;
+        let headers = reader.headers()?.clone();
+        assert_eq!(headers.len(), results.metadata.number_of_columns());
+        for col in results.columns() {
+            assert_eq!(
+                &headers[col.get_index()],
+                results.metadata.column_title(&col)
+            );
+        }
+        for (index, line) in reader.records().enumerate() {
+            let actual = line?;
+            let expected = results.data.get(index).unwrap();
+            for col in results.columns() {
+                assert_eq!(&actual[col.get_index()], expected.get(&col).to_string());
+            }
+        }
+        let all = fs::read_to_string(temp_dir.path().join("all.csv"))?;
+        assert!(all.starts_with("index;file;sql\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn csv_outputter_with_multi_byte_delimiter() -> Result<(), CvsSqlError> {
+        let temp_dir = tempdir()?;
+        let args = Args {
+            output: Some(temp_dir.path().to_path_buf()),
+            delimiter: Some('€'),
+            ..Args::default()
+        };
+
+        let outputer = create_outputer(&args);
+
+        assert!(matches!(outputer, Err(CvsSqlError::OutputCreationError(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn outputters_with_null_string() -> Result<(), CvsSqlError> {
+        let sql = "SELECT id, \"delivered at\" FROM tests.data.sales";
+        for format in [OutputFormat::Csv, OutputFormat::Txt] {
+            let temp_dir = tempdir()?;
+            let args = Args {
+                output: Some(temp_dir.path().to_path_buf()),
+                output_format: format.clone(),
+                null_string: "\\N".to_string(),
+                ..Args::default()
+            };
+            let mut outputer = create_outputer(&args)?;
+            let engine = Engine::try_from(&args)?;
+            let results = engine.execute_commands(sql)?.remove(0);
+            outputer.write(&results)?;
+
+            let (file, delimiter) = match format {
+                OutputFormat::Csv => ("1.csv", b','),
+                _ => ("1.txt", b'\t'),
+            };
+            let mut reader = ReaderBuilder::new()
+                .delimiter(delimiter)
+                .from_path(temp_dir.path().join(file))?;
+            let mut nulls = 0;
+            for (line, expected) in reader.records().zip(results.results.data.iter()) {
+                let delivered_at = &line?[1];
+                match expected.get(&Column::from_index(1)) {
+                    Value::Empty => {
+                        nulls += 1;
+                        assert_eq!(delivered_at, "\\N");
+                    }
+                    value => assert_eq!(delivered_at, value.to_string()),
+                }
+            }
+            assert!(nulls > 0);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn outputters_with_gzip() -> Result<(), CvsSqlError> {
+        let sql = "SELECT * FROM tests.data.sales";
+        for (format, extension) in [(OutputFormat::Csv, "csv"), (OutputFormat::Json, "json")] {
+            let temp_dir = tempdir()?;
+            let args = Args {
+                output: Some(temp_dir.path().to_path_buf()),
+                output_format: format,
+                gzip: true,
+                ..Args::default()
+            };
+            let mut outputer = create_outputer(&args)?;
+            let engine = Engine::try_from(&args)?;
+            let results = engine.execute_commands(sql)?.remove(0);
+            outputer.write(&results)?;
+
+            let path = temp_dir.path().join(format!("1.{extension}.gz"));
+            let mut content = String::new();
+            GzDecoder::new(File::open(path)?).read_to_string(&mut content)?;
+            let results = &results.results;
+            if extension == "json" {
+                let json: JsonValue = serde_json::from_str(&content).unwrap();
+                assert_eq!(json["sql"], sql);
+                assert_eq!(json["results"], results_as_json(results));
+                continue;
+            }
+            let mut reader = Reader::from_reader(content.as_bytes());
+            let mut rows = 0;
+            for (line, expected) in reader.records().zip(results.data.iter()) {
+                let line = line?;
+                for col in results.columns() {
+                    assert_eq!(&line[col.get_index()], expected.get(&col).to_string());
+                }
+                rows += 1;
+            }
+            assert_eq!(rows, results.data.iter().count());
+            let all = fs::read_to_string(temp_dir.path().join("all.csv"))?;
+            assert!(all.contains("1.csv.gz"));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn gzip_only_for_supported_outputs() -> Result<(), CvsSqlError> {
+        let temp_dir = tempdir()?;
+        let args = Args {
+            output: Some(temp_dir.path().to_path_buf()),
+            output_format: OutputFormat::Html,
+            gzip: true,
+            ..Args::default()
+        };
+
+        let outputer = create_outputer(&args);
+
+        assert!(matches!(outputer, Err(CvsSqlError::OutputCreationError(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn gzip_only_with_output_directory() -> Result<(), CvsSqlError> {
+        let args = Args {
+            gzip: true,
+            ..Args::default()
+        };
+
+        let outputer = create_outputer(&args);
+
+        assert!(matches!(outputer, Err(CvsSqlError::OutputCreationError(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn csv_outputter_writes_streams() -> Result<(), CvsSqlError> {
+        let temp_dir = tempdir()?;
+        let args = Args {
+            output: Some(temp_dir.path().to_path_buf()),
+            ..Args::default()
+        };
+        let mut outputer = create_outputer(&args)?;
+        let engine = Engine::try_from(&args)?;
+        let sql = "SELECT name, artist_id FROM tests.data.artists WHERE artist_id > 1";
+        let statement = engine.parse(sql)?.remove(0);
+        let rows = stream_statement(&engine, &statement)?.unwrap();
+
+        outputer.write_stream(sql, rows)?;
+
+        let expected = engine.execute_commands(sql)?.remove(0).results;
+        verify_csv(&expected, &temp_dir.path().join("1.csv"))?;
+        let mut reader = Reader::from_path(temp_dir.path().join("all.csv"))?;
+        let saved = reader.records().next().unwrap()?;
+        assert_eq!(&saved[1], "1.csv");
+        assert_eq!(&saved[2], sql);
+
+        Ok(())
+    }
+
+    #[test]
+    fn csv_outputter_removes_a_failed_stream() -> Result<(), CvsSqlError> {
+        let temp_dir = tempdir()?;
+        let home = tempdir()?;
+        fs::write(home.path().join("broken.csv"), b"a,b\n1,2\n\xff\xfe,3\n")?;
+        let args = Args {
+            output: Some(temp_dir.path().to_path_buf()),
+            home: Some(home.path().to_path_buf()),
+            ..Args::default()
+        };
+        let mut outputer = create_outputer(&args)?;
+        let engine = Engine::try_from(&args)?;
+        let sql = "SELECT a, b FROM broken";
+        let statement = engine.parse(sql)?.remove(0);
+        let rows = stream_statement(&engine, &statement)?.unwrap();
+
+        assert!(outputer.write_stream(sql, rows).is_err());
+
+        let mut files: Vec<_> = fs::read_dir(temp_dir.path())?
+            .map(|entry| entry.map(|entry| entry.file_name()))
+            .collect::<Result<_, _>>()?;
+        files.sort();
+        assert_eq!(files, vec!["all.csv"]);
+        let mut reader = Reader::from_path(temp_dir.path().join("all.csv"))?;
+        assert!(reader.records().next().is_none());
+
+        Ok(())
+    }
+
+    fn scalar_output(commands: &str) -> Result<Vec<Option<String>>, CvsSqlError> {
+        let args = Args {
+            scalar: true,
+            ..Args::default()
+        };
+        let mut outputer = create_outputer(&args)?;
+        let engine = Engine::try_from(&args)?;
+        let mut outputs = vec![];
+        for results in engine.execute_commands(commands)? {
+            outputs.push(outputer.write(&results)?);
+        }
+        Ok(outputs)
+    }
+
+    #[test]
+    fn scalar_outputter_test() -> Result<(), CvsSqlError> {
+        let outputs = scalar_output(
+            "SELECT COUNT(*) FROM tests.data.artists;
+            SELECT name FROM tests.data.artists WHERE artist_id = 2;",
+        )?;
+
+        assert_eq!(
+            outputs,
+            vec![Some("4".to_string()), Some("Aerosmith".to_string())]
+        );
+
         Ok(())
     }
-}
-impl Outputer for XlsxOutputer {
-    fn write(&mut self, results: &CommandExecution) -> Result<Option<String>, CvsSqlError> {
-        match self.add_worksheet(results) {
-            Ok(_) => Ok(Some(format!(
-                "Sheet was added to {}",
-                self.path.to_str().unwrap_or_default()
-            ))),
-            Err(err) => Err(CvsSqlError::OutputCreationError(format!(
-                "Xlsx error: {err}"
-            ))),
-        }
+
+    #[test]
+    fn scalar_outputter_multiple_columns() -> Result<(), CvsSqlError> {
+        let outputs = scalar_output("SELECT * FROM tests.data.artists WHERE artist_id = 2");
+
+        assert!(matches!(outputs, Err(CvsSqlError::NotAScalar(1, 2))));
+
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
+    #[test]
+    fn scalar_outputter_multiple_rows() -> Result<(), CvsSqlError> {
+        let outputs = scalar_output("SELECT name FROM tests.data.artists LIMIT 3");
 
-    use std::path::Path;
+        assert!(matches!(outputs, Err(CvsSqlError::NotAScalar(3, 1))));
 
-    use bigdecimal::{BigDecimal, FromPrimitive};
-    use calamine::{Data, Reader as XlsxReader, Xlsx, open_workbook};
-    use csv::{Reader, ReaderBuilder};
-    use scraper::Html;
-    use tempfile::{NamedTempFile, tempdir};
+        Ok(())
+    }
 
-    use crate::{engine::Engine, results::ResultSet};
+    #[test]
+    fn scalar_outputter_no_rows() -> Result<(), CvsSqlError> {
+        let outputs = scalar_output("SELECT name FROM tests.data.artists WHERE artist_id < 0");
 
-    use super::*;
+        assert!(matches!(outputs, Err(CvsSqlError::NotAScalar(0, 1))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn query_csv_outputs() -> Result<(), CvsSqlError> {
+        let temp_dir = tempdir()?;
+        run_commands_of_path(
+            temp_dir.path().to_path_buf(),
+            "SELECT * FROM tests.data.artists;
+            SELECT \"customer id\", COUNT(*) AS sales FROM tests.data.sales GROUP BY \"customer id\";",
+            OutputFormat::Csv,
+        )?;
 
-    fn run_commands_of_path(
-        path: PathBuf,
-        commands: &str,
-        format: OutputFormat,
-    ) -> Result<Vec<CommandExecution>, CvsSqlError> {
         let args = Args {
-            output_format: format,
-            output: Some(path),
+            home: Some(temp_dir.path().to_path_buf()),
             ..Args::default()
         };
-        let mut outputer = create_outputer(&args)?;
         let engine = Engine::try_from(&args)?;
-        let results = engine.execute_commands(commands)?;
-        for results in &results {
-            outputer.write(results)?;
-        }
-        Ok(results)
+        let results = engine.execute_commands(
+            "SELECT SUM(sales) FROM \"2\";
+            SELECT file FROM \"all\" WHERE index = 1;",
+        )?;
+
+        let sum = results[0].results.data.iter().next().unwrap();
+        assert_eq!(
+            sum.get(&Column::from_index(0)),
+            &Value::Number(BigDecimal::from_u8(40).unwrap())
+        );
+        let file = results[1].results.data.iter().next().unwrap();
+        assert_eq!(
+            file.get(&Column::from_index(0)),
+            &Value::Str("1.csv".to_string())
+        );
+
+        Ok(())
     }
 
-    fn verify_csv(result: &ResultSet, path: &PathBuf) -> Result<(), CvsSqlError> {
-        let mut reader = Reader::from_path(path)?;
+    fn verify_txt(result: &ResultSet, path: &PathBuf) -> Result<(), CvsSqlError> {
+        let mut reader = ReaderBuilder::new().delimiter(b'\t').from_path(path)?;
         let headers = reader.headers()?;
         for col in result.columns() {
             let expected_header = result.metadata.column_title(&col);
@@ -538,22 +1613,23 @@ mod tests {
     }
 
     #[test]
-    fn csv_outputter_test() -> Result<(), CvsSqlError> {
+    fn txt_outputter_test() -> Result<(), CvsSqlError> {
         let temp_dir = tempdir()?;
         let results = run_commands_of_path(
             temp_dir.path().to_path_buf(),
             "SELECT * FROM tests.data.artists; 
             SELECT COUNT(*) FROM tests.data.artists;
             SELECT id, price, \"delivered at\"  FROM tests.data.sales WHERE \"tax percentage\" > 10 AND \"tax percentage\" < 12; "            , 
-            OutputFormat::Csv,
+            OutputFormat::Txt,
         )?;
         for (index, results) in results.iter().enumerate() {
-            let file = temp_dir.path().join(format!("{}.csv", index + 1));
-            verify_csv(&results.results, &file)?;
+            let file = temp_dir.path().join(format!("{}.txt", index + 1));
+            verify_txt(&results.results, &file)?;
         }
 
-        let all = temp_dir.path().join("all.csv");
-        let mut reader = Reader::from_path(all)?;
+        let all = temp_dir.path().join("all.txt");
+        let mut reader = ReaderBuilder::new().delimiter(b'\t').from_path(&all)?;
+
         let headers = reader.headers()?;
         assert_eq!(&headers[0], "index");
         assert_eq!(&headers[1], "file");
@@ -561,64 +1637,153 @@ mod tests {
         for (index, line) in reader.records().enumerate() {
             let actual = line?;
             assert_eq!(actual[0], format!("{}", index + 1));
-            assert_eq!(actual[1], format!("{}.csv", index + 1));
+            assert_eq!(actual[1], format!("{}.txt", index + 1));
             assert_eq!(actual[2], results[index].sql);
         }
 
         Ok(())
     }
 
-    fn verify_txt(result: &ResultSet, path: &PathBuf) -> Result<(), CvsSqlError> {
-        let mut reader = ReaderBuilder::new().delimiter(b'\t').from_path(path)?;
-        let headers = reader.headers()?;
-        for col in result.columns() {
-            let expected_header = result.metadata.column_title(&col);
-            let actual_header = headers.get(col.get_index()).unwrap_or_default();
-            assert_eq!(actual_header, expected_header);
+    fn parse_markdown_row(line: &str) -> Vec<String> {
+        let line = line
+            .strip_prefix("| ")
+            .and_then(|line| line.strip_suffix(" |"))
+            .unwrap();
+        let mut cells = vec![];
+        let mut cell = String::new();
+        let mut chars = line.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' if chars.peek() == Some(&'|') => {
+                    cell.push('|');
+                    chars.next();
+                }
+                '|' => {
+                    cells.push(cell.trim().to_string());
+                    cell.clear();
+                }
+                _ => cell.push(ch),
+            }
         }
+        cells.push(cell.trim().to_string());
+        cells
+    }
 
-        for (index, line) in reader.records().enumerate() {
-            let actual = line?;
-            let expected = result.data.get(index).unwrap();
+    fn verify_markdown(result: &ResultSet, path: &PathBuf) -> Result<(), CvsSqlError> {
+        let markdown = fs::read_to_string(path)?;
+        let mut lines = markdown.lines();
+        let headers = parse_markdown_row(lines.next().unwrap());
+        let expected_headers: Vec<_> = result
+            .columns()
+            .map(|col| result.metadata.column_title(&col).to_string())
+            .collect();
+        assert_eq!(headers, expected_headers);
+        let separators = parse_markdown_row(lines.next().unwrap());
+        assert_eq!(separators, vec!["---"; result.metadata.number_of_columns()]);
 
-            for col in result.columns() {
-                let actual_data = actual.get(col.get_index()).unwrap();
-                let expected_data = expected.get(&col).to_string();
-                assert_eq!(actual_data, expected_data);
-            }
+        for expected in result.data.iter() {
+            let actual = parse_markdown_row(lines.next().unwrap());
+            let expected: Vec<_> = result
+                .columns()
+                .map(|col| expected.get(&col).to_string())
+                .collect();
+            assert_eq!(actual, expected);
         }
+        assert!(lines.next().is_none());
 
         Ok(())
     }
 
     #[test]
-    fn txt_outputter_test() -> Result<(), CvsSqlError> {
+    fn markdown_outputter_test() -> Result<(), CvsSqlError> {
         let temp_dir = tempdir()?;
         let results = run_commands_of_path(
             temp_dir.path().to_path_buf(),
-            "SELECT * FROM tests.data.artists; 
+            "SELECT * FROM tests.data.artists;
             SELECT COUNT(*) FROM tests.data.artists;
-            SELECT id, price, \"delivered at\"  FROM tests.data.sales WHERE \"tax percentage\" > 10 AND \"tax percentage\" < 12; "            , 
-            OutputFormat::Txt,
+            SELECT name, CONCAT(name, ' | ', artist_id) AS \"name | id\" FROM tests.data.artists;",
+            OutputFormat::Markdown,
         )?;
         for (index, results) in results.iter().enumerate() {
-            let file = temp_dir.path().join(format!("{}.txt", index + 1));
-            verify_txt(&results.results, &file)?;
+            let file = temp_dir.path().join(format!("{}.md", index + 1));
+            verify_markdown(&results.results, &file)?;
         }
+        let piped = fs::read_to_string(temp_dir.path().join("3.md"))?;
+        assert!(piped.starts_with("| name | name \\| id |"));
 
-        let all = temp_dir.path().join("all.txt");
-        let mut reader = ReaderBuilder::new().delimiter(b'\t').from_path(&all)?;
-
-        let headers = reader.headers()?;
-        assert_eq!(&headers[0], "index");
-        assert_eq!(&headers[1], "file");
-        assert_eq!(&headers[2], "sql");
-        for (index, line) in reader.records().enumerate() {
-            let actual = line?;
+        let all = fs::read_to_string(temp_dir.path().join("all.md"))?;
+        let mut lines = all.lines();
+        assert_eq!(
+            parse_markdown_row(lines.next().unwrap()),
+            vec!["index", "file", "sql"]
+        );
+        assert_eq!(
+            parse_markdown_row(lines.next().unwrap()),
+            vec!["---", "---", "---"]
+        );
+        for (index, results) in results.iter().enumerate() {
+            let actual = parse_markdown_row(lines.next().unwrap());
             assert_eq!(actual[0], format!("{}", index + 1));
-            assert_eq!(actual[1], format!("{}.txt", index + 1));
-            assert_eq!(actual[2], results[index].sql);
+            assert_eq!(actual[1], format!("[{0}.md]({0}.md)", index + 1));
+            assert_eq!(actual[2], results.sql);
         }
+        assert!(lines.next().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn sql_outputter_test() -> Result<(), CvsSqlError> {
+        let temp_dir = tempdir()?;
+        let results = run_commands_of_path(
+            temp_dir.path().to_path_buf(),
+            "SELECT * FROM tests.data.customers;
+            SELECT id, price, \"delivered at\" FROM tests.data.sales WHERE \"tax percentage\" > 10 AND \"tax percentage\" < 12;
+            SELECT name, 'it''s' AS \"quoted \"\"name\"\"\", NULL AS nothing, CAST('2024-02-01' AS DATE) AS date FROM tests.data.artists;",
+            OutputFormat::Sql,
+        )?;
+        for (index, results) in results.iter().enumerate() {
+            let results = &results.results;
+            let sql = fs::read_to_string(temp_dir.path().join(format!("{}.sql", index + 1)))?;
+            let statements = Parser::parse_sql(&GenericDialect {}, &sql).unwrap();
+            assert_eq!(statements.len(), results.data.iter().count());
+            for (statement, row) in statements.iter().zip(results.data.iter()) {
+                let Statement::Insert(insert) = statement else {
+                    panic!("{statement} is not an insert");
+                };
+                let columns: Vec<_> = insert.columns.iter().map(|c| c.value.clone()).collect();
+                let expected: Vec<_> = results
+                    .columns()
+                    .map(|col| results.metadata.column_title(&col).to_string())
+                    .collect();
+                assert_eq!(columns, expected);
+                let SetExpr::Values(values) = insert.source.as_ref().unwrap().body.as_ref() else {
+                    panic!("{statement} has no values");
+                };
+                for (expr, col) in values.rows[0].iter().zip(results.columns()) {
+                    let expected = row.get(&col);
+                    let Expr::Value(actual) = expr else {
+                        assert!(matches!(expected, Value::Number(_)));
+                        continue;
+                    };
+                    let actual = match &actual.value {
+                        AstValue::Null => Value::Empty,
+                        AstValue::Boolean(b) => Value::Bool(*b),
+                        AstValue::Number(num, _) => Value::Number(num.clone()),
+                        AstValue::SingleQuotedString(str) => {
+                            assert_eq!(str, &expected.to_string());
+                            continue;
+                        }
+                        _ => panic!("unexpected value {actual}"),
+                    };
+                    assert_eq!(&actual, expected);
+                }
+            }
+        }
+        let sql = fs::read_to_string(temp_dir.path().join("3.sql"))?;
+        assert!(sql.starts_with(
+            "INSERT INTO \"artists\" (\"name\", \"quoted \"\"name\"\"\", \"nothing\", \"date\") VALUES ('AC/DC', 'it''s', NULL, '2024-02-01');"
+        ));
 
         Ok(())
     }
@@ -795,6 +1960,198 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn json_stream_outputter_test() -> Result<(), CvsSqlError> {
+        let args = Args::default();
+        let engine = Engine::try_from(&args)?;
+        let results = engine.execute_commands(
+            "SELECT * FROM tests.data.artists ORDER BY artist_id LIMIT 2;
+            SELECT COUNT(*) AS count FROM tests.data.artists;",
+        )?;
+        let mut output = vec![];
+        let mut outputer = JsonStreamOutputer::new(&mut output);
+        for results in &results {
+            assert!(outputer.write(results)?.is_none());
+        }
+
+        let output = String::from_utf8(output).unwrap();
+        let jsons: Vec<JsonValue> = serde_json::Deserializer::from_str(&output)
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            jsons,
+            vec![
+                serde_json::json!([
+                    {"artist_id": 1, "name": "AC/DC"},
+                    {"artist_id": 2, "name": "Aerosmith"}
+                ]),
+                serde_json::json!([{"count": 4}]),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn json_keeps_large_numbers_exact() -> Result<(), CvsSqlError> {
+        let args = Args::default();
+        let engine = Engine::try_from(&args)?;
+        let results = engine.execute_commands(
+            "SELECT \"customer id\", price, 12345678901234567890123 AS big, 0.1234567890123456789 AS precise FROM tests.data.sales LIMIT 1",
+        )?;
+        let mut output = vec![];
+        let mut outputer = JsonStreamOutputer::new(&mut output);
+        outputer.write(results.first().unwrap())?;
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("\"customer id\": -8862786196595644070"));
+        let json: JsonValue = serde_json::from_str(&output).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "customer id": -8862786196595644070i64,
+                "price": 52.45,
+                "big": "12345678901234567890123",
+                "precise": "0.1234567890123456789"
+            }])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn html_stream_outputter_test() -> Result<(), CvsSqlError> {
+        let args = Args::default();
+        let engine = Engine::try_from(&args)?;
+        let results = engine.execute_commands("SELECT * FROM tests.data.artists LIMIT 1")?;
+        let mut output = vec![];
+        let mut outputer = HtmlStreamOutputer::new(&mut output);
+        outputer.write(results.first().unwrap())?;
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("<th>artist_id</th>"));
+        assert!(output.contains("<td>AC/DC</td>"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn txt_stream_outputter_test() -> Result<(), CvsSqlError> {
+        let args = Args::default();
+        let engine = Engine::try_from(&args)?;
+        let results = engine.execute_commands("SELECT * FROM tests.data.artists LIMIT 1")?;
+        let mut output = vec![];
+        let mut outputer = TxtStreamOutputer::new(&mut output, false, "");
+        outputer.write(results.first().unwrap())?;
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output, "artist_id\tname\n1\tAC/DC\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn txt_stream_outputter_typed_header() -> Result<(), CvsSqlError> {
+        let args = Args::default();
+        let engine = Engine::try_from(&args)?;
+        let results = engine.execute_commands(
+            "SELECT id, name, active, \"last modified\" FROM tests.data.customers LIMIT 1",
+        )?;
+        let mut output = vec![];
+        let mut outputer = TxtStreamOutputer::new(&mut output, true, "");
+        outputer.write(results.first().unwrap())?;
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines[0], "id\tname\tactive\tlast modified");
+        assert_eq!(lines[1], "number\tstring\tbool\ttimestamp");
+        assert_eq!(lines.len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn expanded_outputter_prints_a_column_per_line() -> Result<(), CvsSqlError> {
+        let args = Args::default();
+        let engine = Engine::try_from(&args)?;
+        let results = engine
+            .execute_commands("SELECT name, country, active FROM tests.data.customers LIMIT 1")?;
+        let mut output = vec![];
+        let mut outputer = ExpandedOutputer::new(&mut output);
+        outputer.write(results.first().unwrap())?;
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            output,
+            "name    | Amely Waelchi\ncountry | Andorra\nactive  | TRUE\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn expanded_outputter_numbers_the_records() -> Result<(), CvsSqlError> {
+        let args = Args::default();
+        let engine = Engine::try_from(&args)?;
+        let results = engine.execute_commands(
+            "SELECT artist_id AS id, name FROM tests.data.artists WHERE artist_id < 3",
+        )?;
+        let mut output = vec![];
+        let mut outputer = ExpandedOutputer::new(&mut output);
+        outputer.write(results.first().unwrap())?;
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines[0], "-[ RECORD 1 ]-");
+        assert_eq!(lines[1], "id   | 1");
+        assert_eq!(lines[3], "-[ RECORD 2 ]-");
+        assert_eq!(lines.len(), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn integers_round_trip_through_csv_and_json() -> Result<(), CvsSqlError> {
+        let args = Args {
+            integers: true,
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+        let results =
+            engine.execute_commands("SELECT id, id + 0.00 AS copy FROM tests.data.customers")?;
+        let content = fs::read_to_string("tests/data/customers.csv")?;
+        let ids: Vec<_> = content
+            .lines()
+            .skip(1)
+            .filter_map(|line| line.split(',').next())
+            .collect();
+
+        let mut output = vec![];
+        new_csv_writer(&mut output, true).write(&results.first().unwrap().results)?;
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<_> = output.lines().skip(1).collect();
+        let expected: Vec<_> = ids.iter().map(|id| format!("{id},{id}")).collect();
+        assert_eq!(lines, expected);
+
+        let mut output = vec![];
+        JsonStreamOutputer::new(&mut output).write(results.first().unwrap())?;
+        let json: JsonValue = serde_json::from_slice(&output)?;
+        let json_ids: Vec<_> = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| (row["id"].to_string(), row["copy"].to_string()))
+            .collect();
+        let expected: Vec<_> = ids
+            .iter()
+            .map(|id| (id.to_string(), id.to_string()))
+            .collect();
+        assert_eq!(json_ids, expected);
+
+        Ok(())
+    }
+
     #[test]
     fn excel_outputter_test() -> Result<(), CvsSqlError> {
         let temp_file = NamedTempFile::with_suffix(".xlsx")?;
@@ -872,4 +2229,72 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn excel_keeps_large_numbers_exact() -> Result<(), CvsSqlError> {
+        let temp_file = NamedTempFile::with_suffix(".xlsx")?;
+        run_commands_of_path(
+            temp_file.path().to_path_buf(),
+            "SELECT \"customer id\", price FROM tests.data.sales LIMIT 1",
+            OutputFormat::Xls,
+        )?;
+
+        let mut workbook: Xlsx<_> = open_workbook(temp_file.path()).unwrap();
+        let sheet = workbook.worksheet_range("Results 1").unwrap();
+        assert_eq!(
+            sheet.get_value((1, 0)),
+            Some(&Data::String("-8862786196595644070".to_string()))
+        );
+        assert_eq!(sheet.get_value((1, 1)), Some(&Data::Float(52.45)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn excel_output_can_be_queried() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        let results = run_commands_of_path(
+            working_dir.path().join("report.xlsx"),
+            "SELECT * FROM tests.data.sales ORDER BY id LIMIT 20",
+            OutputFormat::Xls,
+        )?;
+        let expected = &results.first().unwrap().results;
+
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+        let results = engine.execute_commands(
+            "SELECT * FROM \"report!Results 1\"; SELECT COUNT(*) FROM report; SELECT COUNT(*) FROM \"report!sqls\"",
+        )?;
+
+        let actual = &results.first().unwrap().results;
+        assert_eq!(
+            actual.metadata.number_of_columns(),
+            expected.metadata.number_of_columns()
+        );
+        for col in expected.columns() {
+            assert_eq!(
+                actual.metadata.column_title(&col),
+                expected.metadata.column_title(&col)
+            );
+        }
+        assert_eq!(actual.data.iter().count(), 20);
+        for (actual_row, expected_row) in actual.data.iter().zip(expected.data.iter()) {
+            for col in expected.columns() {
+                assert_eq!(actual_row.get(&col), expected_row.get(&col));
+            }
+        }
+
+        // Without a sheet name, the first sheet is read
+        let first_sheet = &results.get(1).unwrap().results;
+        let count = first_sheet.data.iter().next().unwrap();
+        assert_eq!(count.get(&Column::from_index(0)), &Value::Number(20.into()));
+        let sqls = &results.get(2).unwrap().results;
+        let count = sqls.data.iter().next().unwrap();
+        assert_eq!(count.get(&Column::from_index(0)), &Value::Number(1.into()));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,249 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Deref;
+use std::rc::Rc;
+
+use sqlparser::ast::{
+    Expr, ExprWithAlias, FunctionArg, FunctionArgExpr, FunctionArguments, PivotValueSource,
+    TableAlias, TableFactor,
+};
+
+use crate::{
+    engine::Engine,
+    error::CvsSqlError,
+    extractor::Extractor,
+    group_by::GroupRow,
+    named_results::alias_results,
+    projections::SingleConvert,
+    result_set_metadata::{Metadata, SimpleResultSetMetadata},
+    results::{Column, Name, ResultSet},
+    results_data::{DataRow, ResultsData},
+    value::Value,
+};
+
+pub(crate) struct Pivot<'a> {
+    pub(crate) table: &'a TableFactor,
+    pub(crate) aggregate_functions: &'a [ExprWithAlias],
+    pub(crate) value_column: &'a [Expr],
+    pub(crate) value_source: &'a PivotValueSource,
+    pub(crate) default_on_null: &'a Option<Expr>,
+    pub(crate) alias: &'a Option<TableAlias>,
+}
+
+impl Extractor for Pivot<'_> {
+    fn extract(&self, engine: &Engine) -> Result<ResultSet, CvsSqlError> {
+        let [value_column] = self.value_column else {
+            return Err(CvsSqlError::Unsupported(
+                "PIVOT with more than one value column".to_string(),
+            ));
+        };
+        let results = self.table.extract(engine)?;
+
+        let mut used_columns = HashSet::new();
+        used_columns.insert(column_of(value_column, &results.metadata)?.get_index());
+        for aggregation in self.aggregate_functions {
+            add_used_columns(&aggregation.expr, &results.metadata, &mut used_columns)?;
+        }
+        let group_columns: Vec<_> = results
+            .metadata
+            .columns()
+            .filter(|column| !used_columns.contains(&column.get_index()))
+            .collect();
+
+        let pivot = value_column.convert_single(&results.metadata, engine)?;
+        let mut keys = vec![];
+        let mut pivot_values = HashSet::new();
+        let mut groups: HashMap<Vec<Value>, HashMap<Value, Vec<GroupRow>>> = HashMap::new();
+        for data in results.data.into_iter() {
+            let row = GroupRow {
+                data,
+                group_rows: vec![],
+            };
+            let key: Vec<_> = group_columns
+                .iter()
+                .map(|column| row.data.get(column).clone())
+                .collect();
+            let pivot_value = pivot.get(&row).deref().clone();
+            pivot_values.insert(pivot_value.clone());
+            let group = groups.entry(key.clone()).or_insert_with(|| {
+                keys.push(key);
+                HashMap::new()
+            });
+            group.entry(pivot_value).or_default().push(row);
+        }
+        let values = self.pivot_values(engine, pivot_values)?;
+
+        let mut metadata = SimpleResultSetMetadata::new(results.metadata.result_name().cloned());
+        for column in &group_columns {
+            metadata.add_column(results.metadata.column_title(column));
+        }
+        let group_metadata = Rc::new(Metadata::Grouped {
+            parent: results.metadata.clone(),
+            this: Box::new(Metadata::Simple(metadata)),
+        });
+        let mut aggregations = vec![];
+        for aggregation in self.aggregate_functions {
+            let projection = aggregation.expr.convert_single(&group_metadata, engine)?;
+            aggregations.push((projection, aggregation.alias.as_ref()));
+        }
+        let default_on_null = match self.default_on_null {
+            Some(default) => Some(default.convert_single(&group_metadata, engine)?),
+            None => None,
+        };
+
+        let mut metadata = SimpleResultSetMetadata::new(results.metadata.result_name().cloned());
+        for column in &group_columns {
+            metadata.add_column(results.metadata.column_title(column));
+        }
+        for (value, alias) in &values {
+            let value_name = match alias {
+                Some(alias) => alias.clone(),
+                None => value.to_string(),
+            };
+            for (projection, aggregation_alias) in &aggregations {
+                if aggregations.len() == 1 {
+                    metadata.add_column(&value_name);
+                } else {
+                    let aggregation_name = match aggregation_alias {
+                        Some(alias) => alias.value.as_str(),
+                        None => projection.name(),
+                    };
+                    metadata.add_column(&format!("{value_name}_{aggregation_name}"));
+                }
+            }
+        }
+
+        let mut rows = vec![];
+        for key in keys {
+            let mut group = groups.remove(&key).unwrap_or_default();
+            let mut row = key.clone();
+            for (value, _) in &values {
+                let group_row = GroupRow {
+                    data: DataRow::new(key.clone()),
+                    group_rows: group.remove(value).unwrap_or_default(),
+                };
+                for (projection, _) in &aggregations {
+                    let mut result = projection.get(&group_row).deref().clone();
+                    if result.is_empty()
+                        && let Some(default) = &default_on_null
+                    {
+                        result = default.get(&group_row).deref().clone();
+                    }
+                    row.push(result);
+                }
+            }
+            rows.push(DataRow::new(row));
+        }
+
+        let results = ResultSet {
+            metadata: Rc::new(metadata.build()),
+            data: ResultsData::new(rows),
+        };
+        match self.alias {
+            Some(alias) => {
+                if !alias.columns.is_empty() {
+                    return Err(CvsSqlError::Unsupported(
+                        "PIVOT with column alias".to_string(),
+                    ));
+                }
+                Ok(alias_results(&alias.name, results))
+            }
+            None => Ok(results),
+        }
+    }
+}
+
+impl Pivot<'_> {
+    fn pivot_values(
+        &self,
+        engine: &Engine,
+        pivot_values: HashSet<Value>,
+    ) -> Result<Vec<(Value, Option<String>)>, CvsSqlError> {
+        match self.value_source {
+            PivotValueSource::List(list) => {
+                let metadata = Metadata::Simple(SimpleResultSetMetadata::new(None));
+                let temp_row = GroupRow {
+                    data: DataRow::new(vec![]),
+                    group_rows: vec![],
+                };
+                let mut values = vec![];
+                for item in list {
+                    let value = item.expr.convert_single(&metadata, engine)?;
+                    let value = value.get(&temp_row).deref().clone();
+                    values.push((value, item.alias.as_ref().map(|a| a.value.to_string())));
+                }
+                Ok(values)
+            }
+            PivotValueSource::Any(order_by) => {
+                if !order_by.is_empty() {
+                    return Err(CvsSqlError::Unsupported(
+                        "PIVOT with ANY ORDER BY".to_string(),
+                    ));
+                }
+                let mut values: Vec<_> = pivot_values.into_iter().collect();
+                values.sort();
+                Ok(values.into_iter().map(|value| (value, None)).collect())
+            }
+            PivotValueSource::Subquery(query) => {
+                let results = query.extract(engine)?;
+                let column = Column::from_index(0);
+                let mut seen = HashSet::new();
+                let mut values = vec![];
+                for row in results.data.iter() {
+                    let value = row.get(&column).clone();
+                    if seen.insert(value.clone()) {
+                        values.push((value, None));
+                    }
+                }
+                Ok(values)
+            }
+        }
+    }
+}
+
+fn column_of(expr: &Expr, metadata: &Metadata) -> Result<Column, CvsSqlError> {
+    let name: Name = match expr {
+        Expr::Identifier(ident) => ident.into(),
+        Expr::CompoundIdentifier(idents) => idents
+            .iter()
+            .map(|i| i.value.to_string())
+            .collect::<Vec<_>>()
+            .into(),
+        _ => {
+            return Err(CvsSqlError::Unsupported(format!(
+                "PIVOT with expression {expr}"
+            )));
+        }
+    };
+    Ok(metadata.column_index(&name)?.clone())
+}
+
+fn add_used_columns(
+    aggregation: &Expr,
+    metadata: &Metadata,
+    used_columns: &mut HashSet<usize>,
+) -> Result<(), CvsSqlError> {
+    let Expr::Function(func) = aggregation else {
+        return Err(CvsSqlError::Unsupported(format!(
+            "PIVOT with {aggregation}"
+        )));
+    };
+    let FunctionArguments::List(args) = &func.args else {
+        return Err(CvsSqlError::Unsupported(format!(
+            "PIVOT with {aggregation}"
+        )));
+    };
+    for arg in &args.args {
+        match arg {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => {
+                used_columns.insert(column_of(expr, metadata)?.get_index());
+            }
+            FunctionArg::Unnamed(FunctionArgExpr::Wildcard) => {}
+            _ => {
+                return Err(CvsSqlError::Unsupported(format!(
+                    "PIVOT with {aggregation}"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
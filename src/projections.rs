@@ -1,7 +1,8 @@
 use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
 use regex::Regex;
 use sqlparser::ast::{
-    BinaryOperator, CaseWhen, CeilFloorKind, DateTimeField, Expr, Query, SelectItem, UnaryOperator,
+    BinaryOperator, CaseWhen, CeilFloorKind, DateTimeField, Expr, FunctionArg, FunctionArgExpr,
+    FunctionArguments, Ident, Query, RenameSelectItem, SelectItem, TrimWhereField, UnaryOperator,
     WildcardAdditionalOptions,
 };
 
@@ -15,7 +16,7 @@ use crate::result_set_metadata::{Metadata, SimpleResultSetMetadata};
 use crate::results_data::{DataRow, ResultsData};
 use crate::util::SmartReference;
 use crate::{
-    results::{Column, Name, ResultSet},
+    results::{Column, ColumnIndexError, Name, ResultSet},
     value::Value,
 };
 use itertools::Itertools;
@@ -46,14 +47,10 @@ pub fn make_projection(
     parent: GroupedResultSet,
     items: &[SelectItem],
 ) -> Result<ResultSet, CvsSqlError> {
-    let mut projections = Vec::new();
+    let projections = select_item_projections(engine, &parent.metadata, items)?;
     let mut metadata = SimpleResultSetMetadata::new(parent.metadata.result_name().cloned());
-    for item in items {
-        let mut items = item.convert(&parent.metadata, engine)?;
-        for i in &items {
-            metadata.add_column(i.name());
-        }
-        projections.append(&mut items);
+    for projection in &projections {
+        metadata.add_column(projection.name());
     }
     let metadata = metadata.build();
     let mut data = Vec::new();
@@ -70,6 +67,17 @@ pub fn make_projection(
     let metadata = Rc::new(metadata);
     Ok(ResultSet { metadata, data })
 }
+pub(crate) fn select_item_projections(
+    engine: &Engine,
+    metadata: &Metadata,
+    items: &[SelectItem],
+) -> Result<Vec<Box<dyn Projection>>, CvsSqlError> {
+    let mut projections = Vec::new();
+    for item in items {
+        projections.append(&mut item.convert(metadata, engine)?);
+    }
+    Ok(projections)
+}
 trait Convert {
     fn convert(
         &self,
@@ -85,7 +93,13 @@ impl Convert for SelectItem {
     ) -> Result<Vec<Box<dyn Projection>>, CvsSqlError> {
         match self {
             SelectItem::Wildcard(options) => options.convert(metadata, engine),
-            SelectItem::UnnamedExpr(exp) => exp.convert(metadata, engine),
+            SelectItem::UnnamedExpr(exp) => match expand_columns(exp, metadata)? {
+                Some(exprs) => exprs
+                    .iter()
+                    .map(|exp| exp.convert_single(metadata, engine))
+                    .collect(),
+                None => exp.convert(metadata, engine),
+            },
             SelectItem::ExprWithAlias { expr, alias } => {
                 let data = expr.convert_single(metadata, engine)?;
                 let alias = alias.value.to_string();
@@ -97,6 +111,74 @@ impl Convert for SelectItem {
         }
     }
 }
+fn columns_regex(expr: &Expr) -> Result<Option<Regex>, CvsSqlError> {
+    let Expr::Function(func) = expr else {
+        return Ok(None);
+    };
+    if func.name.to_string().to_uppercase() != "COLUMNS" {
+        return Ok(None);
+    }
+    let FunctionArguments::List(args) = &func.args else {
+        return Err(CvsSqlError::Unsupported(format!("{expr}")));
+    };
+    let [FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(pattern)))] = args.args.as_slice()
+    else {
+        return Err(CvsSqlError::Unsupported(format!("{expr}")));
+    };
+    let Some(pattern) = pattern.clone().into_string() else {
+        return Err(CvsSqlError::Unsupported(format!("{expr}")));
+    };
+    match Regex::new(&pattern) {
+        Ok(regex) => Ok(Some(regex)),
+        Err(e) => Err(CvsSqlError::InvalidRegex(e.to_string())),
+    }
+}
+
+fn matching_columns(regex: &Regex, metadata: &Metadata) -> Vec<Expr> {
+    metadata
+        .columns()
+        .filter_map(|column| metadata.column_name(&column))
+        .filter(|name| regex.is_match(name.short_name()))
+        .map(|name| Expr::CompoundIdentifier(name.elements().iter().map(Ident::new).collect()))
+        .collect()
+}
+
+fn expand_columns(expr: &Expr, metadata: &Metadata) -> Result<Option<Vec<Expr>>, CvsSqlError> {
+    if let Some(regex) = columns_regex(expr)? {
+        return Ok(Some(matching_columns(&regex, metadata)));
+    }
+    let Expr::Function(func) = expr else {
+        return Ok(None);
+    };
+    let FunctionArguments::List(args) = &func.args else {
+        return Ok(None);
+    };
+    for (index, arg) in args.args.iter().enumerate() {
+        let FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)) = arg else {
+            continue;
+        };
+        let Some(regex) = columns_regex(arg)? else {
+            continue;
+        };
+        let source = match metadata {
+            Metadata::Grouped { parent, this: _ } => parent,
+            _ => metadata,
+        };
+        let exprs = matching_columns(&regex, source)
+            .into_iter()
+            .map(|column| {
+                let mut func = func.clone();
+                if let FunctionArguments::List(args) = &mut func.args {
+                    args.args[index] = FunctionArg::Unnamed(FunctionArgExpr::Expr(column));
+                }
+                Expr::Function(func)
+            })
+            .collect();
+        return Ok(Some(exprs));
+    }
+    Ok(None)
+}
+
 impl Convert for WildcardAdditionalOptions {
     fn convert(
         &self,
@@ -115,17 +197,31 @@ impl Convert for WildcardAdditionalOptions {
         if self.opt_replace.is_some() {
             return Err(CvsSqlError::Unsupported("Select * REPLACE".into()));
         }
-        if self.opt_rename.is_some() {
-            return Err(CvsSqlError::Unsupported("Select * RENAME".into()));
+        let renames = match &self.opt_rename {
+            None => vec![],
+            Some(RenameSelectItem::Single(rename)) => vec![rename],
+            Some(RenameSelectItem::Multiple(renames)) => renames.iter().collect(),
+        };
+        let mut new_names = Vec::new();
+        for rename in renames {
+            let name: Name = (&rename.ident).into();
+            let column = metadata.column_index(&name)?;
+            new_names.push((column.get_index(), rename.alias.value.to_string()));
         }
         let mut projections: Vec<Box<dyn Projection>> = Vec::new();
-        for column in metadata.columns() {
+        for column in metadata.visible_columns() {
             let Some(column_name) = metadata.column_name(&column) else {
                 return Err(CvsSqlError::Unsupported(
                     "Select * with unnamed column".into(),
                 ));
             };
-            let column_name = column_name.short_name().to_string();
+            let column_name = match new_names
+                .iter()
+                .find(|(index, _)| *index == column.get_index())
+            {
+                Some((_, new_name)) => new_name.clone(),
+                None => column_name.short_name().to_string(),
+            };
             projections.push(Box::new(ColumnProjection {
                 column,
                 column_name,
@@ -191,6 +287,25 @@ impl BinaryFunction for Divide {
         "/"
     }
 }
+struct IntegerDivide {}
+impl BinaryFunction for IntegerDivide {
+    fn calculate<'a>(
+        &'a self,
+        left: SmartReference<Value>,
+        right: SmartReference<Value>,
+    ) -> SmartReference<'a, Value> {
+        match left.deref() / right.deref() {
+            Value::Number(num) => {
+                Value::Number(num.with_scale_round(0, bigdecimal::RoundingMode::Down))
+            }
+            value => value,
+        }
+        .into()
+    }
+    fn name(&self) -> &str {
+        "DIV"
+    }
+}
 struct TakeAway {}
 impl BinaryFunction for TakeAway {
     fn calculate<'a>(
@@ -235,6 +350,31 @@ impl BinaryFunction for ConcatOperator {
     }
 }
 
+fn compare(left: &Value, right: &Value, comparator: impl Fn(&Value, &Value) -> bool) -> Value {
+    if left.is_empty() || right.is_empty() {
+        Value::Empty
+    } else {
+        comparator(left, right).into()
+    }
+}
+
+fn compare_order(
+    left: &Value,
+    right: &Value,
+    comparator: impl Fn(&Value, &Value) -> bool,
+) -> Value {
+    match (left, right) {
+        (Value::Date(date), Value::Timestamp(_)) => {
+            comparator(&Value::Timestamp((*date).into()), right).into()
+        }
+        (Value::Timestamp(_), Value::Date(date)) => {
+            comparator(left, &Value::Timestamp((*date).into())).into()
+        }
+        _ if std::mem::discriminant(left) != std::mem::discriminant(right) => Value::Empty,
+        _ => compare(left, right, comparator),
+    }
+}
+
 struct LessThen {}
 impl BinaryFunction for LessThen {
     fn calculate<'a>(
@@ -242,9 +382,7 @@ impl BinaryFunction for LessThen {
         left: SmartReference<Value>,
         right: SmartReference<Value>,
     ) -> SmartReference<'a, Value> {
-        let comp = left.deref() < right.deref();
-        let val: Value = comp.into();
-        val.into()
+        compare_order(&left, &right, |left, right| left < right).into()
     }
     fn name(&self) -> &str {
         "<"
@@ -258,9 +396,7 @@ impl BinaryFunction for GreaterThen {
         left: SmartReference<Value>,
         right: SmartReference<Value>,
     ) -> SmartReference<'a, Value> {
-        let comp = left.deref() > right.deref();
-        let val: Value = comp.into();
-        val.into()
+        compare_order(&left, &right, |left, right| left > right).into()
     }
     fn name(&self) -> &str {
         ">"
@@ -273,9 +409,7 @@ impl BinaryFunction for Equals {
         left: SmartReference<Value>,
         right: SmartReference<Value>,
     ) -> SmartReference<'a, Value> {
-        let comp = left.deref() == right.deref();
-        let val: Value = comp.into();
-        val.into()
+        compare(&left, &right, |left, right| left == right).into()
     }
     fn name(&self) -> &str {
         "="
@@ -289,9 +423,7 @@ impl BinaryFunction for LessThenEq {
         left: SmartReference<Value>,
         right: SmartReference<Value>,
     ) -> SmartReference<'a, Value> {
-        let comp = left.deref() <= right.deref();
-        let val: Value = comp.into();
-        val.into()
+        compare_order(&left, &right, |left, right| left <= right).into()
     }
     fn name(&self) -> &str {
         "<="
@@ -305,9 +437,7 @@ impl BinaryFunction for GreaterThenEq {
         left: SmartReference<Value>,
         right: SmartReference<Value>,
     ) -> SmartReference<'a, Value> {
-        let comp = left.deref() >= right.deref();
-        let val: Value = comp.into();
-        val.into()
+        compare_order(&left, &right, |left, right| left >= right).into()
     }
     fn name(&self) -> &str {
         ">="
@@ -321,9 +451,7 @@ impl BinaryFunction for NotEquals {
         left: SmartReference<Value>,
         right: SmartReference<Value>,
     ) -> SmartReference<'a, Value> {
-        let comp = left.deref() != right.deref();
-        let val: Value = comp.into();
-        val.into()
+        compare(&left, &right, |left, right| left != right).into()
     }
     fn name(&self) -> &str {
         "<>"
@@ -339,7 +467,7 @@ impl BinaryFunction for AndBinaryFunction {
     ) -> SmartReference<'a, Value> {
         let results = match (left.deref(), right.deref()) {
             (&Value::Bool(true), &Value::Bool(true)) => Value::Bool(true),
-            (&Value::Bool(_), &Value::Bool(_)) => Value::Bool(false),
+            (&Value::Bool(false), _) | (_, &Value::Bool(false)) => Value::Bool(false),
             _ => Value::Empty,
         };
         results.into()
@@ -358,7 +486,7 @@ impl BinaryFunction for OrBinaryFunction {
     ) -> SmartReference<'a, Value> {
         let results = match (left.deref(), right.deref()) {
             (&Value::Bool(false), &Value::Bool(false)) => Value::Bool(false),
-            (&Value::Bool(_), &Value::Bool(_)) => Value::Bool(true),
+            (&Value::Bool(true), _) | (_, &Value::Bool(true)) => Value::Bool(true),
             _ => Value::Empty,
         };
         results.into()
@@ -500,6 +628,21 @@ impl UnartyProjection {
             name,
         }
     }
+    fn with_scale(
+        value: Box<dyn Projection>,
+        operator: Box<dyn UnaryFunction>,
+        scale: i64,
+    ) -> Self {
+        if scale == 0 {
+            return Self::new(value, operator);
+        }
+        let name = format!("{}({}, {})", operator.name(), value.name(), scale);
+        Self {
+            value,
+            operator,
+            name,
+        }
+    }
 }
 
 struct IsFalse {}
@@ -628,12 +771,30 @@ impl UnaryFunction for PlusUnary {
     }
 }
 
-struct Ceil {}
+fn rounding_scale(name: &str, field: &CeilFloorKind) -> Result<i64, CvsSqlError> {
+    match field {
+        CeilFloorKind::DateTimeField(DateTimeField::NoDateTime) => Ok(0),
+        CeilFloorKind::Scale(AstValue::Number(scale, _)) => match scale.to_i64() {
+            Some(scale) => Ok(scale),
+            None => Err(CvsSqlError::Unsupported(format!(
+                "{name} with precision {scale}"
+            ))),
+        },
+        _ => Err(CvsSqlError::Unsupported(format!(
+            "{name} with two arguments"
+        ))),
+    }
+}
+
+struct Ceil {
+    scale: i64,
+}
 impl UnaryFunction for Ceil {
     fn calculate(&self, value: SmartReference<Value>) -> SmartReference<'_, Value> {
         match value.deref() {
             Value::Number(num) => {
-                Value::Number(num.with_scale_round(0, bigdecimal::RoundingMode::Ceiling)).into()
+                Value::Number(num.with_scale_round(self.scale, bigdecimal::RoundingMode::Ceiling))
+                    .into()
             }
             _ => Value::Empty.into(),
         }
@@ -646,12 +807,15 @@ impl UnaryFunction for Ceil {
     }
 }
 
-struct Floor {}
+struct Floor {
+    scale: i64,
+}
 impl UnaryFunction for Floor {
     fn calculate(&self, value: SmartReference<Value>) -> SmartReference<'_, Value> {
         match value.deref() {
             Value::Number(num) => {
-                Value::Number(num.with_scale_round(0, bigdecimal::RoundingMode::Floor)).into()
+                Value::Number(num.with_scale_round(self.scale, bigdecimal::RoundingMode::Floor))
+                    .into()
             }
             _ => Value::Empty.into(),
         }
@@ -748,6 +912,12 @@ impl InSubquery {
     }
 }
 
+/// Checks if a column that is missing from a subquery belongs to the outer query (that is, the subquery is correlated).
+fn is_outer_column(metadata: &Metadata, column: &str) -> bool {
+    let name: Vec<String> = column.split('.').map(|part| part.to_string()).collect();
+    metadata.column_index(&name.into()).is_ok()
+}
+
 struct Between {
     value: Box<dyn Projection>,
     low: Box<dyn Projection>,
@@ -933,6 +1103,169 @@ impl RegexProjection {
         })
     }
 }
+struct LikeProjection {
+    value: Box<dyn Projection>,
+    pattern: Box<dyn Projection>,
+    negated: bool,
+    escape: Option<char>,
+    case_insensitive: bool,
+    name: String,
+}
+
+impl Projection for LikeProjection {
+    fn get<'a>(&'a self, row: &'a GroupRow) -> SmartReference<'a, Value> {
+        let value = self.value.get(row);
+        let pattern = self.pattern.get(row);
+        if value.deref() == &Value::Empty || pattern.deref() == &Value::Empty {
+            return Value::Empty.into();
+        }
+        let regex = like_pattern_to_regex(&pattern.to_string(), self.escape, self.case_insensitive);
+        let Ok(regex) = Regex::new(&regex) else {
+            return Value::Bool(self.negated).into();
+        };
+        let value = value.to_string();
+        if regex.is_match(&value) {
+            Value::Bool(!self.negated).into()
+        } else {
+            Value::Bool(self.negated).into()
+        }
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+impl LikeProjection {
+    fn new(
+        expr: &Expr,
+        pattern: &Expr,
+        negated: bool,
+        escape: Option<char>,
+        case_insensitive: bool,
+        engine: &Engine,
+        metadata: &Metadata,
+    ) -> Result<Self, CvsSqlError> {
+        let value = expr.convert_single(metadata, engine)?;
+        let pattern = pattern.convert_single(metadata, engine)?;
+        let neg = if negated { "NOT " } else { "" };
+        let like = if case_insensitive { "ILIKE" } else { "LIKE" };
+        let name = format!("{} {}{} {}", value.name(), neg, like, pattern.name());
+        Ok(Self {
+            value,
+            pattern,
+            negated,
+            escape,
+            case_insensitive,
+            name,
+        })
+    }
+}
+
+fn like_pattern_to_regex(pattern: &str, escape: Option<char>, case_insensitive: bool) -> String {
+    let mut regex = String::from(if case_insensitive { "(?si)^" } else { "(?s)^" });
+    let mut chars = pattern.chars();
+    while let Some(chr) = chars.next() {
+        match chr {
+            _ if Some(chr) == escape => {
+                let escaped = chars.next().unwrap_or(chr);
+                regex.push_str(&regex::escape(&escaped.to_string()));
+            }
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            _ => regex.push_str(&regex::escape(&chr.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+fn new_like(
+    like: &Expr,
+    engine: &Engine,
+    metadata: &Metadata,
+) -> Result<Box<dyn Projection>, CvsSqlError> {
+    let (negated, any, expr, pattern, escape_char, case_insensitive) = match like {
+        Expr::Like {
+            negated,
+            any,
+            expr,
+            pattern,
+            escape_char,
+        } => (*negated, *any, expr, pattern, escape_char, false),
+        Expr::ILike {
+            negated,
+            any,
+            expr,
+            pattern,
+            escape_char,
+        } => (*negated, *any, expr, pattern, escape_char, true),
+        _ => return Err(CvsSqlError::Unsupported(format!("{like}"))),
+    };
+    let escape = match escape_char {
+        None => None,
+        Some(escape_char) => {
+            let escape = escape_char.clone().into_string().unwrap_or_default();
+            let mut chars = escape.chars();
+            match (chars.next(), chars.next()) {
+                (Some(escape), None) => Some(escape),
+                _ => {
+                    return Err(CvsSqlError::Unsupported(format!(
+                        "LIKE with ESCAPE {escape_char}"
+                    )));
+                }
+            }
+        }
+    };
+    let pattern = pattern.deref();
+    let (patterns, all): (Vec<&Expr>, bool) = match pattern {
+        Expr::Tuple(patterns) if any => (patterns.iter().collect(), false),
+        Expr::Nested(pattern) if any => (vec![pattern], false),
+        Expr::Function(func) if func.name.to_string().to_uppercase() == "ALL" => {
+            let FunctionArguments::List(args) = &func.args else {
+                return Err(CvsSqlError::Unsupported(format!("{like}")));
+            };
+            let mut patterns = vec![];
+            for arg in &args.args {
+                let FunctionArg::Unnamed(FunctionArgExpr::Expr(pattern)) = arg else {
+                    return Err(CvsSqlError::Unsupported(format!("{like}")));
+                };
+                patterns.push(pattern);
+            }
+            (patterns, true)
+        }
+        _ => (vec![pattern], false),
+    };
+    let mut projection: Option<Box<dyn Projection>> = None;
+    for pattern in patterns {
+        let like = Box::new(LikeProjection::new(
+            expr,
+            pattern,
+            negated,
+            escape,
+            case_insensitive,
+            engine,
+            metadata,
+        )?);
+        projection = Some(match projection {
+            None => like,
+            Some(left) => {
+                let operator: Box<dyn BinaryFunction> = if all {
+                    Box::new(AndBinaryFunction {})
+                } else {
+                    Box::new(OrBinaryFunction {})
+                };
+                Box::new(BinaryProjection::new(left, like, operator))
+            }
+        });
+    }
+    let Some(data) = projection else {
+        return Err(CvsSqlError::Unsupported(format!("{like}")));
+    };
+    Ok(Box::new(AliasProjection {
+        data,
+        alias: like.to_string(),
+    }))
+}
+
 impl SingleConvert for Expr {
     fn convert_single(
         &self,
@@ -956,6 +1289,7 @@ impl SingleConvert for Expr {
                     BinaryOperator::Plus => Box::new(Plus {}),
                     BinaryOperator::Multiply => Box::new(Times {}),
                     BinaryOperator::Divide => Box::new(Divide {}),
+                    BinaryOperator::MyIntegerDivide => Box::new(IntegerDivide {}),
                     BinaryOperator::Minus => Box::new(TakeAway {}),
                     BinaryOperator::Modulo => Box::new(Modulo {}),
                     BinaryOperator::StringConcat => Box::new(ConcatOperator {}),
@@ -993,6 +1327,13 @@ impl SingleConvert for Expr {
                         value: Value::Empty,
                         name,
                     })),
+                    AstValue::Placeholder(placeholder) => {
+                        if let Some(value) = engine.param(placeholder) {
+                            return Ok(Box::new(ValueProjection { value, name }));
+                        }
+                        let name: Name = placeholder.as_str().into();
+                        name.convert_single(metadata, engine)
+                    }
                     _ => Err(CvsSqlError::Unsupported(format!(
                         "Select literal value {self}"
                     ))),
@@ -1049,6 +1390,42 @@ impl SingleConvert for Expr {
                 let expr = InSubquery::new(expr, subquery, negated, engine, metadata)?;
                 Ok(Box::new(expr))
             }
+            Expr::Exists { subquery, negated } => {
+                let results = match subquery.extract(engine) {
+                    Ok(results) => results,
+                    Err(CvsSqlError::ColumnIndexError(ColumnIndexError::NoSuchColumn(column)))
+                        if is_outer_column(metadata, &column) =>
+                    {
+                        return Err(CvsSqlError::Unsupported(format!(
+                            "EXISTS with a correlated subquery (referencing {column})"
+                        )));
+                    }
+                    Err(e) => return Err(e),
+                };
+                let exists = results.data.iter().next().is_some();
+                let value = Value::Bool(exists != *negated);
+                let name = self.to_string();
+                Ok(Box::new(ValueProjection { value, name }))
+            }
+            Expr::Subquery(subquery) => {
+                let results = subquery.extract(engine)?;
+                let number_of_columns = results.metadata.number_of_columns();
+                let mut rows = results.data.iter();
+                let value = match (rows.next(), rows.next()) {
+                    (None, _) if number_of_columns == 1 => Value::Empty,
+                    (Some(row), None) if number_of_columns == 1 => {
+                        row.get(&Column::from_index(0)).clone()
+                    }
+                    _ => {
+                        return Err(CvsSqlError::NotAScalar(
+                            results.data.iter().count(),
+                            number_of_columns,
+                        ));
+                    }
+                };
+                let name = self.to_string();
+                Ok(Box::new(ValueProjection { value, name }))
+            }
             Expr::Between {
                 expr,
                 negated,
@@ -1067,6 +1444,7 @@ impl SingleConvert for Expr {
                 let expr = RegexProjection::new(expr, pattern, negated, engine, metadata)?;
                 Ok(Box::new(expr))
             }
+            Expr::Like { .. } | Expr::ILike { .. } => new_like(self, engine, metadata),
             Expr::SimilarTo {
                 negated,
                 expr,
@@ -1128,32 +1506,20 @@ impl SingleConvert for Expr {
                 create_extract(field, value)
             }
             Expr::Ceil { expr, field } => {
-                match field {
-                    CeilFloorKind::DateTimeField(DateTimeField::NoDateTime) => {}
-                    _ => {
-                        return Err(CvsSqlError::Unsupported(
-                            "CEIL with two arguments".to_string(),
-                        ));
-                    }
-                }
-
+                let scale = rounding_scale("CEIL", field)?;
                 let value = expr.convert_single(metadata, engine)?;
-                let operator = Box::new(Ceil {});
-                Ok(Box::new(UnartyProjection::new(value, operator)))
+                let operator = Box::new(Ceil { scale });
+                Ok(Box::new(UnartyProjection::with_scale(
+                    value, operator, scale,
+                )))
             }
             Expr::Floor { expr, field } => {
-                match field {
-                    CeilFloorKind::DateTimeField(DateTimeField::NoDateTime) => {}
-                    _ => {
-                        return Err(CvsSqlError::Unsupported(
-                            "CEIL with two arguments".to_string(),
-                        ));
-                    }
-                }
-
+                let scale = rounding_scale("FLOOR", field)?;
                 let value = expr.convert_single(metadata, engine)?;
-                let operator = Box::new(Floor {});
-                Ok(Box::new(UnartyProjection::new(value, operator)))
+                let operator = Box::new(Floor { scale });
+                Ok(Box::new(UnartyProjection::with_scale(
+                    value, operator, scale,
+                )))
             }
             Expr::Position { expr, r#in } => {
                 let sub_str = expr.convert_single(metadata, engine)?;
@@ -1171,6 +1537,24 @@ impl SingleConvert for Expr {
                 let sub = SubString::new(expr, substring_from, substring_for, engine, metadata)?;
                 Ok(Box::new(sub))
             }
+            Expr::Trim {
+                expr,
+                trim_where,
+                trim_what,
+                trim_characters,
+            } => {
+                let what = match (trim_what.as_deref(), trim_characters.as_deref()) {
+                    (None, None) => None,
+                    (Some(what), None) | (None, Some([what])) => Some(what),
+                    _ => {
+                        return Err(CvsSqlError::Unsupported(format!(
+                            "Select expression like {self}"
+                        )));
+                    }
+                };
+                let trim = Trim::new(expr, trim_where, what, engine, metadata)?;
+                Ok(Box::new(trim))
+            }
             Expr::Function(func) => func.convert_single(metadata, engine),
             Expr::Case {
                 case_token: _,
@@ -1187,15 +1571,43 @@ impl SingleConvert for Expr {
     }
 }
 
+/// A positional reference to a column, like `$1` or `@1` for the first column.
+fn positional_column(name: &str, metadata: &Metadata) -> Option<Column> {
+    let position = name
+        .strip_prefix('$')
+        .or_else(|| name.strip_prefix('@'))?
+        .parse::<usize>()
+        .ok()?;
+    if position == 0 || position > metadata.number_of_columns() {
+        return None;
+    }
+    Some(Column::from_index(position - 1))
+}
+
 impl SingleConvert for Name {
     fn convert_single(
         &self,
         metadata: &Metadata,
         _: &Engine,
     ) -> Result<Box<dyn Projection>, CvsSqlError> {
-        let column = metadata.column_index(self)?;
+        let column = match metadata.column_index(self) {
+            Ok(column) => column.clone(),
+            Err(ColumnIndexError::NoSuchColumn(name)) => {
+                let [short_name] = self.elements() else {
+                    return Err(ColumnIndexError::NoSuchColumn(name).into());
+                };
+                let Some(column) = positional_column(short_name, metadata) else {
+                    return Err(ColumnIndexError::NoSuchColumn(name).into());
+                };
+                return Ok(Box::new(ColumnProjection {
+                    column_name: metadata.column_title(&column).to_string(),
+                    column,
+                }));
+            }
+            Err(e) => return Err(e.into()),
+        };
         let projection = Box::new(ColumnProjection {
-            column: column.clone(),
+            column,
             column_name: self.short_name().to_string(),
         });
         Ok(projection)
@@ -1237,6 +1649,75 @@ impl Position {
     }
 }
 
+struct Trim {
+    str: Box<dyn Projection>,
+    what: Option<Box<dyn Projection>>,
+    trim_where: TrimWhereField,
+    name: String,
+}
+impl Projection for Trim {
+    fn get<'a>(&'a self, row: &'a GroupRow) -> SmartReference<'a, Value> {
+        let str = self.str.get(row);
+        let Value::Str(str) = str.deref() else {
+            return Value::Empty.into();
+        };
+        let trimmed = match &self.what {
+            None => match self.trim_where {
+                TrimWhereField::Both => str.trim(),
+                TrimWhereField::Leading => str.trim_start(),
+                TrimWhereField::Trailing => str.trim_end(),
+            },
+            Some(what) => {
+                let what = what.get(row);
+                let Value::Str(what) = what.deref() else {
+                    return Value::Empty.into();
+                };
+                let chars: Vec<char> = what.chars().collect();
+                let chars = chars.as_slice();
+                match self.trim_where {
+                    TrimWhereField::Both => str.trim_matches(chars),
+                    TrimWhereField::Leading => str.trim_start_matches(chars),
+                    TrimWhereField::Trailing => str.trim_end_matches(chars),
+                }
+            }
+        };
+        Value::Str(trimmed.to_string()).into()
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+impl Trim {
+    fn new(
+        str: &Expr,
+        trim_where: &Option<TrimWhereField>,
+        what: Option<&Expr>,
+        engine: &Engine,
+        metadata: &Metadata,
+    ) -> Result<Self, CvsSqlError> {
+        let str = str.convert_single(metadata, engine)?;
+        let what = match what {
+            Some(what) => Some(what.convert_single(metadata, engine)?),
+            None => None,
+        };
+        let name = match (trim_where, &what) {
+            (Some(trim_where), Some(what)) => {
+                format!("TRIM({trim_where} {} FROM {})", what.name(), str.name())
+            }
+            (Some(trim_where), None) => format!("TRIM({trim_where} FROM {})", str.name()),
+            (None, Some(what)) => format!("TRIM({} FROM {})", what.name(), str.name()),
+            (None, None) => format!("TRIM({})", str.name()),
+        };
+        let trim_where = trim_where.unwrap_or(TrimWhereField::Both);
+        Ok(Self {
+            str,
+            what,
+            trim_where,
+            name,
+        })
+    }
+}
+
 struct Case {
     leavs: Vec<(Box<dyn Projection>, Box<dyn Projection>)>,
     default: Option<Box<dyn Projection>>,
@@ -15,6 +15,11 @@ pub enum Metadata {
         parent: Rc<Metadata>,
         this: Box<Metadata>,
     },
+    /// The columns of the window functions (like `ROW_NUMBER() OVER (...)`), that are added after the columns of the parent.
+    Windowed {
+        parent: Rc<Metadata>,
+        windows: SimpleResultSetMetadata,
+    },
 }
 impl Metadata {
     pub fn column_index(
@@ -25,6 +30,15 @@ impl Metadata {
             Metadata::Simple(data) => data.column_index(name),
             Metadata::Product(data) => data.column_index(name),
             Metadata::Grouped { parent: _, this } => this.column_index(name),
+            Metadata::Windowed { parent, windows } => match parent.column_index(name) {
+                Err(ColumnIndexError::NoSuchColumn(_)) => {
+                    let column = windows.column_index(name)?;
+                    let column =
+                        Column::from_index(column.get_index() + parent.number_of_columns());
+                    Ok(SmartReference::Owned(column))
+                }
+                found => found,
+            },
         }
     }
     pub fn column_name(&self, column: &Column) -> Option<&Name> {
@@ -32,6 +46,12 @@ impl Metadata {
             Metadata::Simple(data) => data.column_name(column),
             Metadata::Product(data) => data.column_name(column),
             Metadata::Grouped { parent: _, this } => this.column_name(column),
+            Metadata::Windowed { parent, windows } => {
+                match column.get_index().checked_sub(parent.number_of_columns()) {
+                    Some(index) => windows.column_name(&Column::from_index(index)),
+                    None => parent.column_name(column),
+                }
+            }
         }
     }
     pub fn column_title(&self, column: &Column) -> &str {
@@ -46,6 +66,7 @@ impl Metadata {
                 data.left.number_of_columns() + data.right.number_of_columns()
             }
             Metadata::Grouped { parent: _, this } => this.number_of_columns(),
+            Metadata::Windowed { parent, windows } => parent.number_of_columns() + windows.len(),
         }
     }
     pub fn result_name(&self) -> Option<&Name> {
@@ -53,6 +74,7 @@ impl Metadata {
             Metadata::Simple(data) => data.name.as_ref(),
             Metadata::Product(_) => None,
             Metadata::Grouped { parent: _, this } => this.result_name(),
+            Metadata::Windowed { parent, windows: _ } => parent.result_name(),
         }
     }
     pub(crate) fn product(left: &Rc<Self>, right: &Rc<Self>) -> Self {
@@ -64,6 +86,15 @@ impl Metadata {
     pub fn columns(&self) -> Box<dyn Iterator<Item = Column>> {
         Box::new((0..self.number_of_columns()).map(Column::from_index))
     }
+
+    /// The columns without the window functions columns (that should not be part of a `SELECT *`).
+    pub(crate) fn visible_columns(&self) -> Box<dyn Iterator<Item = Column>> {
+        match self {
+            Metadata::Grouped { parent: _, this } => this.visible_columns(),
+            Metadata::Windowed { parent, windows: _ } => parent.visible_columns(),
+            _ => self.columns(),
+        }
+    }
 }
 pub struct ProductResultSetMetadata {
     left: Rc<Metadata>,
@@ -140,6 +171,20 @@ impl SimpleResultSetMetadata {
         self.set_name_to_index(&index, &full_name);
         self.columns.push(full_name);
     }
+    /// Adds a column with a name that is already fully qualified (like `table.column`).
+    pub(crate) fn add_qualified_column(&mut self, name: &Name) {
+        let index = Column::from_index(self.columns.len());
+        self.set_name_to_index(&index, name);
+        self.columns.push(name.clone());
+    }
+    /// Makes the qualified names of `name` (but not its short name) refer to an existing column.
+    pub(crate) fn add_alias(&mut self, column: &Column, name: &Name) {
+        for name in name.available_names() {
+            if name.parent().is_some() {
+                self.set_single_name_to_index(column, name);
+            }
+        }
+    }
     pub(crate) fn build(self) -> Metadata {
         Metadata::Simple(self)
     }
@@ -148,14 +193,17 @@ impl SimpleResultSetMetadata {
     }
     fn set_name_to_index(&mut self, index: &Column, name: &Name) {
         for name in name.available_names() {
-            match self.column_names.entry(name) {
-                Entry::Vacant(entry) => {
-                    entry.insert(ColumnInResult::Column(index.clone()));
-                }
-                Entry::Occupied(mut entry) => {
-                    entry.insert(ColumnInResult::Ambiguous);
-                }
-            };
+            self.set_single_name_to_index(index, name);
         }
     }
+    fn set_single_name_to_index(&mut self, index: &Column, name: Name) {
+        match self.column_names.entry(name) {
+            Entry::Vacant(entry) => {
+                entry.insert(ColumnInResult::Column(index.clone()));
+            }
+            Entry::Occupied(mut entry) => {
+                entry.insert(ColumnInResult::Ambiguous);
+            }
+        };
+    }
 }
@@ -1,4 +1,4 @@
-use std::{ops::Deref, rc::Rc};
+use std::{collections::HashMap, ops::Deref, rc::Rc};
 
 use sqlparser::ast::{Ident, ObjectName};
 use thiserror::Error;
@@ -6,6 +6,7 @@ use thiserror::Error;
 use crate::{
     result_set_metadata::Metadata,
     results_data::{DataRow, ResultsData},
+    schema::InferredType,
     value::Value,
 };
 
@@ -43,6 +44,9 @@ impl Name {
             Some(Self { elements })
         }
     }
+    pub(crate) fn elements(&self) -> &[String] {
+        &self.elements
+    }
     pub fn full_name(&self) -> String {
         self.elements.join(".")
     }
@@ -89,7 +93,14 @@ impl From<Vec<String>> for Name {
 impl From<&ObjectName> for Name {
     fn from(value: &ObjectName) -> Self {
         Self {
-            elements: value.0.iter().map(|i| i.to_string()).collect(),
+            elements: value
+                .0
+                .iter()
+                .map(|part| match part.as_ident() {
+                    Some(ident) => ident.value.clone(),
+                    None => part.to_string(),
+                })
+                .collect(),
         }
     }
 }
@@ -123,4 +134,26 @@ impl ResultSet {
             Err(_) => &Value::Empty,
         }
     }
+    /// The rows as maps from the column titles to the values. If more than one column has the same
+    /// title, the first one wins (like in the JSON output).
+    pub fn rows_as_maps(&self) -> impl Iterator<Item = HashMap<String, Value>> + '_ {
+        self.data.iter().map(|row| {
+            let mut map = HashMap::new();
+            for column in self.columns() {
+                map.entry(self.metadata.column_title(&column).to_string())
+                    .or_insert_with(|| row.get(&column).clone());
+            }
+            map
+        })
+    }
+    /// The name of the type of every column, inferred from all the values of that column.
+    pub(crate) fn column_types(&self) -> Vec<&'static str> {
+        let mut types: Vec<_> = self.columns().map(|_| InferredType::Unknown).collect();
+        for row in self.data.iter() {
+            for (column, inferred) in self.columns().zip(types.iter_mut()) {
+                inferred.add(row.get(&column));
+            }
+        }
+        types.iter().map(|inferred| inferred.name()).collect()
+    }
 }
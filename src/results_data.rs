@@ -1,5 +1,6 @@
 use crate::{results::Column, value::Value};
 
+#[derive(Clone)]
 pub struct DataRow {
     row: Vec<Value>,
 }
@@ -25,6 +26,16 @@ impl DataRow {
             self.set(column, value);
         }
     }
+    /// Drops the fraction of integral numbers (so `12.00` will be `12`).
+    pub(crate) fn trim_integers(&mut self) {
+        for value in &mut self.row {
+            if let Value::Number(num) = value
+                && num.is_integer()
+            {
+                *value = Value::Number(num.with_scale(0));
+            }
+        }
+    }
     pub(crate) fn delete_at(&mut self, column: &Column) {
         let index = column.get_index();
         if self.row.len() < index {
@@ -45,6 +56,7 @@ impl ResultsData {
     pub(crate) fn new(rows: Vec<DataRow>) -> Self {
         Self { rows }
     }
+    #[cfg(test)]
     pub(crate) fn get(&self, index: usize) -> Option<&DataRow> {
         self.rows.get(index)
     }
@@ -0,0 +1,248 @@
+use std::ops::Deref;
+
+use bigdecimal::ToPrimitive;
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+use sqlparser::ast::{
+    TableSample, TableSampleKind, TableSampleQuantity, TableSampleSeed, TableSampleUnit,
+    Value as AstValue,
+};
+
+use crate::{
+    engine::Engine,
+    error::CvsSqlError,
+    group_by::GroupRow,
+    projections::SingleConvert,
+    result_set_metadata::{Metadata, SimpleResultSetMetadata},
+    results::ResultSet,
+    results_data::{DataRow, ResultsData},
+    value::Value,
+};
+
+pub(crate) fn sample_results(
+    sample: &TableSampleKind,
+    engine: &Engine,
+    results: ResultSet,
+) -> Result<ResultSet, CvsSqlError> {
+    let sample = match sample {
+        TableSampleKind::BeforeTableAlias(sample) => sample,
+        TableSampleKind::AfterTableAlias(sample) => sample,
+    };
+    let TableSample {
+        modifier: _,
+        name: _,
+        quantity,
+        seed,
+        bucket,
+        offset,
+    } = sample.deref();
+    if bucket.is_some() {
+        return Err(CvsSqlError::Unsupported(
+            "TABLESAMPLE with BUCKET".to_string(),
+        ));
+    }
+    if offset.is_some() {
+        return Err(CvsSqlError::Unsupported(
+            "TABLESAMPLE with OFFSET".to_string(),
+        ));
+    }
+    let Some(TableSampleQuantity {
+        parenthesized: _,
+        value,
+        unit,
+    }) = quantity
+    else {
+        return Err(CvsSqlError::Unsupported(
+            "TABLESAMPLE without quantity".to_string(),
+        ));
+    };
+
+    let metadata = Metadata::Simple(SimpleResultSetMetadata::new(None));
+    let quantity = value.convert_single(&metadata, engine)?;
+    let temp_row = GroupRow {
+        data: DataRow::new(vec![]),
+        group_rows: vec![],
+    };
+    let quantity = quantity.get(&temp_row);
+    let Value::Number(quantity) = quantity.deref() else {
+        return Err(CvsSqlError::NoNumericSample);
+    };
+
+    let mut rng = match seed {
+        Some(seed) => seeded_rng(seed)?,
+        None => StdRng::from_rng(&mut *engine.rng().borrow_mut()),
+    };
+    let rows = results.data.into_iter();
+    let rows = match unit {
+        Some(TableSampleUnit::Rows) => {
+            let Some(size) = quantity.to_usize() else {
+                return Err(CvsSqlError::NoNumericSample);
+            };
+            reservoir(rows, size, &mut rng)
+        }
+        Some(TableSampleUnit::Percent) | None => {
+            let Some(percent) = quantity.to_f64() else {
+                return Err(CvsSqlError::NoNumericSample);
+            };
+            if !(0.0..=100.0).contains(&percent) {
+                return Err(CvsSqlError::NoNumericSample);
+            }
+            let probability = percent / 100.0;
+            rows.filter(|_| rng.random_bool(probability)).collect()
+        }
+    };
+
+    Ok(ResultSet {
+        metadata: results.metadata,
+        data: ResultsData::new(rows),
+    })
+}
+
+fn seeded_rng(seed: &TableSampleSeed) -> Result<StdRng, CvsSqlError> {
+    let AstValue::Number(seed, _) = &seed.value else {
+        return Err(CvsSqlError::NoNumericSample);
+    };
+    let Some(seed) = seed.to_u64() else {
+        return Err(CvsSqlError::NoNumericSample);
+    };
+    Ok(StdRng::seed_from_u64(seed))
+}
+
+fn reservoir(rows: impl Iterator<Item = DataRow>, size: usize, rng: &mut StdRng) -> Vec<DataRow> {
+    let mut reservoir: Vec<(usize, DataRow)> = Vec::with_capacity(size.min(rows.size_hint().0));
+    if size == 0 {
+        return vec![];
+    }
+    for (index, row) in rows.enumerate() {
+        if reservoir.len() < size {
+            reservoir.push((index, row));
+        } else {
+            let replace = rng.random_range(0..=index);
+            if replace < size {
+                reservoir[replace] = (index, row);
+            }
+        }
+    }
+    reservoir.sort_by_key(|(index, _)| *index);
+    reservoir.into_iter().map(|(_, row)| row).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::{TempDir, tempdir};
+
+    use crate::value::Value;
+    use crate::{args::Args, engine::Engine, error::CvsSqlError, results::Column};
+
+    fn create_table() -> Result<TempDir, CvsSqlError> {
+        let working_dir = tempdir()?;
+        let mut content = String::from("id\n");
+        for i in 0..1000 {
+            content.push_str(&format!("{i}\n"));
+        }
+        fs::write(working_dir.path().join("tab.csv"), content)?;
+        Ok(working_dir)
+    }
+
+    fn ids(engine: &Engine, sql: &str) -> Result<Vec<Value>, CvsSqlError> {
+        let results = engine.execute_commands(sql)?;
+        let results = &results.first().unwrap().results;
+        Ok(results
+            .data
+            .iter()
+            .map(|row| row.get(&Column::from_index(0)).clone())
+            .collect())
+    }
+
+    #[test]
+    fn system_sample_keeps_about_the_percentage() -> Result<(), CvsSqlError> {
+        let working_dir = create_table()?;
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            seed: Some(42),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let sampled = ids(&engine, "SELECT * FROM tab TABLESAMPLE SYSTEM (30 PERCENT)")?;
+
+        let fraction = sampled.len() as f64 / 1000.0;
+        assert!((0.25..0.35).contains(&fraction), "fraction was {fraction}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn sample_with_same_seed_is_the_same() -> Result<(), CvsSqlError> {
+        let working_dir = create_table()?;
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            seed: Some(7),
+            ..Args::default()
+        };
+        let sql = "SELECT * FROM tab TABLESAMPLE BERNOULLI (50)";
+
+        let first = ids(&Engine::try_from(&args)?, sql)?;
+        let second = ids(&Engine::try_from(&args)?, sql)?;
+
+        assert_eq!(first, second);
+
+        Ok(())
+    }
+
+    #[test]
+    fn sample_with_repeatable_seed() -> Result<(), CvsSqlError> {
+        let working_dir = create_table()?;
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+        let sql = "SELECT * FROM tab TABLESAMPLE SYSTEM (10) REPEATABLE (3)";
+
+        let first = ids(&engine, sql)?;
+        let second = ids(&engine, sql)?;
+
+        assert_eq!(first, second);
+
+        Ok(())
+    }
+
+    #[test]
+    fn sample_rows() -> Result<(), CvsSqlError> {
+        let working_dir = create_table()?;
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let sampled = ids(&engine, "SELECT * FROM tab TABLESAMPLE (12 ROWS)")?;
+        assert_eq!(sampled.len(), 12);
+        assert!(sampled.is_sorted());
+
+        let sampled = ids(&engine, "SELECT * FROM tab TABLESAMPLE (2000 ROWS)")?;
+        assert_eq!(sampled.len(), 1000);
+
+        let sampled = ids(
+            &engine,
+            "SELECT * FROM tab TABLESAMPLE (100000000000000000 ROWS)",
+        )?;
+        assert_eq!(sampled.len(), 1000);
+
+        Ok(())
+    }
+
+    #[test]
+    fn sample_with_invalid_percentage() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let results =
+            engine.execute_commands("SELECT * FROM tests.data.numbers TABLESAMPLE SYSTEM (120)");
+        assert!(matches!(results, Err(CvsSqlError::NoNumericSample)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use chrono::{NaiveDate, NaiveDateTime};
+use toml::{Table, Value as TomlValue};
+
+use crate::engine::EngineError;
+use crate::error::CvsSqlError;
+use crate::value::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ColumnType {
+    String,
+    Number,
+    Bool,
+    Date,
+    Timestamp,
+}
+
+impl FromStr for ColumnType {
+    type Err = ();
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "string" | "text" => Ok(ColumnType::String),
+            "number" => Ok(ColumnType::Number),
+            "bool" | "boolean" => Ok(ColumnType::Bool),
+            "date" => Ok(ColumnType::Date),
+            "timestamp" => Ok(ColumnType::Timestamp),
+            _ => Err(()),
+        }
+    }
+}
+
+impl ColumnType {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            ColumnType::String => "string",
+            ColumnType::Number => "number",
+            ColumnType::Bool => "bool",
+            ColumnType::Date => "date",
+            ColumnType::Timestamp => "timestamp",
+        }
+    }
+
+    pub(crate) fn parse(&self, column: &str, value: &str) -> Result<Value, CvsSqlError> {
+        if value.is_empty() {
+            return Ok(Value::Empty);
+        }
+        let parsed = match self {
+            ColumnType::String => Some(Value::Str(value.to_string())),
+            ColumnType::Number => BigDecimal::from_str(value).ok().map(Value::Number),
+            ColumnType::Bool => match value.to_lowercase().as_str() {
+                "true" => Some(Value::Bool(true)),
+                "false" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            ColumnType::Date => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .map(Value::Date),
+            ColumnType::Timestamp => NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+                .ok()
+                .map(Value::Timestamp),
+        };
+        parsed.ok_or_else(|| {
+            CvsSqlError::SchemaMismatch(
+                value.to_string(),
+                column.to_string(),
+                self.name().to_string(),
+            )
+        })
+    }
+}
+
+/// The type of a column as inferred from its values; a column with mixed types is a string.
+pub(crate) enum InferredType {
+    Unknown,
+    Known(ColumnType),
+}
+impl InferredType {
+    pub(crate) fn add(&mut self, value: &Value) {
+        let column_type = match value {
+            Value::Empty => return,
+            Value::Bool(_) => ColumnType::Bool,
+            Value::Number(_) => ColumnType::Number,
+            Value::Date(_) => ColumnType::Date,
+            Value::Timestamp(_) => ColumnType::Timestamp,
+            Value::Str(_) => ColumnType::String,
+        };
+        *self = match self {
+            InferredType::Known(known) if *known != column_type => {
+                InferredType::Known(ColumnType::String)
+            }
+            _ => InferredType::Known(column_type),
+        };
+    }
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            InferredType::Unknown => "unknown",
+            InferredType::Known(column_type) => column_type.name(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Schema {
+    columns: HashMap<String, ColumnType>,
+}
+
+impl Schema {
+    pub(crate) fn read(path: &Path) -> Result<Self, EngineError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| EngineError::InvalidSchema(format!("{}: {e}", path.display())))?;
+        let table = content
+            .parse::<Table>()
+            .map_err(|e| EngineError::InvalidSchema(format!("{}: {e}", path.display())))?;
+        let mut columns = HashMap::new();
+        for (column, column_type) in table {
+            let TomlValue::String(column_type) = column_type else {
+                return Err(EngineError::InvalidSchema(format!(
+                    "type of `{column}` should be a string"
+                )));
+            };
+            let Ok(column_type) = column_type.parse() else {
+                return Err(EngineError::InvalidSchema(format!(
+                    "unknown type `{column_type}` for `{column}`"
+                )));
+            };
+            columns.insert(column, column_type);
+        }
+        Ok(Self { columns })
+    }
+
+    pub(crate) fn column_type(&self, column: &str) -> Option<ColumnType> {
+        self.columns.get(column).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn read_schema() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        let path = working_dir.path().join("schema.toml");
+        fs::write(&path, "zip = \"string\"\nsold = \"Date\"\n")?;
+
+        let schema = Schema::read(&path)?;
+
+        assert_eq!(schema.column_type("zip"), Some(ColumnType::String));
+        assert_eq!(schema.column_type("sold"), Some(ColumnType::Date));
+        assert_eq!(schema.column_type("other"), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_schema_with_unknown_type() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        let path = working_dir.path().join("schema.toml");
+        fs::write(&path, "zip = \"zip code\"\n")?;
+
+        assert!(matches!(
+            Schema::read(&path),
+            Err(EngineError::InvalidSchema(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_values() -> Result<(), CvsSqlError> {
+        assert_eq!(
+            ColumnType::String.parse("zip", "00123")?,
+            Value::Str("00123".into())
+        );
+        assert_eq!(ColumnType::Number.parse("id", "00123")?, Value::from("123"));
+        assert_eq!(ColumnType::Bool.parse("ok", "True")?, Value::Bool(true));
+        assert_eq!(ColumnType::Date.parse("day", "")?, Value::Empty);
+        assert!(matches!(
+            ColumnType::Date.parse("day", "yesterday"),
+            Err(CvsSqlError::SchemaMismatch(_, _, _))
+        ));
+
+        Ok(())
+    }
+}
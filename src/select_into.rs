@@ -0,0 +1,162 @@
+use bigdecimal::{BigDecimal, FromPrimitive};
+use sqlparser::ast::{ObjectNamePart, SelectInto};
+
+use crate::engine::Engine;
+use crate::error::CvsSqlError;
+use crate::outputer::write_results_to_file;
+use crate::results::ResultSet;
+use crate::results_builder::build_simple_results;
+use crate::value::Value;
+
+pub(crate) fn select_into(
+    engine: &Engine,
+    into: &SelectInto,
+    results: ResultSet,
+) -> Result<ResultSet, CvsSqlError> {
+    if into.temporary || into.unlogged || into.table {
+        return Err(CvsSqlError::Unsupported(format!("SELECT {into}")));
+    }
+    let [ObjectNamePart::Identifier(file)] = into.name.0.as_slice() else {
+        return Err(CvsSqlError::Unsupported(format!("SELECT {into}")));
+    };
+    if file.quote_style != Some('\'') {
+        return Err(CvsSqlError::Unsupported(format!(
+            "SELECT {into} (the target should be a quoted file name)"
+        )));
+    }
+    if engine.is_read_only() {
+        return Err(CvsSqlError::ReadOnlyMode);
+    }
+    let path = engine.home().join(&file.value);
+    if path.exists() {
+        return Err(CvsSqlError::OutputCreationError(format!(
+            "File {} already exists",
+            path.to_str().unwrap_or_default()
+        )));
+    }
+
+    if !engine.is_dry_run() {
+        write_results_to_file(&path, &results)?;
+    }
+
+    let number_of_rows = results.data.iter().count();
+    build_simple_results(vec![
+        ("action", Value::Str("SELECT INTO".to_string())),
+        (
+            "file",
+            Value::Str(path.to_str().unwrap_or_default().to_string()),
+        ),
+        (
+            "number_of_rows",
+            Value::Number(BigDecimal::from_usize(number_of_rows).unwrap()),
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::{args::Args, writer::Writer, writer::new_csv_writer};
+
+    use super::*;
+
+    #[test]
+    fn select_into_matches_direct_query() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        fs::copy(
+            "tests/data/artists.csv",
+            working_dir.path().join("artists.csv"),
+        )?;
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            write_mode: true,
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+        let sql = "SELECT name, artist_id * 2 AS double_id FROM artists ORDER BY name DESC";
+
+        let results = engine.execute_commands(sql)?;
+        let mut expected = Vec::new();
+        new_csv_writer(&mut expected, true).write(&results.first().unwrap().results)?;
+
+        engine.execute_commands(
+            "SELECT name, artist_id * 2 AS double_id INTO 'result.csv' FROM artists ORDER BY name DESC",
+        )?;
+        let written = fs::read(working_dir.path().join("result.csv"))?;
+
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            String::from_utf8(expected).unwrap()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn select_into_json() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        fs::copy(
+            "tests/data/artists.csv",
+            working_dir.path().join("artists.csv"),
+        )?;
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            write_mode: true,
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        engine.execute_commands("SELECT * INTO 'result.json' FROM artists")?;
+
+        let written = fs::read_to_string(working_dir.path().join("result.json"))?;
+        let json: serde_json::Value = serde_json::from_str(&written)?;
+        assert_eq!(json.as_array().map(|a| a.len()), Some(4));
+
+        Ok(())
+    }
+
+    #[test]
+    fn select_into_in_read_only_mode() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        fs::copy(
+            "tests/data/artists.csv",
+            working_dir.path().join("artists.csv"),
+        )?;
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands("SELECT * INTO 'result.csv' FROM artists");
+
+        assert!(matches!(results, Err(CvsSqlError::ReadOnlyMode)));
+        assert!(!working_dir.path().join("result.csv").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn select_into_unknown_format() -> Result<(), CvsSqlError> {
+        let working_dir = tempdir()?;
+        fs::copy(
+            "tests/data/artists.csv",
+            working_dir.path().join("artists.csv"),
+        )?;
+        let args = Args {
+            home: Some(working_dir.path().to_path_buf()),
+            write_mode: true,
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let results = engine.execute_commands("SELECT * INTO 'result.xlsx' FROM artists");
+
+        assert!(matches!(results, Err(CvsSqlError::OutputCreationError(_))));
+
+        Ok(())
+    }
+}
@@ -4,9 +4,11 @@ use std::time::SystemTime;
 use std::{fs, path::PathBuf};
 
 use chrono::{DateTime, Utc};
+use sqlparser::ast::ObjectName;
 use sqlparser::dialect::Dialect;
 
 use crate::dialect::FilesDialect;
+use crate::file_results::read_file;
 use crate::result_set_metadata::{Metadata, SimpleResultSetMetadata};
 use crate::results_data::ResultsData;
 use crate::{
@@ -32,7 +34,33 @@ pub(crate) fn show_tables(engine: &Engine, full: &bool) -> Result<ResultSet, Cvs
     Ok(results)
 }
 
-fn get_table_name(file: &Path) -> Option<String> {
+/// The columns of a table (for `DESCRIBE table`), with their position and their inferred type.
+pub(crate) fn describe_table(engine: &Engine, name: &ObjectName) -> Result<ResultSet, CvsSqlError> {
+    let table = read_file(engine, name)?;
+    let types = table.column_types();
+
+    let mut metadata = SimpleResultSetMetadata::new(None);
+    metadata.add_column("column_name");
+    metadata.add_column("ordinal_position");
+    metadata.add_column("inferred_type");
+    let rows = table
+        .columns()
+        .zip(types)
+        .map(|(column, inferred_type)| {
+            DataRow::new(vec![
+                Value::Str(table.metadata.column_title(&column).to_string()),
+                Value::Number((column.get_index() as u64 + 1).into()),
+                Value::Str(inferred_type.to_string()),
+            ])
+        })
+        .collect();
+
+    let metadata = Rc::new(Metadata::Simple(metadata));
+    let data = ResultsData::new(rows);
+    Ok(ResultSet { metadata, data })
+}
+
+pub(crate) fn get_table_name(file: &Path) -> Option<String> {
     let dialect = FilesDialect {};
     if file
         .extension()
@@ -221,6 +249,44 @@ mod tests {
         Ok(home)
     }
 
+    #[test]
+    fn test_describe_table() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let results =
+            engine.execute_commands("DESCRIBE tests.data.customers; DESC tests.data.customers")?;
+        assert_eq!(results.len(), 2);
+
+        for results in results {
+            let results = results.results;
+            assert_eq!(results.metadata.number_of_columns(), 3);
+            let columns: Vec<_> = results
+                .data
+                .iter()
+                .map(|row| {
+                    (
+                        row.get(&Column::from_index(0)).to_string(),
+                        row.get(&Column::from_index(2)).to_string(),
+                    )
+                })
+                .collect();
+            assert_eq!(
+                columns,
+                vec![
+                    ("id".to_string(), "number".to_string()),
+                    ("company".to_string(), "string".to_string()),
+                    ("name".to_string(), "string".to_string()),
+                    ("country".to_string(), "string".to_string()),
+                    ("email".to_string(), "string".to_string()),
+                    ("active".to_string(), "bool".to_string()),
+                    ("last modified".to_string(), "timestamp".to_string()),
+                ]
+            );
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_show_tables() -> Result<(), CvsSqlError> {
         let started = Utc::now().naive_utc() - Duration::seconds(20);
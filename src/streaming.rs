@@ -0,0 +1,482 @@
+use std::fs::File;
+use std::ops::Deref;
+use std::path::Path;
+use std::rc::Rc;
+
+use csv::ByteRecord;
+use sqlparser::ast::{
+    Expr, GroupByExpr, LimitClause, Query, Select, SelectItem, SetExpr, Statement, TableFactor,
+    TableWithJoins,
+};
+
+use crate::{
+    engine::Engine,
+    error::CvsSqlError,
+    file_results::{is_blank, read_cell},
+    group_by::GroupRow,
+    projections::{SingleConvert, select_item_projections},
+    result_set_metadata::{Metadata, SimpleResultSetMetadata},
+    results::{Name, ResultSet},
+    results_data::{DataRow, ResultsData},
+    trimmer::{limit_size, offset_size},
+    value::Value,
+};
+
+/// The results of a query that are calculated one row at a time, while they are read.
+pub struct RowStream<'a> {
+    pub metadata: Rc<Metadata>,
+    rows: Box<dyn Iterator<Item = Result<DataRow, CvsSqlError>> + 'a>,
+}
+impl Iterator for RowStream<'_> {
+    type Item = Result<DataRow, CvsSqlError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows.next()
+    }
+}
+impl RowStream<'_> {
+    pub fn collect_results(self) -> Result<ResultSet, CvsSqlError> {
+        let metadata = self.metadata;
+        let data = self.rows.collect::<Result<Vec<_>, _>>()?;
+        let data = ResultsData::new(data);
+        Ok(ResultSet { metadata, data })
+    }
+}
+
+/// Returns the results of the statement as a stream if it is a simple query over a single CSV file
+/// (that is, `SELECT ... FROM table WHERE ... LIMIT ... OFFSET ...` without joins, grouping,
+/// aggregations or ordering). With a limit, the file is read only until there are enough rows.
+/// Any other statement returns `None` and should be extracted as usual.
+pub(crate) fn stream_statement<'a>(
+    engine: &'a Engine,
+    statement: &Statement,
+) -> Result<Option<RowStream<'a>>, CvsSqlError> {
+    let Statement::Query(query) = statement else {
+        return Ok(None);
+    };
+    let Some((select, limit, offset)) = streamable_select(query) else {
+        return Ok(None);
+    };
+    let [
+        TableWithJoins {
+            relation:
+                TableFactor::Table {
+                    name,
+                    alias,
+                    args: None,
+                    with_hints,
+                    version: None,
+                    with_ordinality: false,
+                    partitions,
+                    json_path: None,
+                    sample: None,
+                    index_hints,
+                },
+            joins,
+        },
+    ] = select.from.as_slice()
+    else {
+        return Ok(None);
+    };
+    if !joins.is_empty()
+        || !with_hints.is_empty()
+        || !partitions.is_empty()
+        || !index_hints.is_empty()
+        || alias
+            .as_ref()
+            .is_some_and(|alias| !alias.columns.is_empty())
+    {
+        return Ok(None);
+    }
+    // Those need the entire file (or only its header line)
+    if !engine.first_line_as_name || engine.empty_as_string || engine.headers_only {
+        return Ok(None);
+    }
+    if name.0.len() == 1 && name.to_string() == "$" {
+        return Ok(None);
+    }
+    if name
+        .0
+        .first()
+        .is_some_and(|schema| schema.to_string().to_lowercase() == "information_schema")
+    {
+        return Ok(None);
+    }
+    let file = engine.file_name(name)?;
+    if !file.exists || file.path.extension().is_none_or(|ext| ext != "csv") {
+        return Ok(None);
+    }
+
+    let mut reader = engine
+        .csv_reader(&file.path)
+        .has_headers(true)
+        .from_reader(File::open(&file.path)?);
+    let result_name: Name = match alias {
+        Some(alias) => alias.name.value.as_str().into(),
+        None => file.result_name,
+    };
+    let mut metadata = SimpleResultSetMetadata::new(Some(result_name));
+    let mut column_types = vec![];
+    for title in reader.headers()? {
+        let title = engine.header_title(title);
+        metadata.add_column(&title);
+        let column_type = engine.column_type(&title);
+        column_types.push((title, column_type));
+    }
+    let metadata = metadata.build();
+
+    // When reading the entire file, cells beyond the header line get their own columns, so a
+    // wildcard would select more columns than the header has.
+    let has_wildcard = select.projection.iter().any(|item| {
+        matches!(
+            item,
+            SelectItem::Wildcard(_) | SelectItem::QualifiedWildcard(_, _)
+        )
+    });
+    if has_wildcard && wider_than_header(engine, &file.path, column_types.len())? {
+        return Ok(None);
+    }
+
+    // Anything that can not be calculated from a single row (like aggregations) will fail here.
+    let filter = match &select.selection {
+        Some(condition) => match condition.convert_single(&metadata, engine) {
+            Ok(filter) => Some(filter),
+            Err(_) => return Ok(None),
+        },
+        None => None,
+    };
+    let Ok(projections) = select_item_projections(engine, &metadata, &select.projection) else {
+        return Ok(None);
+    };
+    let mut projected = SimpleResultSetMetadata::new(metadata.result_name().cloned());
+    for projection in &projections {
+        projected.add_column(projection.name());
+    }
+    let metadata = Rc::new(projected.build());
+
+    let rows = reader.into_records().filter_map(move |record| {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => return Some(Err(e.into())),
+        };
+        if is_blank(&record) {
+            return None;
+        }
+        let mut values = Vec::with_capacity(column_types.len());
+        for ((title, column_type), cell) in column_types.iter().zip(record.iter()) {
+            match read_cell(engine, title, *column_type, cell) {
+                Ok(value) => values.push(value),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        let row = GroupRow {
+            data: DataRow::new(values),
+            group_rows: vec![],
+        };
+        if let Some(filter) = &filter
+            && filter.get(&row).deref() != &Value::Bool(true)
+        {
+            return None;
+        }
+        let values = projections
+            .iter()
+            .map(|projection| projection.get(&row).clone())
+            .collect();
+        let mut row = DataRow::new(values);
+        if engine.integers {
+            row.trim_integers();
+        }
+        Some(Ok(row))
+    });
+
+    let mut to_skip = match offset {
+        Some(offset) => offset_size(offset, engine)?,
+        None => 0,
+    };
+    let rows = rows.filter(move |row| {
+        if to_skip > 0 && row.is_ok() {
+            to_skip -= 1;
+            false
+        } else {
+            true
+        }
+    });
+    let rows: Box<dyn Iterator<Item = _>> = match limit {
+        Some(limit) => Box::new(rows.take(limit_size(limit, engine)?)),
+        None => Box::new(rows),
+    };
+
+    Ok(Some(RowStream { metadata, rows }))
+}
+
+/// Whether any row of the file has more cells than its header line.
+fn wider_than_header(engine: &Engine, path: &Path, width: usize) -> Result<bool, CvsSqlError> {
+    let mut reader = engine
+        .csv_reader(path)
+        .has_headers(true)
+        .from_reader(File::open(path)?);
+    let mut record = ByteRecord::new();
+    while reader.read_byte_record(&mut record)? {
+        if record.len() > width {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// The select of a query that can be streamed, with its limit and offset.
+fn streamable_select(query: &Query) -> Option<(&Select, Option<&Expr>, Option<&Expr>)> {
+    let (limit, offset) = match &query.limit_clause {
+        None => (None, None),
+        Some(LimitClause::OffsetCommaLimit { offset, limit }) => (Some(limit), Some(offset)),
+        Some(LimitClause::LimitOffset {
+            limit,
+            offset,
+            limit_by,
+        }) => {
+            if !limit_by.is_empty() {
+                return None;
+            }
+            (limit.as_ref(), offset.as_ref().map(|offset| &offset.value))
+        }
+    };
+    if query.with.is_some()
+        || query.order_by.is_some()
+        || query.fetch.is_some()
+        || !query.locks.is_empty()
+        || query.for_clause.is_some()
+        || query.settings.is_some()
+        || query.format_clause.is_some()
+        || !query.pipe_operators.is_empty()
+    {
+        return None;
+    }
+    let SetExpr::Select(select) = query.body.deref() else {
+        return None;
+    };
+    let no_group_by = match &select.group_by {
+        GroupByExpr::Expressions(expressions, modifiers) => {
+            expressions.is_empty() && modifiers.is_empty()
+        }
+        GroupByExpr::All(_) => false,
+    };
+    if !no_group_by
+        || select.distinct.is_some()
+        || select.top.is_some()
+        || select.into.is_some()
+        || select.having.is_some()
+        || select.prewhere.is_some()
+        || select.qualify.is_some()
+        || select.value_table_mode.is_some()
+        || select.window_before_qualify
+        || !select.lateral_views.is_empty()
+        || !select.cluster_by.is_empty()
+        || !select.distribute_by.is_empty()
+        || !select.sort_by.is_empty()
+        || !select.named_window.is_empty()
+        || !select.connect_by.is_empty()
+    {
+        return None;
+    }
+    Some((select, limit, offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+    use std::io::{BufWriter, Write};
+
+    use sqlparser::parser::Parser;
+    use tempfile::tempdir;
+
+    use crate::{
+        args::Args, dialect::FilesDialect, engine::Engine, error::CvsSqlError,
+        extractor::Extractor, results::Column, value::Value,
+    };
+
+    use super::stream_statement;
+
+    fn stream_or_extract(
+        engine: &Engine,
+        sql: &str,
+    ) -> Result<(bool, Vec<Vec<Value>>), CvsSqlError> {
+        let statement = Parser::parse_sql(&FilesDialect {}, sql)?.remove(0);
+        let (streamed, results) = match stream_statement(engine, &statement)? {
+            Some(stream) => (true, stream.collect_results()?),
+            None => (false, statement.extract(engine)?),
+        };
+        let rows = results
+            .data
+            .iter()
+            .map(|row| results.columns().map(|col| row.get(&col).clone()).collect())
+            .collect();
+        Ok((streamed, rows))
+    }
+
+    #[test]
+    fn simple_queries_are_streamed_with_the_same_results() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+        for sql in [
+            "SELECT * FROM tests.data.artists",
+            "SELECT name, artist_id * 2 AS double FROM tests.data.artists WHERE artist_id > 1",
+            "SELECT a.name FROM tests.data.artists AS a WHERE a.name LIKE 'A%'",
+            "SELECT id, UPPER(name) FROM tests.data.customers WHERE active",
+            "SELECT name FROM tests.data.artists LIMIT 2",
+            "SELECT name FROM tests.data.artists WHERE artist_id > 2 LIMIT 3 OFFSET 2",
+            "SELECT name FROM tests.data.artists OFFSET 4",
+            "SELECT name FROM tests.data.artists LIMIT 0",
+            "SELECT artist_id FROM tests.data.artists LIMIT 1, 2",
+        ] {
+            let (streamed, rows) = stream_or_extract(&engine, sql)?;
+            assert!(streamed, "{sql} was not streamed");
+            let expected = engine.execute_commands(sql)?.remove(0).results;
+            let expected: Vec<Vec<Value>> = expected
+                .data
+                .iter()
+                .map(|row| {
+                    expected
+                        .columns()
+                        .map(|col| row.get(&col).clone())
+                        .collect()
+                })
+                .collect();
+            assert_eq!(rows, expected, "{sql}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn other_queries_are_not_streamed() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+        for sql in [
+            "SELECT COUNT(*) FROM tests.data.artists",
+            "SELECT name FROM tests.data.artists ORDER BY name",
+            "SELECT name FROM tests.data.artists GROUP BY name",
+            "SELECT DISTINCT name FROM tests.data.artists",
+            "SELECT * FROM tests.data.artists JOIN tests.data.sales ON artist_id = id",
+            "SELECT * FROM information_schema.tables",
+            "SELECT * FROM GENERATE_SERIES(1, 3)",
+            "SELECT * FROM (SELECT * FROM tests.data.artists)",
+            "SELECT * FROM tests.data.no_such_table",
+            "SHOW TABLES",
+        ] {
+            let statement = Parser::parse_sql(&FilesDialect {}, sql)?.remove(0);
+            assert!(
+                stream_statement(&engine, &statement)?.is_none(),
+                "{sql} was streamed"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn rows_wider_than_the_header_are_not_streamed_for_wildcards() -> Result<(), CvsSqlError> {
+        let dir = tempdir()?;
+        std::fs::write(dir.path().join("wide.csv"), "a,b\n1,2\n3,4,5\n")?;
+        let args = Args {
+            home: Some(dir.path().to_path_buf()),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let (streamed, rows) = stream_or_extract(&engine, "SELECT * FROM wide")?;
+        assert!(!streamed);
+        assert_eq!(rows[1].len(), 3);
+
+        let (streamed, rows) = stream_or_extract(&engine, "SELECT b FROM wide")?;
+        assert!(streamed);
+        assert_eq!(rows.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn huge_file_is_streamed_row_by_row() -> Result<(), CvsSqlError> {
+        let dir = tempdir()?;
+        let rows = 200_000;
+        {
+            let mut file = BufWriter::new(File::create(dir.path().join("huge.csv"))?);
+            writeln!(file, "id,name,score")?;
+            for id in 0..rows {
+                writeln!(file, "{id},name {id},{}", id % 100)?;
+            }
+            // Reading the entire file up front would fail because of this line
+            writeln!(file, "{rows},broken,not a number")?;
+        }
+        let schema = dir.path().join("schema.toml");
+        fs::write(&schema, "score = \"number\"")?;
+        let args = Args {
+            home: Some(dir.path().to_path_buf()),
+            schema: Some(schema),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+        let statement = Parser::parse_sql(
+            &FilesDialect {},
+            "SELECT id, score + 1 AS next FROM huge WHERE score = 99",
+        )?
+        .remove(0);
+
+        let mut stream = stream_statement(&engine, &statement)?.unwrap();
+        let first = stream.next().unwrap()?;
+        assert_eq!(first.get(&Column::from_index(0)), &Value::Number(99.into()));
+        assert_eq!(
+            first.get(&Column::from_index(1)),
+            &Value::Number(100.into())
+        );
+
+        // Every row is dropped right after it is read, so the memory does not grow with the file
+        let mut count = 1;
+        let mut error = None;
+        for row in stream {
+            match row {
+                Ok(_) => count += 1,
+                Err(err) => error = Some(err),
+            }
+        }
+        assert_eq!(count, rows / 100);
+        assert!(matches!(error, Some(CvsSqlError::SchemaMismatch(_, _, _))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn limit_stops_reading_the_file() -> Result<(), CvsSqlError> {
+        let dir = tempdir()?;
+        let rows = 200_000;
+        {
+            let mut file = BufWriter::new(File::create(dir.path().join("huge.csv"))?);
+            writeln!(file, "id,score")?;
+            for id in 0..10 {
+                writeln!(file, "{id},{id}")?;
+            }
+            // Reading more than the first few rows would fail because of this line
+            writeln!(file, "10,not a number")?;
+            for id in 11..rows {
+                writeln!(file, "{id},{id}")?;
+            }
+        }
+        let schema = dir.path().join("schema.toml");
+        fs::write(&schema, "score = \"number\"")?;
+        let args = Args {
+            home: Some(dir.path().to_path_buf()),
+            schema: Some(schema),
+            ..Args::default()
+        };
+        let engine = Engine::try_from(&args)?;
+
+        let (streamed, rows) = stream_or_extract(
+            &engine,
+            "SELECT id FROM huge WHERE score > 2 LIMIT 5 OFFSET 1",
+        )?;
+        assert!(streamed);
+        let expected: Vec<Vec<Value>> = (4..9).map(|id| vec![Value::Number(id.into())]).collect();
+        assert_eq!(rows, expected);
+
+        let results = stream_or_extract(&engine, "SELECT id FROM huge LIMIT 20");
+        assert!(matches!(results, Err(CvsSqlError::SchemaMismatch(_, _, _))));
+
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ use std::io;
 use crossterm::event::{self, Event};
 use ratatui::{Frame, Terminal, prelude::Backend};
 
-use crate::{error::CvsSqlError, results::ResultSet};
+use crate::{error::CvsSqlError, results::ResultSet, writer::display_value};
 
 use ratatui::{
     crossterm::event::{KeyCode, KeyEventKind},
@@ -21,9 +21,9 @@ use unicode_width::UnicodeWidthStr;
 const PALETTE: tailwind::Palette = tailwind::SKY;
 const INFO_TEXT: &str = "(Esc) quit | (↑) move up | (↓) move down";
 
-pub(crate) fn draw_table(results: &ResultSet) -> Result<(), CvsSqlError> {
+pub(crate) fn draw_table(results: &ResultSet, null_string: &str) -> Result<(), CvsSqlError> {
     let mut terminal = ratatui::init();
-    let result = TableApp::new(results, event::read).run(&mut terminal);
+    let result = TableApp::new(results, null_string, event::read).run(&mut terminal);
     ratatui::restore();
     result
 }
@@ -69,7 +69,7 @@ struct TableApp {
 }
 
 impl TableApp {
-    fn new(results: &ResultSet, next_event: fn() -> io::Result<Event>) -> Self {
+    fn new(results: &ResultSet, null_string: &str, next_event: fn() -> io::Result<Event>) -> Self {
         let mut headers = vec![];
         let mut longest_item_lens = vec![];
         for col in results.columns() {
@@ -82,7 +82,7 @@ impl TableApp {
         for row in results.data.iter() {
             let mut line = vec![];
             for col in results.columns() {
-                let val = row.get(&col).to_string();
+                let val = display_value(row.get(&col), null_string);
                 let width = UnicodeWidthStr::width(val.as_str());
                 if longest_item_lens[col.get_index()] < width {
                     longest_item_lens[col.get_index()] = width;
@@ -328,7 +328,7 @@ mod tests {
 
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
 
-        let mut table = TableApp::new(results, send_q);
+        let mut table = TableApp::new(results, "", send_q);
         table.run(&mut terminal)?;
         assert_snapshot!(terminal.backend());
 
@@ -0,0 +1,341 @@
+use std::ops::Deref;
+use std::rc::Rc;
+
+use bigdecimal::{BigDecimal, ToPrimitive, Zero};
+use chrono::{Duration, NaiveDate};
+use regex::Regex;
+use sqlparser::ast::{
+    Expr, FunctionArg, FunctionArgExpr, Interval, ObjectName, TableFactor, TableFunctionArgs,
+};
+
+use crate::{
+    engine::Engine,
+    error::CvsSqlError,
+    group_by::GroupRow,
+    named_results::alias_results,
+    projections::SingleConvert,
+    result_set_metadata::{Metadata, SimpleResultSetMetadata},
+    results::ResultSet,
+    results_data::{DataRow, ResultsData},
+    value::Value,
+};
+
+/// The arguments of a table function are evaluated on a single row: an empty one, or a row of the
+/// tables before the function (see `extract_lateral_table_function`).
+struct Scope<'a> {
+    engine: &'a Engine,
+    metadata: &'a Metadata,
+    row: &'a GroupRow,
+}
+impl Scope<'_> {
+    fn evaluate(&self, expr: &Expr) -> Result<Value, CvsSqlError> {
+        let projection = expr.convert_single(self.metadata, self.engine)?;
+        Ok(projection.get(self.row).deref().clone())
+    }
+}
+
+pub(crate) fn extract_table_function(
+    engine: &Engine,
+    name: &ObjectName,
+    args: &TableFunctionArgs,
+) -> Result<ResultSet, CvsSqlError> {
+    let metadata = SimpleResultSetMetadata::new(None).build();
+    let row = GroupRow {
+        data: DataRow::new(vec![]),
+        group_rows: vec![],
+    };
+    let scope = Scope {
+        engine,
+        metadata: &metadata,
+        row: &row,
+    };
+    table_function(&scope, name, args)
+}
+
+/// A table function whose arguments use the columns of the tables before it (like
+/// `FROM customers, REGEXP_SPLIT_TO_TABLE(customers.name, ' ')`) is called for every row of those
+/// tables, and its rows are joined to that row. Returns `None` if the relation is not a table
+/// function.
+pub(crate) fn extract_lateral_table_function(
+    engine: &Engine,
+    left: &ResultSet,
+    relation: &TableFactor,
+) -> Result<Option<ResultSet>, CvsSqlError> {
+    let TableFactor::Table {
+        name,
+        alias,
+        args: Some(args),
+        sample: None,
+        ..
+    } = relation
+    else {
+        return Ok(None);
+    };
+    let mut data = vec![];
+    for left_row in left.data.iter() {
+        let row = GroupRow {
+            data: left_row.clone(),
+            group_rows: vec![],
+        };
+        let scope = Scope {
+            engine,
+            metadata: &left.metadata,
+            row: &row,
+        };
+        let results = table_function(&scope, name, args)?;
+        for right_row in results.data.iter() {
+            let values = left
+                .columns()
+                .map(|column| left_row.get(&column).clone())
+                .chain(
+                    results
+                        .columns()
+                        .map(|column| right_row.get(&column).clone()),
+                )
+                .collect();
+            data.push(DataRow::new(values));
+        }
+    }
+    let mut right = single_column_results(vec![]);
+    if let Some(alias) = alias {
+        if !alias.columns.is_empty() {
+            return Err(CvsSqlError::Unsupported(
+                "SELECT ... FROM with subquery column alias".to_string(),
+            ));
+        }
+        right = alias_results(&alias.name, right);
+    }
+    let metadata = Rc::new(Metadata::product(&left.metadata, &right.metadata));
+    let data = ResultsData::new(data);
+    Ok(Some(ResultSet { metadata, data }))
+}
+
+fn table_function(
+    scope: &Scope,
+    name: &ObjectName,
+    args: &TableFunctionArgs,
+) -> Result<ResultSet, CvsSqlError> {
+    if args.settings.is_some() {
+        return Err(CvsSqlError::Unsupported(format!(
+            "table function {name} with settings"
+        )));
+    }
+    let function_name = name.to_string().to_uppercase();
+    let mut arguments = vec![];
+    for arg in &args.args {
+        let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg else {
+            return Err(CvsSqlError::Unsupported(format!(
+                "{arg} as argument in table function {function_name}"
+            )));
+        };
+        arguments.push(expr);
+    }
+    match function_name.as_str() {
+        "GENERATE_SERIES" => generate_series(scope, &arguments),
+        "REGEXP_SPLIT_TO_TABLE" => regexp_split_to_table(scope, &arguments),
+        _ => Err(CvsSqlError::Unsupported(format!("table function {name}"))),
+    }
+}
+
+fn single_column_results(values: Vec<Value>) -> ResultSet {
+    let mut metadata = SimpleResultSetMetadata::new(None);
+    metadata.add_column("value");
+    let metadata = Rc::new(metadata.build());
+    let data = values
+        .into_iter()
+        .map(|value| DataRow::new(vec![value]))
+        .collect();
+    let data = ResultsData::new(data);
+    ResultSet { metadata, data }
+}
+
+fn generate_series(scope: &Scope, arguments: &[&Expr]) -> Result<ResultSet, CvsSqlError> {
+    let invalid = |reason: &str| {
+        CvsSqlError::InvalidTableFunctionArguments("GENERATE_SERIES".into(), reason.into())
+    };
+    let (start, end, step) = match arguments {
+        [start, end] => (start, end, None),
+        [start, end, step] => (start, end, Some(step)),
+        _ => return Err(invalid("expecting start, end and an optional step")),
+    };
+    match (scope.evaluate(start)?, scope.evaluate(end)?) {
+        (Value::Number(start), Value::Number(end)) => {
+            let step = match step {
+                None => BigDecimal::from(1),
+                Some(step) => match scope.evaluate(step)? {
+                    Value::Number(step) => step,
+                    _ => return Err(invalid("the step of a numeric series must be a number")),
+                },
+            };
+            if step.is_zero() {
+                return Err(invalid("the step can not be zero"));
+            }
+            let mut values = vec![];
+            let mut current = start;
+            while (step > BigDecimal::zero() && current <= end)
+                || (step < BigDecimal::zero() && current >= end)
+            {
+                values.push(Value::Number(current.clone()));
+                current += &step;
+            }
+            Ok(single_column_results(values))
+        }
+        (Value::Date(start), Value::Date(end)) => {
+            let days = match step {
+                None => 1,
+                Some(Expr::Interval(interval)) => interval_days(interval).ok_or_else(|| {
+                    invalid("the step of a date series must be an interval of days or weeks")
+                })?,
+                Some(step) => match scope.evaluate(step)? {
+                    Value::Number(days) if days.is_integer() => days.to_i64().ok_or_else(|| {
+                        invalid("the step of a date series must be a whole number of days")
+                    })?,
+                    _ => return Err(invalid("the step of a date series must be an interval")),
+                },
+            };
+            if days == 0 {
+                return Err(invalid("the step can not be zero"));
+            }
+            let step = Duration::try_days(days).ok_or_else(|| invalid("the step is too large"))?;
+            let mut values = vec![];
+            let mut current: NaiveDate = start;
+            while (days > 0 && current <= end) || (days < 0 && current >= end) {
+                values.push(Value::Date(current));
+                let Some(next) = current.checked_add_signed(step) else {
+                    break;
+                };
+                current = next;
+            }
+            Ok(single_column_results(values))
+        }
+        _ => Err(invalid("start and end must be both numbers or both dates")),
+    }
+}
+
+fn regexp_split_to_table(scope: &Scope, arguments: &[&Expr]) -> Result<ResultSet, CvsSqlError> {
+    let invalid = |reason: &str| {
+        CvsSqlError::InvalidTableFunctionArguments("REGEXP_SPLIT_TO_TABLE".into(), reason.into())
+    };
+    let [text, pattern] = arguments else {
+        return Err(invalid("expecting a string and a pattern"));
+    };
+    let pattern = match scope.evaluate(pattern)? {
+        Value::Str(pattern) => pattern,
+        _ => return Err(invalid("the pattern must be a string")),
+    };
+    let pattern = Regex::new(&pattern).map_err(|e| CvsSqlError::InvalidRegex(e.to_string()))?;
+    let values = match scope.evaluate(text)? {
+        Value::Empty => vec![],
+        text => pattern
+            .split(&text.to_string())
+            .map(|token| Value::Str(token.to_string()))
+            .collect(),
+    };
+    Ok(single_column_results(values))
+}
+
+fn interval_days(interval: &Interval) -> Option<i64> {
+    let Expr::Value(value) = interval.value.deref() else {
+        return None;
+    };
+    let text = value.value.clone().into_string()?;
+    let mut parts = text.split_whitespace();
+    let count: i64 = parts.next()?.parse().ok()?;
+    let unit = match (&interval.leading_field, parts.next()) {
+        (Some(field), None) => field.to_string(),
+        (None, Some(unit)) => unit.to_uppercase(),
+        _ => return None,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    let multiplier = match unit.as_str() {
+        "DAY" | "DAYS" => 1,
+        "WEEK" | "WEEKS" => 7,
+        _ => return None,
+    };
+    count.checked_mul(multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::{args::Args, engine::Engine, error::CvsSqlError, results::Column, value::Value};
+
+    fn series(sql: &str) -> Result<Vec<Value>, CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+        let results = engine.execute_commands(sql)?;
+        let results = &results.first().unwrap().results;
+        Ok(results
+            .data
+            .iter()
+            .map(|row| row.get(&Column::from_index(0)).clone())
+            .collect())
+    }
+
+    #[test]
+    fn numeric_series() -> Result<(), CvsSqlError> {
+        let values = series("SELECT * FROM GENERATE_SERIES(1, 100)")?;
+
+        assert_eq!(values.len(), 100);
+        assert_eq!(values.first(), Some(&Value::Number(1.into())));
+        assert_eq!(values.last(), Some(&Value::Number(100.into())));
+
+        Ok(())
+    }
+
+    #[test]
+    fn numeric_series_with_step() -> Result<(), CvsSqlError> {
+        let values = series("SELECT * FROM GENERATE_SERIES(20, 1, -3)")?;
+
+        assert_eq!(values.len(), 7);
+        assert_eq!(values.first(), Some(&Value::Number(20.into())));
+        assert_eq!(values.last(), Some(&Value::Number(2.into())));
+
+        Ok(())
+    }
+
+    #[test]
+    fn empty_numeric_series() -> Result<(), CvsSqlError> {
+        let values = series("SELECT * FROM GENERATE_SERIES(5, 1)")?;
+
+        assert!(values.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn regexp_split_to_table() -> Result<(), CvsSqlError> {
+        let values = series("SELECT * FROM REGEXP_SPLIT_TO_TABLE('red  green\tblue', '\\s+')")?;
+
+        assert_eq!(
+            values,
+            vec![
+                Value::Str("red".into()),
+                Value::Str("green".into()),
+                Value::Str("blue".into())
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn date_series() -> Result<(), CvsSqlError> {
+        let values =
+            series("SELECT * FROM GENERATE_SERIES('2024-12-30', '2025-01-13', INTERVAL '2 days')")?;
+
+        assert_eq!(values.len(), 8);
+        assert_eq!(
+            values.first(),
+            Some(&Value::Date(NaiveDate::from_ymd_opt(2024, 12, 30).unwrap()))
+        );
+        assert_eq!(
+            values.last(),
+            Some(&Value::Date(NaiveDate::from_ymd_opt(2025, 1, 13).unwrap()))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use sqlparser::ast::{Expr, FunctionArg, FunctionArgExpr, FunctionArguments};
+
+use crate::{
+    engine::Engine,
+    error::CvsSqlError,
+    group_by::GroupRow,
+    outputer::{row_as_json, value_as_json},
+    projections::{Projection, SingleConvert},
+    result_set_metadata::Metadata,
+    results::{Column, ColumnIndexError, Name},
+    util::SmartReference,
+    value::Value,
+};
+
+struct RowToJson {
+    columns: Vec<(String, Column)>,
+    name: String,
+}
+
+impl Projection for RowToJson {
+    fn get<'a>(&'a self, row: &'a GroupRow) -> SmartReference<'a, Value> {
+        let json = row_as_json(
+            self.columns
+                .iter()
+                .map(|(name, column)| (name.as_str(), row.data.get(column))),
+        );
+        Value::Str(json.to_string()).into()
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+struct ValueToJson {
+    value: Box<dyn Projection>,
+    name: String,
+}
+
+impl Projection for ValueToJson {
+    fn get<'a>(&'a self, row: &'a GroupRow) -> SmartReference<'a, Value> {
+        let value = self.value.get(row);
+        Value::Str(value_as_json(&value).to_string()).into()
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+pub(crate) fn create_to_json(
+    metadata: &Metadata,
+    engine: &Engine,
+    args: &FunctionArguments,
+) -> Result<Box<dyn Projection>, CvsSqlError> {
+    let argument = match args {
+        FunctionArguments::List(lst) if lst.args.len() == 1 && lst.clauses.is_empty() => {
+            &lst.args[0]
+        }
+        _ => {
+            return Err(CvsSqlError::Unsupported(
+                "Function TO_JSON must have a single argument".into(),
+            ));
+        }
+    };
+    let name = format!("TO_JSON({argument})");
+    let table = match argument {
+        FunctionArg::Unnamed(FunctionArgExpr::Wildcard) => None,
+        FunctionArg::Unnamed(FunctionArgExpr::QualifiedWildcard(table)) => Some(table.into()),
+        FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => {
+            let table: Name = match expr {
+                Expr::Identifier(ident) => ident.into(),
+                Expr::CompoundIdentifier(idents) => idents
+                    .iter()
+                    .map(|i| i.value.to_string())
+                    .collect::<Vec<_>>()
+                    .into(),
+                _ => {
+                    let value = expr.convert_single(metadata, engine)?;
+                    return Ok(Box::new(ValueToJson { value, name }));
+                }
+            };
+            if metadata.column_index(&table).is_ok() {
+                let value = expr.convert_single(metadata, engine)?;
+                return Ok(Box::new(ValueToJson { value, name }));
+            }
+            Some(table)
+        }
+        _ => return Err(CvsSqlError::Unsupported(format!("TO_JSON({argument})"))),
+    };
+
+    let mut columns = vec![];
+    for column in metadata.columns() {
+        let Some(column_name) = metadata.column_name(&column) else {
+            continue;
+        };
+        if let Some(table) = &table {
+            let in_table = column_name
+                .parent()
+                .is_some_and(|parent| parent.available_names().contains(table));
+            if !in_table {
+                continue;
+            }
+        }
+        columns.push((column_name.short_name().to_string(), column));
+    }
+    if let Some(table) = table
+        && columns.is_empty()
+    {
+        return Err(ColumnIndexError::NoSuchColumn(table.full_name()).into());
+    }
+
+    Ok(Box::new(RowToJson { columns, name }))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value as JsonValue;
+
+    use crate::{args::Args, engine::Engine, error::CvsSqlError, outputer::row_as_json};
+
+    use super::*;
+
+    #[test]
+    fn to_json_matches_json_output() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let rows = engine.execute_commands("SELECT * FROM tests.data.customers")?;
+        let rows = &rows.first().unwrap().results;
+        let jsons = engine
+            .execute_commands("SELECT TO_JSON(customers) FROM tests.data.customers AS customers")?;
+        let jsons = &jsons.first().unwrap().results;
+
+        assert_eq!(rows.data.iter().count(), jsons.data.iter().count());
+        for (row, json) in rows.data.iter().zip(jsons.data.iter()) {
+            let expected = row_as_json(
+                rows.columns()
+                    .map(|col| (rows.metadata.column_title(&col), row.get(&col))),
+            );
+            let Value::Str(json) = json.get(&Column::from_index(0)) else {
+                panic!("TO_JSON should return a string");
+            };
+            let json: JsonValue = serde_json::from_str(json)?;
+            assert_eq!(json, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn to_json_of_a_value() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let results = engine
+            .execute_commands("SELECT TO_JSON(name), TO_JSON(artist_id) FROM tests.data.artists")?;
+        let results = &results.first().unwrap().results;
+        let row = results.data.iter().next().unwrap();
+        assert_eq!(
+            row.get(&Column::from_index(0)),
+            &Value::Str("\"AC/DC\"".into())
+        );
+        assert_eq!(row.get(&Column::from_index(1)), &Value::Str("1".into()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn to_json_of_unknown_table() -> Result<(), CvsSqlError> {
+        let engine = Engine::try_from(&Args::default())?;
+
+        let results = engine.execute_commands("SELECT TO_JSON(albums) FROM tests.data.artists");
+        assert!(matches!(results, Err(CvsSqlError::ColumnIndexError(_))));
+
+        Ok(())
+    }
+}
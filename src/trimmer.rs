@@ -20,21 +20,7 @@ pub(crate) fn trim(
     results: &mut GroupedResultSet,
 ) -> Result<(), CvsSqlError> {
     if let Some(offset) = offset {
-        let metadata = Metadata::Simple(SimpleResultSetMetadata::new(None));
-        let offset = offset.convert_single(&metadata, engine)?;
-        let data_row = DataRow::new(vec![]);
-        let temp_row = GroupRow {
-            data: data_row,
-            group_rows: vec![],
-        };
-        let offset = offset.get(&temp_row);
-
-        let Value::Number(offset) = offset.deref() else {
-            return Err(CvsSqlError::NoNumericOffset);
-        };
-        let Some(offset) = offset.to_usize() else {
-            return Err(CvsSqlError::NoNumericOffset);
-        };
+        let offset = offset_size(offset, engine)?;
         if offset >= results.rows.len() {
             results.rows.clear();
             return Ok(());
@@ -43,23 +29,38 @@ pub(crate) fn trim(
         results.rows.drain(0..offset);
     }
     if let Some(limit) = limit {
-        let metadata = Metadata::Simple(SimpleResultSetMetadata::new(None));
-        let limit = limit.convert_single(&metadata, engine)?;
-        let data_row = DataRow::new(vec![]);
-        let temp_row = GroupRow {
-            data: data_row,
-            group_rows: vec![],
-        };
-        let limit = limit.get(&temp_row);
-
-        let Value::Number(limit) = limit.deref() else {
-            return Err(CvsSqlError::NoNumericLimit);
-        };
-        let Some(limit) = limit.to_usize() else {
-            return Err(CvsSqlError::NoNumericLimit);
-        };
+        let limit = limit_size(limit, engine)?;
         results.rows.truncate(limit);
     }
 
     Ok(())
 }
+
+/// The number of rows to skip, an `OFFSET` must be a non negative whole number.
+pub(crate) fn offset_size(offset: &Expr, engine: &Engine) -> Result<usize, CvsSqlError> {
+    size(offset, engine)?.ok_or(CvsSqlError::NoNumericOffset)
+}
+
+/// The maximal number of rows, a `LIMIT` must be a non negative whole number.
+pub(crate) fn limit_size(limit: &Expr, engine: &Engine) -> Result<usize, CvsSqlError> {
+    size(limit, engine)?.ok_or(CvsSqlError::NoNumericLimit)
+}
+
+fn size(expr: &Expr, engine: &Engine) -> Result<Option<usize>, CvsSqlError> {
+    let metadata = Metadata::Simple(SimpleResultSetMetadata::new(None));
+    let size = expr.convert_single(&metadata, engine)?;
+    let data_row = DataRow::new(vec![]);
+    let temp_row = GroupRow {
+        data: data_row,
+        group_rows: vec![],
+    };
+    let size = size.get(&temp_row);
+
+    let Value::Number(size) = size.deref() else {
+        return Ok(None);
+    };
+    if !size.is_integer() {
+        return Ok(None);
+    }
+    Ok(size.to_usize())
+}
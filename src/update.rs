@@ -15,7 +15,7 @@ use crate::{
     results_builder::build_simple_results,
     results_data::ResultsData,
     value::Value,
-    writer::{Writer, new_csv_writer},
+    writer::{Writer, new_delimited_csv_writer},
 };
 
 pub(crate) fn update_table(
@@ -121,12 +121,18 @@ pub(crate) fn update_table(
     let data = ResultsData::new(new_data);
     let results = ResultSet { metadata, data };
 
-    let file = OpenOptions::new()
-        .write(true)
-        .truncate(true)
-        .open(table_file.path)?;
-    let mut writer = new_csv_writer(file, engine.first_line_as_name);
-    writer.write(&results)?;
+    if engine.should_write(&table_file) {
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .open(&table_file.path)?;
+        let mut writer = new_delimited_csv_writer(
+            file,
+            engine.first_line_as_name,
+            engine.delimiter(&table_file.path),
+        );
+        writer.write(&results)?;
+    }
 
     build_simple_results(vec![
         ("action", Value::Str("UPDATE".to_string())),
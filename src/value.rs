@@ -5,6 +5,7 @@ use std::{
     str::FromStr,
 };
 
+use crate::args::NumericLocale;
 use crate::util::SmartReference;
 use bigdecimal::BigDecimal;
 use bigdecimal::Zero;
@@ -79,6 +80,76 @@ impl From<&str> for Value {
         Value::Str(value.to_string())
     }
 }
+const CURRENCY_SYMBOLS: [char; 5] = ['$', '€', '£', '¥', '₹'];
+
+pub(crate) fn parse_formatted_number(
+    value: &str,
+    locale: Option<NumericLocale>,
+    strip_currency: bool,
+) -> Option<BigDecimal> {
+    if locale.is_none() && !strip_currency {
+        return None;
+    }
+    let mut value = value.trim();
+    let mut negative = false;
+    if let Some(rest) = value.strip_prefix('-') {
+        negative = true;
+        value = rest.trim_start();
+    }
+    if strip_currency {
+        if let Some(rest) = value.strip_prefix(CURRENCY_SYMBOLS) {
+            value = rest.trim_start();
+        } else if let Some(rest) = value.strip_suffix(CURRENCY_SYMBOLS) {
+            value = rest.trim_end();
+        }
+        if !negative && let Some(rest) = value.strip_prefix('-') {
+            negative = true;
+            value = rest;
+        }
+    }
+    let (group_separator, decimal_point) = match locale {
+        Some(NumericLocale::Us) | None => (',', '.'),
+        Some(NumericLocale::Eu) => ('.', ','),
+    };
+    let (integer, fraction) = match value.split_once(decimal_point) {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (value, None),
+    };
+    let all_digits = |str: &str| !str.is_empty() && str.chars().all(|c| c.is_ascii_digit());
+    let integer = if locale.is_some() && integer.contains(group_separator) {
+        let mut groups = integer.split(group_separator);
+        let first = groups.next().unwrap_or_default();
+        if !all_digits(first) || first.len() > 3 {
+            return None;
+        }
+        let mut integer = first.to_string();
+        for group in groups {
+            if group.len() != 3 || !all_digits(group) {
+                return None;
+            }
+            integer.push_str(group);
+        }
+        integer
+    } else if all_digits(integer) {
+        integer.to_string()
+    } else {
+        return None;
+    };
+    let mut number = String::new();
+    if negative {
+        number.push('-');
+    }
+    number.push_str(&integer);
+    if let Some(fraction) = fraction {
+        if !all_digits(fraction) {
+            return None;
+        }
+        number.push('.');
+        number.push_str(fraction);
+    }
+    BigDecimal::from_str(&number).ok()
+}
+
 impl From<bool> for Value {
     fn from(value: bool) -> Self {
         Value::Bool(value)
@@ -346,4 +417,47 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn parse_formatted_number_is_strict_by_default() {
+        assert_eq!(parse_formatted_number("$1,234.50", None, false), None);
+    }
+
+    #[test]
+    fn parse_us_formatted_numbers() {
+        let locale = Some(NumericLocale::Us);
+        let parse = |str| parse_formatted_number(str, locale, true);
+
+        assert_eq!(parse("1,234.50"), BigDecimal::from_str("1234.50").ok());
+        assert_eq!(parse("$1,234.50"), BigDecimal::from_str("1234.50").ok());
+        assert_eq!(parse("-$1,234,567"), BigDecimal::from_str("-1234567").ok());
+        assert_eq!(parse("$-12.5"), BigDecimal::from_str("-12.5").ok());
+        assert_eq!(parse("12 $"), BigDecimal::from_str("12").ok());
+        assert_eq!(parse("1,23.50"), None);
+        assert_eq!(parse("1.234,56"), None);
+        assert_eq!(parse("hello"), None);
+    }
+
+    #[test]
+    fn parse_eu_formatted_numbers() {
+        let locale = Some(NumericLocale::Eu);
+        let parse = |str| parse_formatted_number(str, locale, true);
+
+        assert_eq!(parse("1.234,56"), BigDecimal::from_str("1234.56").ok());
+        assert_eq!(parse("1.234.567"), BigDecimal::from_str("1234567").ok());
+        assert_eq!(parse("€ 1.234,56"), BigDecimal::from_str("1234.56").ok());
+        assert_eq!(parse("1.234,56 €"), BigDecimal::from_str("1234.56").ok());
+        assert_eq!(parse("-0,5"), BigDecimal::from_str("-0.5").ok());
+        assert_eq!(parse("1,234.56"), None);
+        assert_eq!(parse("2024-10-08"), None);
+    }
+
+    #[test]
+    fn parse_currency_without_locale() {
+        let parse = |str| parse_formatted_number(str, None, true);
+
+        assert_eq!(parse("$12.50"), BigDecimal::from_str("12.50").ok());
+        assert_eq!(parse("£100"), BigDecimal::from_str("100").ok());
+        assert_eq!(parse("$1,234.50"), None);
+    }
 }
@@ -0,0 +1,351 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::rc::Rc;
+
+use bigdecimal::{BigDecimal, ToPrimitive, Zero};
+use sqlparser::ast::{
+    Expr, Function, FunctionArg, FunctionArgExpr, FunctionArguments, NullTreatment, SelectItem,
+    WindowType,
+};
+
+use crate::{
+    engine::Engine,
+    error::CvsSqlError,
+    functions::sum_values,
+    group_by::{GroupRow, GroupedResultSet},
+    order_by_results::{OrderByItem, compare_keys},
+    projections::{Projection, SingleConvert},
+    result_set_metadata::{Metadata, SimpleResultSetMetadata},
+    results::Column,
+    value::Value,
+};
+
+/// Calculates the window functions of the selected items (like `ROW_NUMBER() OVER (...)`) and adds
+/// their values as new columns, so the projection (and the ordering) can use them.
+pub(crate) fn add_window_columns(
+    engine: &Engine,
+    items: &[SelectItem],
+    results: &mut GroupedResultSet,
+) -> Result<(), CvsSqlError> {
+    let mut functions = vec![];
+    for item in items {
+        match item {
+            SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, alias: _ } => {
+                find_window_functions(expr, &mut functions);
+            }
+            _ => {}
+        }
+    }
+    if functions.is_empty() {
+        return Ok(());
+    }
+
+    let mut windows = SimpleResultSetMetadata::new(None);
+    let first_column = results.metadata.number_of_columns();
+    for function in functions {
+        let values = window_values(engine, &results.metadata, &results.rows, function)?;
+        let column = Column::from_index(first_column + windows.len());
+        for (row, value) in results.rows.iter_mut().zip(values) {
+            row.data.insert_at(&column, value);
+        }
+        windows.add_column(&function.to_string());
+    }
+
+    let parent = results.metadata.clone();
+    let metadata = match results.metadata.deref() {
+        Metadata::Grouped {
+            parent: grouped,
+            this: _,
+        } => Metadata::Grouped {
+            parent: grouped.clone(),
+            this: Box::new(Metadata::Windowed { parent, windows }),
+        },
+        _ => Metadata::Windowed { parent, windows },
+    };
+    results.metadata = Rc::new(metadata);
+    Ok(())
+}
+
+fn find_window_functions<'a>(expr: &'a Expr, functions: &mut Vec<&'a Function>) {
+    match expr {
+        Expr::Function(function) if function.over.is_some() => {
+            let name = function.to_string();
+            if !functions.iter().any(|f| f.to_string() == name) {
+                functions.push(function);
+            }
+        }
+        Expr::Function(function) => {
+            if let FunctionArguments::List(args) = &function.args {
+                for arg in &args.args {
+                    if let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg {
+                        find_window_functions(expr, functions);
+                    }
+                }
+            }
+        }
+        Expr::BinaryOp { left, op: _, right } => {
+            find_window_functions(left, functions);
+            find_window_functions(right, functions);
+        }
+        Expr::UnaryOp { op: _, expr }
+        | Expr::Nested(expr)
+        | Expr::Cast { expr, .. }
+        | Expr::IsNull(expr)
+        | Expr::IsNotNull(expr) => find_window_functions(expr, functions),
+        _ => {}
+    }
+}
+
+enum WindowFunction {
+    RowNumber,
+    Rank,
+    DenseRank,
+    Sum {
+        value: Box<dyn Projection>,
+        filter: Option<Box<dyn Projection>>,
+    },
+    Shift {
+        value: Box<dyn Projection>,
+        offset: Option<Box<dyn Projection>>,
+        default: Option<Box<dyn Projection>>,
+        forward: bool,
+        ignore_nulls: bool,
+    },
+}
+
+impl WindowFunction {
+    fn create(
+        engine: &Engine,
+        metadata: &Metadata,
+        function: &Function,
+    ) -> Result<Self, CvsSqlError> {
+        let name = function.name.to_string().to_uppercase();
+        let args = match &function.args {
+            FunctionArguments::None => vec![],
+            FunctionArguments::List(args) => {
+                if args.duplicate_treatment.is_some() || !args.clauses.is_empty() {
+                    return Err(CvsSqlError::Unsupported(format!(
+                        "{function} (only simple arguments are supported)"
+                    )));
+                }
+                args.args.iter().collect()
+            }
+            FunctionArguments::Subquery(_) => {
+                return Err(CvsSqlError::Unsupported(format!(
+                    "{function} with subquery"
+                )));
+            }
+        };
+        // Only the aggregation takes a filter, like `SUM(x) FILTER (WHERE y) OVER (...)`
+        let filter = match &function.filter {
+            None => None,
+            Some(filter) if name == "SUM" => Some(filter.convert_single(metadata, engine)?),
+            Some(filter) => {
+                return Err(CvsSqlError::Unsupported(format!(
+                    "{name} with FILTER (WHERE {filter})"
+                )));
+            }
+        };
+        let ignore_nulls = match &function.null_treatment {
+            None | Some(NullTreatment::RespectNulls) => false,
+            Some(NullTreatment::IgnoreNulls) if name == "LAG" || name == "LEAD" => true,
+            Some(null_treatment) => {
+                return Err(CvsSqlError::Unsupported(format!(
+                    "{name} with {null_treatment}"
+                )));
+            }
+        };
+        let window_function = match name.as_str() {
+            "ROW_NUMBER" => Self::RowNumber,
+            "RANK" => Self::Rank,
+            "DENSE_RANK" => Self::DenseRank,
+            "SUM" => match args.as_slice() {
+                [FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))] => {
+                    return Ok(Self::Sum {
+                        value: expr.convert_single(metadata, engine)?,
+                        filter,
+                    });
+                }
+                _ => {
+                    return Err(CvsSqlError::Unsupported(format!(
+                        "{function} (SUM expects a single argument)"
+                    )));
+                }
+            },
+            "LAG" | "LEAD" => {
+                let mut args = args
+                    .iter()
+                    .map(|arg| match arg {
+                        FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => {
+                            expr.convert_single(metadata, engine)
+                        }
+                        _ => Err(CvsSqlError::Unsupported(format!(
+                            "{function} (only simple arguments are supported)"
+                        ))),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into_iter();
+                let (Some(value), offset, default, None) =
+                    (args.next(), args.next(), args.next(), args.next())
+                else {
+                    return Err(CvsSqlError::Unsupported(format!(
+                        "{function} ({name} expects one to three arguments)"
+                    )));
+                };
+                return Ok(Self::Shift {
+                    value,
+                    offset,
+                    default,
+                    forward: name == "LEAD",
+                    ignore_nulls,
+                });
+            }
+            _ => {
+                return Err(CvsSqlError::Unsupported(format!(
+                    "window function {name} (only ROW_NUMBER, RANK, DENSE_RANK, SUM, LAG and LEAD are supported)"
+                )));
+            }
+        };
+        if !args.is_empty() {
+            return Err(CvsSqlError::Unsupported(format!(
+                "{function} with arguments"
+            )));
+        }
+        Ok(window_function)
+    }
+}
+
+fn window_values(
+    engine: &Engine,
+    metadata: &Metadata,
+    rows: &[GroupRow],
+    function: &Function,
+) -> Result<Vec<Value>, CvsSqlError> {
+    let window_function = WindowFunction::create(engine, metadata, function)?;
+    let Some(WindowType::WindowSpec(spec)) = &function.over else {
+        return Err(CvsSqlError::Unsupported(format!(
+            "{function} (named window)"
+        )));
+    };
+    if spec.window_name.is_some() {
+        return Err(CvsSqlError::Unsupported(format!(
+            "{function} (named window)"
+        )));
+    }
+    if spec.window_frame.is_some() {
+        return Err(CvsSqlError::Unsupported(format!(
+            "{function} (window frame)"
+        )));
+    }
+
+    let partition_by = spec
+        .partition_by
+        .iter()
+        .map(|expr| expr.convert_single(metadata, engine))
+        .collect::<Result<Vec<_>, _>>()?;
+    let order_by = spec
+        .order_by
+        .iter()
+        .map(|expr| OrderByItem::create(metadata, engine, expr, &expr.expr))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut partitions: HashMap<Vec<Value>, Vec<(Vec<Value>, usize)>> = HashMap::new();
+    for (index, row) in rows.iter().enumerate() {
+        let partition: Vec<_> = partition_by
+            .iter()
+            .map(|item| item.get(row).deref().clone())
+            .collect();
+        let keys: Vec<_> = order_by
+            .iter()
+            .map(|item| item.by.get(row).deref().clone())
+            .collect();
+        partitions.entry(partition).or_default().push((keys, index));
+    }
+
+    let mut values = vec![Value::Empty; rows.len()];
+    for mut partition in partitions.into_values() {
+        partition.sort_by(|(left, _), (right, _)| compare_keys(&order_by, left, right));
+        // Rows with the same ordering keys are peers; they share the rank and the running sum.
+        let peers = partition.chunk_by(|(left, _), (right, _)| {
+            compare_keys(&order_by, left, right) == Ordering::Equal
+        });
+        let mut position = 0;
+        let mut total = BigDecimal::zero();
+        for (peer_index, peers) in peers.enumerate() {
+            if let WindowFunction::Sum { value, filter } = &window_function {
+                let mut data = peers
+                    .iter()
+                    .map(|(_, index)| &rows[*index])
+                    .filter(|row| match filter {
+                        Some(filter) => filter.get(row).deref() == &Value::Bool(true),
+                        None => true,
+                    })
+                    .map(|row| value.get(row).deref().clone());
+                if let Value::Number(sum) = sum_values(&mut data) {
+                    total += sum;
+                }
+            }
+            for (peer_offset, (_, index)) in peers.iter().enumerate() {
+                values[*index] = match &window_function {
+                    WindowFunction::RowNumber => number(position + peer_offset + 1),
+                    WindowFunction::Rank => number(position + 1),
+                    WindowFunction::DenseRank => number(peer_index + 1),
+                    WindowFunction::Sum { .. } => Value::Number(total.clone()),
+                    WindowFunction::Shift {
+                        value,
+                        offset,
+                        default,
+                        forward,
+                        ignore_nulls,
+                    } => {
+                        let row = &rows[*index];
+                        let current = position + peer_offset;
+                        let offset = match offset.as_ref().map(|offset| offset.get(row)) {
+                            None => Some(1),
+                            Some(offset) => match offset.deref() {
+                                Value::Number(num) if num.is_integer() => num.to_usize(),
+                                _ => None,
+                            },
+                        };
+                        let target = offset.and_then(|offset| {
+                            if offset == 0 {
+                                Some(current)
+                            } else if *ignore_nulls {
+                                // The offset counts only the rows with a value
+                                let candidates: Box<dyn Iterator<Item = usize>> = if *forward {
+                                    Box::new(current + 1..partition.len())
+                                } else {
+                                    Box::new((0..current).rev())
+                                };
+                                candidates
+                                    .filter(|candidate| {
+                                        let (_, target) = &partition[*candidate];
+                                        !value.get(&rows[*target]).is_empty()
+                                    })
+                                    .nth(offset - 1)
+                            } else if *forward {
+                                current.checked_add(offset)
+                            } else {
+                                current.checked_sub(offset)
+                            }
+                        });
+                        match target.and_then(|target| partition.get(target)) {
+                            Some((_, target)) => value.get(&rows[*target]).deref().clone(),
+                            None => match default {
+                                Some(default) => default.get(row).deref().clone(),
+                                None => Value::Empty,
+                            },
+                        }
+                    }
+                };
+            }
+            position += peers.len();
+        }
+    }
+    Ok(values)
+}
+
+fn number(value: usize) -> Value {
+    Value::Number(BigDecimal::from(value as u64))
+}
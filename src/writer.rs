@@ -4,6 +4,7 @@ use csv::WriterBuilder;
 use thiserror::Error;
 
 use crate::results::ResultSet;
+use crate::value::Value;
 
 pub trait Writer {
     fn write(&mut self, results: &ResultSet) -> Result<(), WriterError>;
@@ -13,6 +14,8 @@ pub trait Writer {
 struct CsvWriter<W: Write> {
     writer: csv::Writer<W>,
     with_headers: bool,
+    typed_header: bool,
+    null_string: String,
 }
 
 impl<W: Write> Writer for CsvWriter<W> {
@@ -23,6 +26,9 @@ impl<W: Write> Writer for CsvWriter<W> {
                 .map(|column| results.metadata.column_title(&column))
                 .collect();
             self.writer.write_record(&headers)?;
+            if self.typed_header {
+                self.writer.write_record(results.column_types())?;
+            }
         }
         self.append(results)
     }
@@ -30,8 +36,7 @@ impl<W: Write> Writer for CsvWriter<W> {
         for row in results.data.iter() {
             let line: Vec<_> = results
                 .columns()
-                .map(|column| row.get(&column))
-                .map(|f| f.to_string())
+                .map(|column| display_value(row.get(&column), &self.null_string))
                 .collect();
             self.writer.write_record(line)?
         }
@@ -41,9 +46,40 @@ impl<W: Write> Writer for CsvWriter<W> {
 }
 
 pub fn new_csv_writer<W: Write>(w: W, with_headers: bool) -> impl Writer {
+    new_delimited_csv_writer(w, with_headers, b',')
+}
+
+/// A writer that separates the values with `delimiter` (for the tables read with `--input-delimiter`).
+pub fn new_delimited_csv_writer<W: Write>(w: W, with_headers: bool, delimiter: u8) -> impl Writer {
     CsvWriter {
-        writer: WriterBuilder::new().from_writer(w),
+        writer: WriterBuilder::new().delimiter(delimiter).from_writer(w),
         with_headers,
+        typed_header: false,
+        null_string: String::new(),
+    }
+}
+
+/// A writer that writes the headers, followed by a line with the type of every column if `typed_header` is set.
+/// The empty values are written as `null_string`.
+pub fn new_typed_csv_writer<W: Write>(
+    w: W,
+    typed_header: bool,
+    delimiter: u8,
+    null_string: &str,
+) -> impl Writer {
+    CsvWriter {
+        writer: WriterBuilder::new().delimiter(delimiter).from_writer(w),
+        with_headers: true,
+        typed_header,
+        null_string: null_string.to_string(),
+    }
+}
+
+/// The value as it should be displayed to the user, with `null_string` for the empty values.
+pub(crate) fn display_value(value: &Value, null_string: &str) -> String {
+    match value {
+        Value::Empty => null_string.to_string(),
+        _ => value.to_string(),
     }
 }
 
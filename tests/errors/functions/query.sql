@@ -29,3 +29,31 @@ SELECT COUNT() FROM tests.data.sales;
 ---
 SELECT MAX(*) FROM tests.data.sales;
 ---
+SELECT LISTAGG(name, ',' ON OVERFLOW ERROR) FROM tests.data.customers;
+---
+SELECT LISTAGG(name, ',', ';') FROM tests.data.customers;
+---
+SELECT MAX(name) WITHIN GROUP (ORDER BY name) FROM tests.data.customers;
+---
+SELECT NTILE(4) OVER (ORDER BY price) FROM tests.data.sales;
+---
+SELECT ROW_NUMBER(price) OVER (ORDER BY price) FROM tests.data.sales;
+---
+SELECT ROW_NUMBER() OVER (ORDER BY price ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) FROM tests.data.sales;
+---
+SELECT id FROM tests.data.sales WHERE ROW_NUMBER() OVER (ORDER BY price) = 1;
+---
+SELECT SUM(price, tax) OVER (ORDER BY price) FROM tests.data.sales;
+---
+SELECT SUM(DISTINCT price) OVER (ORDER BY price) FROM tests.data.sales;
+---
+SELECT LAG() OVER (ORDER BY price) FROM tests.data.sales;
+---
+SELECT UPPER(name) FILTER (WHERE active) FROM tests.data.customers;
+---
+SELECT LISTAGG(name, ',') FILTER (WHERE active) FROM tests.data.customers;
+
+---
+SELECT ROW_NUMBER() FILTER (WHERE active) OVER (ORDER BY name) FROM tests.data.customers;
+---
+SELECT RANK() IGNORE NULLS OVER (ORDER BY name) FROM tests.data.customers;
@@ -1,3 +1,6 @@
 INSERT INTO tests.data.sales VALUES(1);
 ---
 
+
+INSERT INTO tests.data.artists SELECT * FROM tests.data.artists;
+---
@@ -1,6 +1,7 @@
 SELECT *
 FROM tests.data.artists AS artist
-NATURAL JOIN tests.data.albums AS album
+JOIN tests.data.albums AS album
+USING (title);
 
 ---
 
@@ -14,3 +15,8 @@ FROM tests.data.artists
     OUTER APPLY
 (SELECT artist_id FROM tests.data.albums) R;
 ---
+SELECT *
+FROM tests.data.albums AS album
+JOIN tests.data.artists AS artist
+USING (title);
+---
@@ -0,0 +1,10 @@
+SELECT * FROM tests.data.numbers LIMIT -1;
+---
+SELECT * FROM tests.data.numbers OFFSET -3;
+---
+SELECT * FROM tests.data.numbers LIMIT 2 OFFSET -1;
+---
+SELECT * FROM tests.data.numbers LIMIT 1.5;
+---
+SELECT * FROM tests.data.numbers OFFSET 'two';
+---
@@ -0,0 +1,10 @@
+MERGE INTO tests.data.artists AS a USING tests.data.albums AS s ON a.artist_id = s.artist_id WHEN MATCHED THEN DELETE;
+---
+CREATE TEMPORARY TABLE merge_target(artist_id INT, name TEXT);
+INSERT INTO merge_target SELECT * FROM tests.data.artists;
+MERGE INTO merge_target AS a USING tests.data.albums AS s ON a.artist_id = s.artist_id WHEN MATCHED THEN DELETE;
+---
+MERGE INTO merge_target AS a USING tests.data.artists AS s ON a.artist_id = s.artist_id WHEN NOT MATCHED BY SOURCE THEN DELETE;
+---
+MERGE INTO merge_target AS a USING tests.data.artists AS s ON a.artist_id = s.artist_id WHEN NOT MATCHED THEN INSERT (artist_id) VALUES (s.artist_id, s.name);
+---
@@ -0,0 +1,5 @@
+SELECT * FROM tests.data.sales PIVOT (SUM(price * 2) FOR "customer id" IN (ANY));
+---
+SELECT * FROM tests.data.sales PIVOT (SUM(price) FOR "customer id" IN (ANY ORDER BY "customer id"));
+---
+SELECT * FROM tests.data.sales PIVOT (SUM(price) FOR "no such column" IN (ANY));
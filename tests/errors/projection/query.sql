@@ -1,8 +1,14 @@
 SELECT sales.* FROM tests.data.sales;
 ---
-SELECT id ILIKE 'hello' FROM tests.data.sales;
+SELECT id LIKE 'hello' ESCAPE 'ab' FROM tests.data.sales;
 ---
 SELECT id FROM tests.data.sales, tests.data.customers;
 ---
-SELECT EXTRACT(quarter FROM dt) AS should_be_empty_one, EXTRACT(quarter FROM ts) AS should_be_empty_two FROM tests.data.dates ORDER BY amount;
+SELECT EXTRACT(century FROM dt) AS should_be_empty_one, EXTRACT(century FROM ts) AS should_be_empty_two FROM tests.data.dates ORDER BY amount;
+---
+SELECT COLUMNS('[') FROM tests.data.sales;
+---
+SELECT COLUMNS(price) FROM tests.data.sales;
+---
+SELECT * RENAME (nothing AS something) FROM tests.data.sales;
 ---
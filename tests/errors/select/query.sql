@@ -20,7 +20,7 @@ SELECT DISTINCT Country FROM Customers;
 SELECT TOP 20 Country FROM Customers;
 ---
 SELECT * INTO CustomersBackup2017
-FROM Customers;
+FROM tests.data.customers;
 ---
 SELECT * FROM person
     LATERAL VIEW EXPLODE(ARRAY(30, 60)) tableName AS c_age
@@ -93,3 +93,16 @@ SELECT * FROM (VALUES (1, 'one'), (2, 'two'), (3, 'three')) AS t (num,letter);
 ---
 SELECT * FROM TABLE(a0);
 ---
+SELECT * INTO 'output.csv' FROM tests.data.customers;
+---
+SELECT country, COUNT(*) FROM tests.data.customers GROUP BY country WITH TOTALS;
+---
+SELECT country, active, COUNT(*) FROM tests.data.customers GROUP BY GROUPING SETS ((country), (active)) WITH ROLLUP;
+---
+SELECT (SELECT name FROM tests.data.artists) AS name FROM tests.data.artists;
+---
+SELECT (SELECT artist_id, name FROM tests.data.artists WHERE artist_id = 1) AS name FROM tests.data.artists;
+---
+SELECT name FROM tests.data.customers AS c WHERE EXISTS (SELECT 1 FROM tests.data.sales AS s WHERE s."customer id" = c.id);
+---
+SELECT name FROM tests.data.customers AS c WHERE EXISTS (SELECT 1 FROM tests.data.sales AS s WHERE s.no_such_column = 1);
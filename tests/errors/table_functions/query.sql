@@ -0,0 +1,20 @@
+SELECT * FROM GENERATE_SERIES(1, 10, 0);
+---
+SELECT * FROM GENERATE_SERIES(1);
+---
+SELECT * FROM GENERATE_SERIES(1, '2024-01-01');
+---
+SELECT * FROM GENERATE_SERIES('2024-01-01', '2024-02-01', INTERVAL '1 month');
+---
+SELECT * FROM REGEXP_SPLIT_TO_TABLE('a b', '(');
+---
+SELECT * FROM REGEXP_SPLIT_TO_TABLE('a b');
+---
+SELECT * FROM GENERATE_SERIES('2024-01-01', '2024-02-01', INTERVAL '1000000000000000 days');
+---
+SELECT * FROM GENERATE_SERIES('2024-01-01', '2024-02-01', 1000000000000000);
+---
+SELECT * FROM GENERATE_SERIES('2024-01-01', '2024-02-01', INTERVAL '2000000000000000000 weeks');
+---
+SELECT * FROM tests.data.artists JOIN REGEXP_SPLIT_TO_TABLE(artists.name, '\s+') ON TRUE;
+---
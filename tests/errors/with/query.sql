@@ -0,0 +1,7 @@
+WITH RECURSIVE numbers AS (SELECT 1 AS n FROM tests.data.artists UNION ALL SELECT n + 1 FROM numbers WHERE n < 5) SELECT * FROM numbers;
+---
+WITH big AS (SELECT * FROM tests.data.sales WHERE price > 400) SELECT COUNT(*) FROM big;
+SELECT COUNT(*) FROM big;
+---
+WITH big (id) AS (SELECT id FROM tests.data.sales) SELECT * FROM big;
+---
@@ -8,7 +8,7 @@ use std::{
 };
 
 use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive, Zero};
-use chrono::{DateTime, NaiveDateTime};
+use chrono::NaiveDateTime;
 use csvsql::{
     args::Args, engine::Engine, error::CvsSqlError, results::Column, value::Value, writer::Writer,
     writer::new_csv_writer,
@@ -927,7 +927,7 @@ fn test_basic_arithmetic() -> Result<(), CvsSqlError> {
     let engine = Engine::try_from(&args)?;
 
     let mut results = engine.execute_commands(
-        "SELECT 7.131 as num, 4 * 2.2 as eight_dot_eight, 2-10 as minus_eight, 1.2/.3 as four, 20 % 6 as two, 0/0 as nothing, 2 + 3 * 5 - 7 as ten, 0 % 0 as more_nothing FROM tests.data.sales;",
+        "SELECT 7.131 as num, 4 * 2.2 as eight_dot_eight, 2-10 as minus_eight, 1.2/.3 as four, 20 % 6 as two, 0/0 as nothing, 2 + 3 * 5 - 7 as ten, 0 % 0 as more_nothing, 7 DIV 2 as three, -7 DIV 2 + 1 as minus_two, MOD(20, 6) as mod_two, price / 0 as by_zero, price / NULL as by_null, price DIV 0 as div_by_zero, price DIV NULL as div_by_null, price % 0 as rem_by_zero, MOD(price, 0) as mod_by_zero, MOD(price, NULL) as mod_by_null FROM tests.data.sales;",
     )?;
 
     assert_eq!(results.len(), 1);
@@ -952,6 +952,16 @@ fn test_basic_arithmetic() -> Result<(), CvsSqlError> {
         assert_eq!(*data.get("ten").unwrap_or(&-200.0), 10.0);
         assert_eq!(*data.get("nothing").unwrap_or(&-200.0), -100.0);
         assert_eq!(*data.get("more_nothing").unwrap_or(&-200.0), -100.0);
+        assert_eq!(*data.get("three").unwrap_or(&-200.0), 3.0);
+        assert_eq!(*data.get("minus_two").unwrap_or(&-200.0), -2.0);
+        assert_eq!(*data.get("mod_two").unwrap_or(&-200.0), 2.0);
+        assert_eq!(*data.get("by_zero").unwrap_or(&-200.0), -100.0);
+        assert_eq!(*data.get("by_null").unwrap_or(&-200.0), -100.0);
+        assert_eq!(*data.get("div_by_zero").unwrap_or(&-200.0), -100.0);
+        assert_eq!(*data.get("div_by_null").unwrap_or(&-200.0), -100.0);
+        assert_eq!(*data.get("rem_by_zero").unwrap_or(&-200.0), -100.0);
+        assert_eq!(*data.get("mod_by_zero").unwrap_or(&-200.0), -100.0);
+        assert_eq!(*data.get("mod_by_null").unwrap_or(&-200.0), -100.0);
     }
 
     Ok(())
@@ -1032,9 +1042,12 @@ fn test_comparisons() -> Result<(), CvsSqlError> {
         };
         assert_eq!(value, &expected_value);
 
-        let timestamp = sale
-            .delivered_at
-            .unwrap_or(DateTime::from_timestamp_nanos(0).naive_utc());
+        let Some(timestamp) = sale.delivered_at else {
+            for name in ["lt", "gt", "eq", "lteq", "gteq", "neq"] {
+                assert_eq!(results.value(&name.into(), row), &Value::Empty);
+            }
+            continue;
+        };
 
         let lt = results.value(&"lt".into(), row);
         assert_eq!(&Value::Bool(timestamp < reference), lt);
@@ -1230,7 +1243,7 @@ fn test_select_with_order_by() -> Result<(), CvsSqlError> {
     for data in get_sales() {
         expected_rows.push((data.id, data.sale_made));
     }
-    expected_rows.sort_by(|left, right| left.1.cmp(&right.1));
+    expected_rows.sort_by_key(|row| row.1);
 
     for (index, row) in results.data.iter().enumerate() {
         let id = results.value(&"id".into(), row).to_string();
@@ -1260,7 +1273,7 @@ fn test_select_with_order_by_desc() -> Result<(), CvsSqlError> {
     for data in get_sales() {
         expected_rows.push((data.id, data.sale_made));
     }
-    expected_rows.sort_by(|left, right| right.1.cmp(&left.1));
+    expected_rows.sort_by_key(|row| std::cmp::Reverse(row.1));
 
     for (index, row) in results.data.iter().enumerate() {
         let id = results.value(&"id".into(), row).to_string();
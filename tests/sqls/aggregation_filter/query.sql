@@ -0,0 +1,3 @@
+SELECT country, COUNT(*) AS customers, COUNT(*) FILTER (WHERE active) AS active, COUNT_IF(active) AS also_active, COUNT_IF(NOT active) AS not_active FROM tests.data.customers GROUP BY country ORDER BY country;
+SELECT "customer id", SUM(price) AS total, SUM(price) FILTER (WHERE price > 300) AS expensive, MAX(price) FILTER (WHERE price < 100) AS max_cheap, COUNT(*) FILTER (WHERE "delivery cost" < 0.5) AS cheap_delivery FROM tests.data.sales GROUP BY "customer id" ORDER BY "customer id";
+SELECT COUNT(*) FILTER (WHERE active), COUNT_IF(active) AS active FROM tests.data.customers;
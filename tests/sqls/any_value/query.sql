@@ -0,0 +1,2 @@
+SELECT ANY_VALUE(zip) AS zip, ARBITRARY(city) AS city FROM (SELECT * FROM tests.data.zip_codes ORDER BY id DESC);
+SELECT ANY_VALUE(opened) AS opened FROM tests.data.zip_codes WHERE zip IS NULL;
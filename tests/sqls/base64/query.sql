@@ -0,0 +1,2 @@
+SELECT TO_BASE64('hello') AS encoded, FROM_BASE64(TO_BASE64('hello')) AS decoded, FROM_BASE64(TO_BASE64('hello')) = 'hello' AS same, FROM_BASE64('not base64') AS invalid FROM tests.data.artists LIMIT 1;
+SELECT company, TO_BASE64(company) AS encoded, FROM_BASE64(TO_BASE64(company)) AS decoded FROM tests.data.customers ORDER BY company;
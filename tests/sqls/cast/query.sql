@@ -6,4 +6,9 @@ SELECT TRY_CAST('1002' AS INT) FROM tests.data.dates;
 
 SELECT TRY_CAST('2025-01-21' AS DATE) FROM tests.data.dates;
 
-SELECT TRY_CAST('2025-01-21 11:20:01' AS TIMESTAMP) FROM tests.data.dates;
\ No newline at end of file
+SELECT TRY_CAST('2025-01-21 11:20:01' AS TIMESTAMP) FROM tests.data.dates;
+SELECT CAST(0 AS BOOLEAN), CAST(2.5 AS BOOLEAN), CAST(-1 AS BOOL), CAST(1 = 1 AS BOOLEAN), CAST(NULL AS BOOLEAN) FROM tests.data.dates LIMIT 1;
+
+SELECT CAST('true' AS BOOLEAN), CAST('False' AS BOOLEAN), CAST('1' AS BOOLEAN), CAST('0' AS BOOLEAN), CAST('YES' AS BOOLEAN), CAST('no' AS BOOLEAN), CAST('maybe' AS BOOLEAN) FROM tests.data.dates LIMIT 1;
+
+SELECT amount, CAST(amount - 7.21 AS BOOLEAN) FROM tests.data.dates LIMIT 2;
@@ -0,0 +1,4 @@
+SELECT id, COLUMNS('price|cost') FROM tests.data.sales WHERE "tax percentage" > 19.5;
+SELECT SUM(COLUMNS('price|cost')) FROM tests.data.sales;
+SELECT "customer id", MAX(COLUMNS('^(price|delivery cost)$')) FROM tests.data.sales GROUP BY "customer id" ORDER BY "customer id";
+SELECT COLUMNS('^a') FROM tests.data.artists, tests.data.albums WHERE artists.artist_id = albums.artist_id AND album_id < 3;
@@ -0,0 +1 @@
+SELECT id, DATE_ADD("sale made", 1, MONTH) AS next_month, DATE_SUB("delivered at", 2, 'HOUR') AS before FROM tests.data.sales ORDER BY id LIMIT 10;
@@ -0,0 +1,2 @@
+SELECT id, DATEDIFF("delivered at", "sale made") AS days FROM tests.data.sales ORDER BY id;
+SELECT id, TIMESTAMPDIFF(HOUR, "sale made", "delivered at") AS hours, TIMESTAMPDIFF('minute', "sale made", "delivered at") AS minutes FROM tests.data.sales ORDER BY id LIMIT 10;
@@ -0,0 +1,7 @@
+SELECT name FROM tests.data.customers WHERE EXISTS (SELECT 1 FROM tests.data.sales WHERE price > 500) ORDER BY name LIMIT 3;
+
+SELECT COUNT(*) FROM tests.data.customers WHERE EXISTS (SELECT 1 FROM tests.data.sales WHERE price > 10000);
+
+SELECT COUNT(*) FROM tests.data.customers WHERE NOT EXISTS (SELECT 1 FROM tests.data.sales WHERE price > 10000);
+
+SELECT name, EXISTS (SELECT * FROM tests.data.albums WHERE artist_id = 4) AS has_albums FROM tests.data.artists WHERE artist_id = 1;
@@ -15,4 +15,9 @@ SELECT EXTRACT(day FROM amount), EXTRACT(day FROM amount) AS should_be_empty_two
 SELECT EXTRACT(milliseconds FROM amount), EXTRACT(milliseconds FROM amount) FROM tests.data.dates ORDER BY amount;
 SELECT EXTRACT(microseconds FROM amount), EXTRACT(microseconds FROM amount) FROM tests.data.dates ORDER BY amount;
 SELECT EXTRACT(second FROM amount), EXTRACT(second FROM amount) FROM tests.data.dates ORDER BY amount;
-SELECT EXTRACT(epoch FROM amount), EXTRACT(epoch FROM amount) FROM tests.data.dates ORDER BY amount;
\ No newline at end of file
+SELECT EXTRACT(epoch FROM amount), EXTRACT(epoch FROM amount) FROM tests.data.dates ORDER BY amount;
+SELECT EXTRACT(year FROM dt) AS year_from_date, EXTRACT(month FROM ts) AS month_from_timestamp FROM tests.data.dates ORDER BY amount;
+SELECT YEAR(dt), MONTH(dt), DAY(dt), YEAR(ts), MONTH(ts), DAY(ts), HOUR(ts), MINUTE(ts), SECOND(ts) FROM tests.data.dates ORDER BY amount;
+SELECT name, YEAR("last modified") FROM tests.data.customers;
+SELECT id, "sale made", QUARTER("sale made") AS quarter, DAYOFYEAR("sale made") AS day_of_year, DAYOFWEEK("sale made") AS day_of_week, DAYNAME("sale made") AS day_name, MONTHNAME("sale made") AS month_name, EXTRACT(QUARTER FROM "delivered at") AS delivery_quarter FROM tests.data.sales ORDER BY id LIMIT 10;
+SELECT MONTHNAME('2024-01-13') AS month_name, QUARTER('2024-08-28') AS quarter FROM tests.data.artists LIMIT 1;
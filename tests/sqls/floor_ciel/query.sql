@@ -1 +1,3 @@
 SELECT amount, FLOOR(amount), CEIL(amount) FROM tests.data.dates;
+SELECT amount, FLOOR(amount, 1), CEIL(amount, 1), FLOOR(amount, -1), CEIL(amount, -1) FROM tests.data.dates;
+SELECT CEIL(1.234, 2), FLOOR(1.239, 2), CEIL(-1.234, 2), FLOOR(1234.5, -2), CEIL(1234.5, -2), CEIL(1.5, 0) FROM tests.data.dates LIMIT 1;
@@ -0,0 +1,5 @@
+SELECT * FROM GENERATE_SERIES(1, 5);
+SELECT value FROM GENERATE_SERIES(10, 0, -2.5);
+SELECT * FROM GENERATE_SERIES('2024-02-27', '2024-03-02');
+SELECT weeks.value AS week_start FROM GENERATE_SERIES('2024-01-01', '2024-02-01', INTERVAL '1 week') AS weeks;
+SELECT days.value AS day, dates.amount FROM GENERATE_SERIES('2024-10-05', '2024-10-10') AS days LEFT JOIN tests.data.dates AS dates ON days.value = dates.dt ORDER BY days.value;
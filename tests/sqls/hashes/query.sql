@@ -0,0 +1,2 @@
+SELECT MD5('abc') AS md5, SHA1('abc') AS sha1, SHA256('abc') AS sha256 FROM tests.data.artists LIMIT 1;
+SELECT email, MD5(email) AS key, SHA256(CONCAT(name, '|', email)) AS long_key, MD5(active) AS not_a_string FROM tests.data.customers ORDER BY email;
@@ -0,0 +1,3 @@
+SELECT * FROM tests.data."customer_ids.list";
+SELECT "customer id", COUNT(*) FROM tests.data.sales WHERE "customer id" IN (SELECT * FROM tests.data."customer_ids.list") GROUP BY "customer id" ORDER BY "customer id";
+SELECT COUNT(*) FROM tests.data.sales WHERE "customer id" NOT IN (SELECT A$ FROM tests.data."customer_ids.list");
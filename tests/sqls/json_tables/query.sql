@@ -0,0 +1,4 @@
+SELECT * FROM tests.data.pets;
+SELECT name, age FROM tests.data.pets WHERE type = 'cat' AND vaccinated ORDER BY age;
+SELECT type, COUNT(*), MAX(age) FROM tests.data.pets GROUP BY type ORDER BY type;
+SELECT name FROM tests.data.pets WHERE age IS NULL;
@@ -0,0 +1,4 @@
+SELECT "customer id", "sale made", LAG("sale made") OVER (PARTITION BY "customer id" ORDER BY "sale made") AS previous, TIMESTAMPDIFF(DAY, LAG("sale made") OVER (PARTITION BY "customer id" ORDER BY "sale made"), "sale made") AS days_since_previous FROM tests.data.sales ORDER BY "customer id", "sale made";
+SELECT id, price, LEAD(price) OVER (ORDER BY price) AS next_price, LEAD(price, 2, 0) OVER (ORDER BY price) AS second_next, LAG(price, 0) OVER (ORDER BY price) AS same FROM tests.data.sales ORDER BY price DESC LIMIT 4;
+SELECT "customer id", price, LAG(price, 1, price) OVER (PARTITION BY "customer id" ORDER BY "sale made") AS previous_or_current FROM tests.data.sales WHERE "customer id" = -8862786196595644070 ORDER BY "sale made";
+SELECT id, "delivered at", LAG("delivered at") IGNORE NULLS OVER (ORDER BY id) AS previous_delivered, LAG("delivered at") RESPECT NULLS OVER (ORDER BY id) AS previous, LEAD("delivered at", 2) IGNORE NULLS OVER (ORDER BY id) AS second_next_delivered FROM tests.data.sales ORDER BY id LIMIT 8;
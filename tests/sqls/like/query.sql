@@ -0,0 +1,11 @@
+SELECT name FROM tests.data.artists WHERE name LIKE 'A%';
+SELECT name, name LIKE '%s_%' AS has_s FROM tests.data.artists;
+SELECT email FROM tests.data.customers WHERE email LIKE ANY ('%@example.com', '%@example.org') ORDER BY email LIMIT 20;
+SELECT COUNT(*) FROM tests.data.customers WHERE email LIKE ANY ('%@example.com', '%@example.org');
+SELECT COUNT(*) FROM tests.data.customers WHERE email NOT LIKE '%@example.com' AND email NOT LIKE '%@example.org';
+SELECT name FROM tests.data.artists WHERE name LIKE ALL ('A%', '%s%');
+SELECT company FROM tests.data.customers WHERE company LIKE '%Inc' ORDER BY company;
+SELECT company FROM tests.data.customers WHERE company ILIKE '%INC' ORDER BY company;
+SELECT COUNT(*) FROM tests.data.customers WHERE company NOT ILIKE '%inc';
+SELECT name FROM tests.data.artists WHERE name ILIKE ANY ('ac%', '%SMITH');
+SELECT name, '100%' LIKE '100!%' ESCAPE '!' AS exact, '1000' LIKE '100!%' ESCAPE '!' AS other, 'a_b' ILIKE 'A\_B' ESCAPE '\' AS underscore FROM tests.data.artists WHERE artist_id = 1;
@@ -5,4 +5,7 @@ SELECT * FROM tests.data.numbers OFFSET 1 limit 2;
 SELECT * FROM tests.data.numbers OFFSET 5 limit 2;
 SELECT * FROM tests.data.numbers OFFSET 0 limit 2;
 SELECT * FROM tests.data.numbers OFFSET 20 limit 2;
-SELECT * FROM tests.data.numbers OFFSET 20 limit 20;
\ No newline at end of file
+SELECT * FROM tests.data.numbers OFFSET 20 limit 20;
+SELECT * FROM tests.data.numbers OFFSET 8;
+SELECT * FROM tests.data.numbers OFFSET 20;
+SELECT * FROM tests.data.numbers ORDER BY row DESC OFFSET 9 ROWS;
\ No newline at end of file
@@ -0,0 +1,3 @@
+SELECT active, LISTAGG(name, ', ') WITHIN GROUP (ORDER BY name) AS names FROM tests.data.customers GROUP BY active ORDER BY active;
+SELECT "customer id", LISTAGG(price, ';') WITHIN GROUP (ORDER BY price DESC) AS prices, COUNT(*) FROM tests.data.sales GROUP BY "customer id" ORDER BY "customer id";
+SELECT LISTAGG(DISTINCT "customer id", ',') WITHIN GROUP (ORDER BY "customer id") FROM tests.data.sales;
@@ -0,0 +1,24 @@
+CREATE TEMPORARY TABLE archive(id TEXT, price FLOAT, "customer id" BIGINT);
+
+INSERT INTO archive SELECT id, 0, "customer id" FROM tests.data.sales WHERE price > 450;
+INSERT INTO archive VALUES('old', 1, 2);
+
+MERGE INTO archive AS a
+USING (SELECT id, price, "customer id" FROM tests.data.sales WHERE price > 400) AS s
+ON a.id = s.id
+WHEN MATCHED THEN UPDATE SET price = s.price
+WHEN NOT MATCHED THEN INSERT (id, price, "customer id") VALUES (s.id, s.price, s."customer id");
+
+SELECT * FROM archive ORDER BY id;
+
+MERGE INTO archive
+USING (SELECT id FROM archive WHERE price = 1) AS s
+ON archive.id = s.id
+WHEN MATCHED THEN DELETE;
+
+MERGE INTO archive
+USING tests.data.sales AS s
+ON archive.id = s.id
+WHEN NOT MATCHED THEN INSERT (id, price) VALUES (s.id, s.price * 2);
+
+SELECT COUNT(*), SUM(price) FROM archive;
@@ -0,0 +1,3 @@
+SELECT TRUE < 1 AS bool_lt_number, 1 > 'a' AS number_gt_string, 'a' <= TRUE AS string_lteq_bool, 1 >= 1 AS same_type, 1 = 'a' AS number_eq_string, 1 <> 'a' AS number_neq_string FROM tests.data.artists LIMIT 1;
+SELECT 1 = 1 = 1 AS chained_number, 1 = 1 = TRUE AS chained_bool, 2 < 3 < 4 AS chained_lt FROM tests.data.artists LIMIT 1;
+SELECT dt, ts, dt < ts AS date_lt_timestamp, ts >= dt AS timestamp_gteq_date FROM tests.data.dates ORDER BY dt LIMIT 3;
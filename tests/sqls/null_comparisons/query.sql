@@ -0,0 +1,5 @@
+SELECT NULL = NULL AS eq, NULL <> 1 AS neq, 1 < NULL AS lt, NULL > 1 AS gt, NULL <= 1 AS lteq, 1 >= NULL AS gteq FROM tests.data.artists LIMIT 1;
+SELECT id, zip, zip = 10001 AS is_ny, zip <> 10001 AS not_ny FROM tests.data.zip_codes;
+SELECT id FROM tests.data.zip_codes WHERE zip <> 10001;
+SELECT id FROM tests.data.zip_codes WHERE NOT zip = 10001;
+SELECT NULL = 1 AND FALSE AS and_false, NULL = 1 AND TRUE AS and_true, NULL = 1 OR TRUE AS or_true, NULL = 1 OR FALSE AS or_false FROM tests.data.artists LIMIT 1;
@@ -0,0 +1,3 @@
+SELECT "customer id", SUM(price) FROM tests.data.sales GROUP BY "customer id" ORDER BY SUM(price) DESC;
+SELECT "customer id", COUNT(*) AS sales FROM tests.data.sales GROUP BY "customer id" ORDER BY sales DESC, "customer id";
+SELECT "customer id", MAX(price) AS max_price FROM tests.data.sales GROUP BY "customer id" HAVING COUNT(*) > 4 ORDER BY max_price;
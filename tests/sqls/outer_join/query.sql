@@ -0,0 +1,26 @@
+CREATE TEMPORARY TABLE orders AS SELECT id, price, "customer id" FROM tests.data.sales WHERE price > 550;
+
+INSERT INTO orders VALUES ('missing-customer', 12.5, 42);
+
+SELECT orders.id, orders.price, customers.name
+FROM orders
+LEFT JOIN tests.data.customers AS customers
+ON orders."customer id" = customers.id;
+
+SELECT orders.id, customers.id, customers.name
+FROM orders
+RIGHT JOIN tests.data.customers AS customers
+ON orders."customer id" = customers.id;
+
+SELECT orders.id, orders."customer id", customers.name
+FROM orders
+FULL OUTER JOIN tests.data.customers AS customers
+ON orders."customer id" = customers.id;
+
+SELECT orders.id, customers.name
+FROM orders
+JOIN tests.data.customers AS customers
+ON orders."customer id" = customers.id;
+
+SELECT COUNT(*)
+FROM orders, tests.data.customers;
@@ -0,0 +1,2 @@
+SELECT CONCAT(RPAD(name, 20, '.'), LPAD(country, 15, ' ')) AS line, CONCAT('[', SPACE(3), ']') AS spaces FROM tests.data.customers ORDER BY name;
+SELECT LPAD('héllo', 8, '·') AS left_padded, RPAD('héllo', 8, '·') AS right_padded, LPAD('héllo', 2, '·') AS cut FROM tests.data.artists LIMIT 1;
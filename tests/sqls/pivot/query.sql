@@ -0,0 +1,3 @@
+SELECT * FROM (SELECT "customer id", SUBSTRING(CAST("sale made" AS TEXT) FOR 7) AS month, id FROM tests.data.sales) PIVOT (COUNT(id) FOR month IN (ANY)) ORDER BY "customer id";
+SELECT * FROM (SELECT "customer id", SUBSTRING(CAST("sale made" AS TEXT) FOR 7) AS month, price FROM tests.data.sales) PIVOT (SUM(price) FOR month IN ('2024-09' AS september, '2024-10' AS october)) AS p ORDER BY p."customer id";
+SELECT * FROM (SELECT "customer id", SUBSTRING(CAST("sale made" AS TEXT) FOR 7) AS month, price FROM tests.data.sales) PIVOT (SUM(price) AS total, COUNT(*) AS sales FOR month IN ('2024-09', '2024-10') DEFAULT ON NULL (0)) ORDER BY "customer id";
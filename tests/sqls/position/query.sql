@@ -1 +1,2 @@
 SELECT company,  POSITION('Son' IN company)  FROM tests.data.customers;
+SELECT company, INSTR(company, 'Son') AS instr, LOCATE('Son', company) AS locate, CHARINDEX('Son', company) AS charindex FROM tests.data.customers;
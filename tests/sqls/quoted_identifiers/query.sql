@@ -0,0 +1,8 @@
+SELECT "say ""hi""", "a, b" FROM tests.data.odd_headers;
+SELECT odd_headers."say ""hi""" AS "greeting ""quoted""" FROM tests.data.odd_headers WHERE "a, b" = 'y';
+SELECT "say ""hi""", COUNT(*) AS count FROM tests.data.odd_headers GROUP BY "say ""hi""" ORDER BY "say ""hi""" DESC;
+SELECT a.key, b.plain FROM tests.data.odd_headers AS a JOIN tests.data.odd_headers AS b USING ("a, b");
+CREATE TEMPORARY TABLE quoted ("x ""q""" TEXT, "y,z" TEXT);
+INSERT INTO quoted ("x ""q""", "y,z") VALUES ('a', 'b');
+UPDATE quoted SET "y,z" = 'c' WHERE "x ""q""" = 'a';
+SELECT "x ""q""", "y,z" FROM quoted;
@@ -0,0 +1,2 @@
+SELECT HEX(255) AS hex, BIN(5) AS bin, OCT(8) AS oct, UNHEX('68656C6C6F') AS unhex, UNHEX('4142') AS digits_only, HEX(1.5) AS not_an_integer FROM tests.data.artists LIMIT 1;
+SELECT artist_id, HEX(artist_id * 1000) AS hex, BIN(artist_id) AS bin, OCT(artist_id * 100) AS oct FROM tests.data.artists ORDER BY artist_id;
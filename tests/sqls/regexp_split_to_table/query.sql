@@ -0,0 +1,5 @@
+SELECT * FROM REGEXP_SPLIT_TO_TABLE('one two  three', '\s+');
+SELECT tokens.value AS token FROM REGEXP_SPLIT_TO_TABLE('a,b,,c', ',') AS tokens;
+SELECT COUNT(*) AS words FROM REGEXP_SPLIT_TO_TABLE('the quick brown fox', ' ');
+SELECT artists.artist_id, words.value AS word FROM tests.data.artists, REGEXP_SPLIT_TO_TABLE(artists.name, '\s+') AS words ORDER BY artists.artist_id, word;
+SELECT name, COUNT(*) AS words FROM tests.data.artists, REGEXP_SPLIT_TO_TABLE(artists.name, '\s+') GROUP BY name ORDER BY words DESC, name;
@@ -0,0 +1,4 @@
+SELECT country, COUNT(*), SUM(price) FROM tests.data.sales AS sale JOIN tests.data.customers AS customer ON sale."customer id" = customer.id GROUP BY ROLLUP (country) ORDER BY country;
+SELECT country, active, COUNT(*) FROM tests.data.customers GROUP BY country, active WITH ROLLUP ORDER BY country, active;
+SELECT active, country, COUNT(*) FROM tests.data.customers WHERE country < 'N' GROUP BY CUBE (active, country) ORDER BY active, country;
+SELECT country, active, COUNT(*) FROM tests.data.customers GROUP BY GROUPING SETS ((country), (active), ()) ORDER BY country, active;
@@ -0,0 +1,5 @@
+SELECT "customer id", id, "sale made", ROW_NUMBER() OVER (PARTITION BY "customer id" ORDER BY "sale made") AS rn FROM tests.data.sales ORDER BY "customer id", rn LIMIT 20;
+SELECT id, price, ROW_NUMBER() OVER (ORDER BY price DESC) AS place FROM tests.data.sales ORDER BY place LIMIT 5;
+SELECT "customer id", COUNT(*) AS sales, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, "customer id") AS place FROM tests.data.sales GROUP BY "customer id" ORDER BY place LIMIT 5;
+SELECT id, ROW_NUMBER() OVER (PARTITION BY "customer id" ORDER BY price) * 10 AS tens FROM tests.data.sales WHERE "customer id" = -8862786196595644070 ORDER BY tens;
+SELECT * FROM (SELECT id, ROW_NUMBER() OVER (PARTITION BY "customer id" ORDER BY "sale made" DESC) AS rn FROM tests.data.sales) AS numbered WHERE rn = 1 ORDER BY id LIMIT 5;
@@ -0,0 +1,13 @@
+SELECT name, (SELECT MAX(price) FROM tests.data.sales) AS top FROM tests.data.artists WHERE artist_id = 1;
+
+SELECT id, price, ROUND(price / (SELECT MAX(price) FROM tests.data.sales), 4) AS ratio
+FROM tests.data.sales
+WHERE price > (SELECT AVG(price) FROM tests.data.sales) + 200
+ORDER BY price DESC;
+
+SELECT name, (SELECT title FROM tests.data.albums WHERE album_id = 2) AS album
+FROM tests.data.artists;
+
+SELECT name, (SELECT title FROM tests.data.albums WHERE album_id = 100) AS missing
+FROM tests.data.artists
+WHERE artist_id = 1;
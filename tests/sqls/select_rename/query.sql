@@ -0,0 +1,2 @@
+SELECT * RENAME ("customer id" AS customer_id, "delivery cost" AS delivery_cost) FROM tests.data.sales ORDER BY id LIMIT 5;
+SELECT customer_id, COUNT(*) FROM (SELECT * RENAME "customer id" AS customer_id FROM tests.data.sales) GROUP BY customer_id ORDER BY customer_id;
@@ -0,0 +1,2 @@
+SELECT id, "sale made", STR_TO_DATE(DATE_FORMAT("sale made", '%d/%m/%Y %H:%M:%S'), '%d/%m/%Y %H:%M:%S') AS same, TO_DATE(DATE_FORMAT("sale made", '%d/%m/%Y'), '%d/%m/%Y') AS sale_date, TO_DATE(id, '%d/%m/%Y') AS not_a_date FROM tests.data.sales ORDER BY id LIMIT 10;
+SELECT COUNT(*) AS count FROM tests.data.sales WHERE STR_TO_DATE(DATE_FORMAT("sale made", '%Y %j %H:%M:%S'), '%Y %j %H:%M:%S') = "sale made";
@@ -0,0 +1,2 @@
+SELECT email, SUBSTRING_INDEX(email, '@', 1) AS user_name, SUBSTRING_INDEX(email, '.', -1) AS top_level_domain, SUBSTRING_INDEX(name, ' ', -1) AS last_name FROM tests.data.customers ORDER BY email;
+SELECT SUBSTRING_INDEX('a.b.c.d', '.', 2) AS left_side, SUBSTRING_INDEX('a.b.c.d', '.', -2) AS right_side FROM tests.data.artists LIMIT 1;
@@ -0,0 +1,3 @@
+SELECT TO_JSON(artists) AS json FROM tests.data.artists;
+SELECT TO_JSON(albums) AS album, artists.name FROM tests.data.artists JOIN tests.data.albums ON artists.artist_id = albums.artist_id ORDER BY albums.album_id LIMIT 3;
+SELECT TO_JSON(*) AS json FROM tests.data.dates ORDER BY amount LIMIT 2;
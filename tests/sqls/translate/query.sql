@@ -0,0 +1,3 @@
+SELECT company, TRANSLATE(company, 'aeiou', 'AEIOU') AS upper_vowels, TRANSLATE(company, ' -', '_') AS no_dashes, TRANSLATE(id, '-', '') AS not_a_string FROM tests.data.customers;
+SELECT TRANSLATE('a1b2c3', 'abc', 'xy') AS translated FROM tests.data.artists LIMIT 1;
+SELECT TRANSLATE('12345', '143', 'ax') AS translated FROM tests.data.artists LIMIT 1;
@@ -0,0 +1,3 @@
+SELECT TRIM(LEADING 'x' FROM 'xxhello') AS leading, TRIM(LEADING 'x' FROM 'xxhello') = 'hello' AS same FROM tests.data.artists LIMIT 1;
+SELECT TRIM('  hello  ') AS both_sides, TRIM(TRAILING ' ' FROM '  hello  ') AS trailing, TRIM(BOTH 'xy' FROM 'xyxhelloyyx') AS characters, TRIM('x' FROM 'xhellox') AS default_side FROM tests.data.artists LIMIT 1;
+SELECT name, TRIM(TRAILING 'aeiou' FROM name) AS no_vowels_at_end, TRIM(artist_id) AS not_a_string FROM tests.data.artists ORDER BY name;
@@ -0,0 +1,2 @@
+SELECT UNIX_TIMESTAMP(TO_TIMESTAMP(1400234525)) AS round_trip, UNIX_TIMESTAMP(TO_TIMESTAMP(1400234525)) = 1400234525 AS same FROM tests.data.artists LIMIT 1;
+SELECT id, "sale made", UNIX_TIMESTAMP("sale made") AS epoch, FROM_UNIXTIME(UNIX_TIMESTAMP("delivered at")) AS delivered FROM tests.data.sales ORDER BY id LIMIT 10;
@@ -0,0 +1,26 @@
+CREATE TEMPORARY TABLE buyers AS SELECT "customer id" AS id, SUM(price) AS total FROM tests.data.sales GROUP BY "customer id" ORDER BY "customer id";
+
+INSERT INTO buyers VALUES (42, 12.5);
+
+SELECT *
+FROM tests.data.customers
+JOIN buyers
+USING (id);
+
+SELECT *
+FROM buyers
+NATURAL JOIN tests.data.customers;
+
+SELECT id, buyers.id, customers.id, customers.name, buyers.total
+FROM tests.data.customers AS customers
+FULL OUTER JOIN buyers
+USING (id);
+
+SELECT *
+FROM tests.data.artists
+NATURAL JOIN tests.data.albums;
+
+SELECT *
+FROM tests.data.artists AS artist
+JOIN tests.data.albums AS album USING (artist_id)
+JOIN tests.data.albums AS other USING (artist_id, album_id);
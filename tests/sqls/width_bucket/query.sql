@@ -0,0 +1,2 @@
+SELECT price, WIDTH_BUCKET(price, 100, 500, 4), WIDTH_BUCKET(price, 500, 100, 4) FROM tests.data.sales ORDER BY price;
+SELECT bucket, COUNT(*) AS sales FROM (SELECT WIDTH_BUCKET(price, 0, 600, 6) AS bucket FROM tests.data.sales) GROUP BY bucket ORDER BY bucket;
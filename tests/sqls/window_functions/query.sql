@@ -0,0 +1,5 @@
+SELECT "customer id", "sale made", price, SUM(price) OVER (PARTITION BY "customer id" ORDER BY "sale made") AS revenue FROM tests.data.sales ORDER BY "customer id", "sale made";
+WITH running AS (SELECT "customer id", "sale made", SUM(price) OVER (PARTITION BY "customer id" ORDER BY "sale made") AS revenue, ROW_NUMBER() OVER (PARTITION BY "customer id" ORDER BY "sale made" DESC) AS from_last FROM tests.data.sales), totals AS (SELECT "customer id", SUM(price) AS total FROM tests.data.sales GROUP BY "customer id") SELECT running."customer id", running.revenue, totals.total, running.revenue = totals.total AS same FROM running JOIN totals ON running."customer id" = totals."customer id" WHERE running.from_last = 1 ORDER BY running."customer id";
+SELECT "customer id", COUNT(*) AS sales, RANK() OVER (ORDER BY COUNT(*) DESC) AS rank, DENSE_RANK() OVER (ORDER BY COUNT(*) DESC) AS dense_rank, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, "customer id") AS row_number FROM tests.data.sales GROUP BY "customer id" ORDER BY row_number;
+SELECT "customer id", price, SUM(price) OVER (PARTITION BY "customer id") AS total, SUM(price) OVER () AS everything FROM tests.data.sales WHERE "customer id" = -8862786196595644070 ORDER BY price;
+SELECT "customer id", price, SUM(price) FILTER (WHERE price > 300) OVER (PARTITION BY "customer id") AS expensive, SUM(price) FILTER (WHERE price > 300) OVER (PARTITION BY "customer id" ORDER BY price) AS running_expensive FROM tests.data.sales WHERE "customer id" = -8862786196595644070 ORDER BY price;
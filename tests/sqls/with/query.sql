@@ -0,0 +1,18 @@
+WITH big AS (SELECT * FROM tests.data.sales WHERE price > 400) SELECT COUNT(*) FROM big;
+
+SELECT COUNT(*) FROM tests.data.sales WHERE price > 400;
+
+WITH big AS (SELECT * FROM tests.data.sales WHERE price > 400),
+    huge AS (SELECT id, price FROM big WHERE price > 500)
+SELECT huge.id, huge.price FROM huge ORDER BY huge.price DESC;
+
+WITH totals AS (SELECT "customer id", SUM(price) AS total FROM tests.data.sales GROUP BY "customer id")
+SELECT c.name, t.total
+FROM tests.data.customers AS c
+JOIN totals AS t ON c.id = t."customer id"
+ORDER BY t.total DESC
+LIMIT 3;
+
+WITH artists AS (SELECT * FROM tests.data.artists WHERE artist_id = 1)
+SELECT o.other, artists.name
+FROM (WITH artists AS (SELECT * FROM tests.data.artists WHERE artist_id = 2) SELECT name AS other FROM artists) AS o, artists;